@@ -0,0 +1,202 @@
+//! 压缩包目录读取（--archive）：无需解压即可按条目列表生成层级结构
+
+use anyhow::Context;
+use chrono::{DateTime, Local, NaiveDateTime};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use crate::error::{bail, Result};
+use crate::flat::{build_tree, mode_string, FlatEntry};
+use crate::parser::TreeItem;
+
+/// 支持的压缩包格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// 按扩展名判断格式
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        let format = if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            ArchiveFormat::TarGz
+        } else if name.ends_with(".tar") {
+            ArchiveFormat::Tar
+        } else if [".zip", ".jar", ".war", ".apk", ".nupkg", ".whl"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+        {
+            ArchiveFormat::Zip
+        } else {
+            return None;
+        };
+        Some(format)
+    }
+}
+
+/// 压缩包读取器：读取条目列表（大小、修改时间、权限来自压缩包中记录的元数据）
+#[derive(Debug, Default)]
+pub struct ArchiveReader;
+
+impl ArchiveReader {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 读取压缩包，返回与TreeParser::parse相同结构的项目列表
+    pub fn read_file(&self, path: &Path, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let Some(format) = ArchiveFormat::from_path(path) else {
+            let name = path.to_string_lossy().to_lowercase();
+            if name.ends_with(".7z") || name.ends_with(".rar") {
+                bail!(
+                    "暂不支持直接读取7z/rar压缩包，请先保存列表输出再读入，如: 7z l {0} > list.txt 后使用 -f 7z -i list.txt（rar为 unrar l 及 -f rar）",
+                    path.display()
+                );
+            }
+            bail!(
+                "不支持的压缩包格式: {}（支持.zip、.tar、.tar.gz/.tgz）",
+                path.display()
+            );
+        };
+        let file =
+            File::open(path).with_context(|| format!("无法打开压缩包: {}", path.display()))?;
+        let entries = match format {
+            ArchiveFormat::Zip => self.zip_entries(file),
+            ArchiveFormat::Tar => self.tar_entries(BufReader::new(file)),
+            ArchiveFormat::TarGz => self.tar_entries(GzDecoder::new(BufReader::new(file))),
+        }
+        .with_context(|| format!("无法读取压缩包目录: {}", path.display()))?;
+        Ok(build_tree(entries, include_hidden))
+    }
+
+    /// zip中央目录中的条目（不解压内容）
+    fn zip_entries(&self, file: File) -> Result<Vec<FlatEntry>> {
+        let mut archive = zip::ZipArchive::new(BufReader::new(file))?;
+        let mut entries = Vec::with_capacity(archive.len());
+        for index in 0..archive.len() {
+            let entry = archive.by_index_raw(index)?;
+            let is_dir = entry.is_dir();
+            entries.push(FlatEntry {
+                size: (!is_dir).then(|| entry.size()),
+                modified: entry
+                    .last_modified()
+                    .and_then(|datetime| NaiveDateTime::try_from(datetime).ok()),
+                permissions: entry
+                    .unix_mode()
+                    .map(|mode| mode_string(if is_dir { 'd' } else { '-' }, mode)),
+                ..FlatEntry::new(entry.name())
+            });
+        }
+        Ok(entries)
+    }
+
+    /// tar（可为gzip压缩）中的条目，需顺序读过整个归档
+    fn tar_entries(&self, reader: impl Read) -> Result<Vec<FlatEntry>> {
+        let mut archive = tar::Archive::new(reader);
+        let mut entries = Vec::new();
+        for entry in archive.entries()? {
+            let entry = entry?;
+            let header = entry.header();
+            let path = entry.path()?.to_string_lossy().into_owned();
+            let entry_type = header.entry_type();
+            // pax扩展头、GNU长文件名等元数据条目不是文件
+            if !(entry_type.is_file()
+                || entry_type.is_dir()
+                || entry_type.is_symlink()
+                || entry_type.is_hard_link())
+            {
+                continue;
+            }
+            let type_char = match () {
+                _ if entry_type.is_dir() => 'd',
+                _ if entry_type.is_symlink() => 'l',
+                _ => '-',
+            };
+            entries.push(FlatEntry {
+                is_dir: entry_type.is_dir() || path.ends_with('/'),
+                size: (!entry_type.is_dir()).then(|| header.size().ok()).flatten(),
+                modified: header
+                    .mtime()
+                    .ok()
+                    .and_then(|seconds| DateTime::from_timestamp(seconds as i64, 0))
+                    .map(|datetime| datetime.with_timezone(&Local).naive_local()),
+                permissions: header.mode().ok().map(|mode| mode_string(type_char, mode)),
+                owner: header.username().ok().flatten().map(str::to_string),
+                group: header.groupname().ok().flatten().map(str::to_string),
+                link_target: entry_type
+                    .is_symlink()
+                    .then(|| entry.link_name().ok().flatten())
+                    .flatten()
+                    .map(|target| target.to_string_lossy().into_owned()),
+                ..FlatEntry::new(&path)
+            });
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn test_read_archives() {
+        let dir = std::env::temp_dir().join("tree_to_excel_archive_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let zip_path = dir.join("release.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let options = SimpleFileOptions::default().unix_permissions(0o644);
+        writer.start_file("docs/guide.md", options).unwrap();
+        writer.write_all(b"# guide\n").unwrap();
+        writer.start_file("README.md", options).unwrap();
+        writer.write_all(b"hello").unwrap();
+        writer.finish().unwrap();
+
+        let tar_path = dir.join("release.tar");
+        let mut builder = tar::Builder::new(File::create(&tar_path).unwrap());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(5);
+        header.set_mode(0o755);
+        header.set_mtime(0);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "bin/run.sh", &b"#!/sh"[..])
+            .unwrap();
+        builder.finish().unwrap();
+        drop(builder);
+
+        let reader = ArchiveReader::new();
+        let zip_items = reader.read_file(&zip_path, false).unwrap();
+        let tar_items = reader.read_file(&tar_path, false).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let paths: Vec<_> = zip_items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.size))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                ("docs", None),
+                ("docs/guide.md", Some(8)),
+                ("README.md", Some(5)),
+                ("📊 统计: 1 directories, 2 files", None),
+            ]
+        );
+        assert_eq!(zip_items[1].permissions.as_deref(), Some("-rw-r--r--"));
+        assert!(zip_items[1].modified.is_some());
+
+        assert_eq!(tar_items[1].full_path, "bin/run.sh");
+        assert_eq!(tar_items[1].permissions.as_deref(), Some("-rwxr-xr-x"));
+        assert!(reader.read_file(Path::new("data.rar"), false).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+//! 可插拔的列：库的使用者实现ColumnProvider即可追加列（如Jira编号、按CSV映射的负责人）
+
+use crate::i18n::Language;
+use crate::output::{CellKind, CellValue};
+use crate::parser::TreeItem;
+
+/// 一列的表头、列宽、样式及每个项目的值
+pub trait ColumnProvider: Send + Sync {
+    /// 表头名称（也是--col-width中使用的列名，不区分大小写）
+    fn name(&self) -> &str;
+
+    /// 默认列宽（字符数）
+    fn width(&self) -> f64 {
+        20.0
+    }
+
+    /// 单元格的用途，输出后端据此选择样式
+    fn kind(&self) -> CellKind {
+        CellKind::Notes
+    }
+
+    /// 项目在该列的值（统计行不调用）
+    fn value(&self, item: &TreeItem) -> CellValue;
+}
+
+/// 完整路径列
+#[derive(Debug, Clone, Copy)]
+pub struct PathColumn {
+    lang: Language,
+}
+
+impl PathColumn {
+    pub fn new(lang: Language) -> Self {
+        Self { lang }
+    }
+}
+
+impl ColumnProvider for PathColumn {
+    fn name(&self) -> &str {
+        self.lang.text("完整路径", "Full Path")
+    }
+
+    fn width(&self) -> f64 {
+        60.0
+    }
+
+    fn kind(&self) -> CellKind {
+        CellKind::Path
+    }
+
+    fn value(&self, item: &TreeItem) -> CellValue {
+        CellValue::Text(item.full_path.clone())
+    }
+}
+
+/// 大小列（字节数，输入没有大小时为空）
+#[derive(Debug, Clone, Copy)]
+pub struct SizeColumn {
+    lang: Language,
+}
+
+impl SizeColumn {
+    pub fn new(lang: Language) -> Self {
+        Self { lang }
+    }
+}
+
+impl ColumnProvider for SizeColumn {
+    fn name(&self) -> &str {
+        self.lang.text("大小", "Size")
+    }
+
+    fn width(&self) -> f64 {
+        14.0
+    }
+
+    fn kind(&self) -> CellKind {
+        CellKind::Size
+    }
+
+    fn value(&self, item: &TreeItem) -> CellValue {
+        item.size
+            .map_or(CellValue::Empty, |size| CellValue::Number(size as f64))
+    }
+}
+
+/// 修改时间列（日期时间值，显示如 "2024-01-15 12:34"）
+#[derive(Debug, Clone, Copy)]
+pub struct ModifiedColumn {
+    lang: Language,
+}
+
+impl ModifiedColumn {
+    pub fn new(lang: Language) -> Self {
+        Self { lang }
+    }
+}
+
+impl ColumnProvider for ModifiedColumn {
+    fn name(&self) -> &str {
+        self.lang.text("修改时间", "Modified")
+    }
+
+    fn width(&self) -> f64 {
+        18.0
+    }
+
+    fn kind(&self) -> CellKind {
+        CellKind::Date
+    }
+
+    fn value(&self, item: &TreeItem) -> CellValue {
+        item.modified.map_or(CellValue::Empty, CellValue::DateTime)
+    }
+}
+
+/// 备注列：读取失败的项目写入错误，其余留空供填写
+#[derive(Debug, Clone, Copy)]
+pub struct NotesColumn {
+    lang: Language,
+}
+
+impl NotesColumn {
+    pub fn new(lang: Language) -> Self {
+        Self { lang }
+    }
+}
+
+impl ColumnProvider for NotesColumn {
+    fn name(&self) -> &str {
+        self.lang.text("备注", "Notes")
+    }
+
+    fn width(&self) -> f64 {
+        30.0
+    }
+
+    fn value(&self, item: &TreeItem) -> CellValue {
+        item.error.as_ref().map_or(CellValue::Empty, |error| {
+            CellValue::Text(format!("{}: {error}", self.lang.text("错误", "Error")))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::excel::ExcelGenerator;
+    use crate::output::{write_table, Cell, OutputWriter};
+    use crate::parser::TreeParser;
+    use calamine::{open_workbook, Reader, Xlsx};
+    use std::sync::Arc;
+
+    /// 按路径前缀对应的Jira编号
+    struct TicketColumn;
+
+    impl ColumnProvider for TicketColumn {
+        fn name(&self) -> &str {
+            "Jira"
+        }
+
+        fn value(&self, item: &TreeItem) -> CellValue {
+            match item.full_path.starts_with("src") {
+                true => CellValue::Text("PROJ-1".to_string()),
+                false => CellValue::Empty,
+            }
+        }
+    }
+
+    /// 记录写入内容的输出后端
+    #[derive(Default)]
+    struct Recorder {
+        headers: Vec<String>,
+        rows: Vec<Vec<Cell>>,
+    }
+
+    impl OutputWriter for Recorder {
+        fn write_header(&mut self, headers: &[String]) -> crate::Result<()> {
+            self.headers = headers.to_vec();
+            Ok(())
+        }
+
+        fn write_row(&mut self, cells: &[Cell]) -> crate::Result<()> {
+            self.rows.push(cells.to_vec());
+            Ok(())
+        }
+
+        fn merge(&mut self, _: usize, _: usize, _: usize, _: usize) -> crate::Result<()> {
+            Ok(())
+        }
+
+        fn finalize(&mut self) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_custom_column() {
+        let input = ".\n├── [        100]  src\n│   └── [         10]  a.rs\n└── [          5]  README.md\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+
+        let mut recorder = Recorder::default();
+        let columns: Vec<Arc<dyn ColumnProvider>> = vec![Arc::new(TicketColumn)];
+        write_table(&mut recorder, items.clone(), Language::Zh, &columns).unwrap();
+        assert_eq!(
+            recorder.headers,
+            ["L1", "L2", "完整路径", "大小", "Jira", "备注"]
+        );
+        assert_eq!(recorder.rows[1][3].value, CellValue::Number(10.0));
+        assert_eq!(
+            recorder.rows[1][4].value,
+            CellValue::Text("PROJ-1".to_string())
+        );
+        assert_eq!(recorder.rows[2][4].value, CellValue::Empty);
+
+        let path = std::env::temp_dir().join("tree_to_excel_custom_column.xlsx");
+        ExcelGenerator::builder()
+            .column(TicketColumn)
+            .col_widths("jira=12")
+            .build()
+            .unwrap()
+            .generate(items, path.to_str().unwrap())
+            .unwrap();
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        let range = workbook.worksheet_range_at(0).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let headers: Vec<String> = range
+            .rows()
+            .next()
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        // 内置列的表头同样来自PathColumn等
+        for name in ["完整路径", "大小", "备注"] {
+            assert!(headers.iter().any(|header| header == name), "{name}");
+        }
+        let jira = headers.iter().position(|header| header == "Jira").unwrap();
+        assert_eq!(range.get((2, jira)).unwrap().to_string(), "PROJ-1");
+    }
+}
@@ -0,0 +1,153 @@
+//! 配置文件（--config）：以命令行选项的长名称为键，保存团队共用的默认参数
+
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::{bail, Result};
+
+/// 当前目录下依次查找的配置文件名
+const LOCAL_NAMES: [&str; 3] = ["tree2excel.toml", "tree2excel.yaml", "tree2excel.yml"];
+
+/// 用户配置目录（如~/.config/tree-to-excel/）下依次查找的配置文件名
+const USER_NAMES: [&str; 3] = ["config.toml", "config.yaml", "config.yml"];
+
+/// 一个选项的值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigValue {
+    Flag(bool),          // 开关选项，如 include-hidden = true
+    Values(Vec<String>), // 带值的选项，数组对应可重复的选项，如 exclude = ["target", "node_modules"]
+}
+
+/// 配置文件中的原始值（TOML与YAML共用）
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    List(Vec<RawValue>),
+}
+
+impl RawValue {
+    /// 数组元素只能是字符串或数字
+    fn scalar(self) -> Option<String> {
+        match self {
+            RawValue::Integer(value) => Some(value.to_string()),
+            RawValue::Float(value) => Some(value.to_string()),
+            RawValue::Text(value) => Some(value),
+            RawValue::Bool(_) | RawValue::List(_) => None,
+        }
+    }
+}
+
+/// 已加载的配置文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub path: PathBuf,
+    pub options: BTreeMap<String, ConfigValue>, // 键为选项的长名称，如 "exclude-regex"
+}
+
+impl Config {
+    /// 查找配置文件：先查当前目录的tree2excel.toml/.yaml/.yml，
+    /// 再查用户配置目录（$XDG_CONFIG_HOME或~/.config，Windows为%APPDATA%）下的tree-to-excel/config.*
+    pub fn find() -> Option<PathBuf> {
+        let local = LOCAL_NAMES.iter().map(PathBuf::from);
+        let user_dir = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .map(|dir| dir.join("tree-to-excel"));
+        let user = user_dir
+            .into_iter()
+            .flat_map(|dir| USER_NAMES.iter().map(move |name| dir.join(name)));
+        local.chain(user).find(|path| path.is_file())
+    }
+
+    /// 读取配置文件，扩展名为.yaml/.yml时按YAML解析，否则按TOML解析
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("无法读取配置文件: {}", path.display()))?;
+        let yaml = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"));
+        let options = Self::parse(&content, yaml)
+            .with_context(|| format!("配置文件格式错误: {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            options,
+        })
+    }
+
+    /// 解析配置内容，键中的"_"视同"-"
+    pub fn parse(content: &str, yaml: bool) -> Result<BTreeMap<String, ConfigValue>> {
+        let raw: BTreeMap<String, RawValue> = if yaml {
+            if content.trim().is_empty() {
+                BTreeMap::new()
+            } else {
+                serde_yaml::from_str(content)?
+            }
+        } else {
+            toml::from_str(content)?
+        };
+        raw.into_iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    RawValue::Bool(value) => ConfigValue::Flag(value),
+                    RawValue::List(values) => {
+                        let values: Option<Vec<String>> =
+                            values.into_iter().map(RawValue::scalar).collect();
+                        let Some(values) = values else {
+                            bail!("{key} 的数组只能包含字符串或数字");
+                        };
+                        ConfigValue::Values(values)
+                    }
+                    value => ConfigValue::Values(value.scalar().into_iter().collect()),
+                };
+                Ok((key.replace('_', "-"), value))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let toml = r#"
+exclude = ["target", "node_modules"]
+include_hidden = true
+theme = "dark"
+max-depth = 3
+"#;
+        let yaml = "
+exclude: [target, node_modules]
+include-hidden: true
+theme: dark
+max_depth: 3
+";
+        let expected = BTreeMap::from([
+            (
+                "exclude".to_string(),
+                ConfigValue::Values(vec!["target".to_string(), "node_modules".to_string()]),
+            ),
+            ("include-hidden".to_string(), ConfigValue::Flag(true)),
+            (
+                "max-depth".to_string(),
+                ConfigValue::Values(vec!["3".to_string()]),
+            ),
+            (
+                "theme".to_string(),
+                ConfigValue::Values(vec!["dark".to_string()]),
+            ),
+        ]);
+        assert_eq!(Config::parse(toml, false).unwrap(), expected);
+        assert_eq!(Config::parse(yaml, true).unwrap(), expected);
+        assert!(Config::parse("exclude = [[\"a\"]]", false).is_err());
+        assert!(Config::parse("", true).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+//! CSV输出（--to csv）
+
+use anyhow::Context;
+use std::io::Write;
+
+use crate::error::Result;
+use crate::excel::convert_to_rows;
+use crate::i18n::Language;
+use crate::output::open_output;
+use crate::parser::TreeItem;
+
+/// CSV生成器：输出与Excel相同的层级列及完整路径，每行重复上级目录名称（没有合并单元格）
+#[derive(Debug)]
+pub struct CsvGenerator {
+    delimiter: u8,  // 字段分隔符
+    lang: Language, // 表头的语言
+}
+
+impl Default for CsvGenerator {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            lang: Language::default(),
+        }
+    }
+}
+
+impl CsvGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置字段分隔符（如 ';' 或 '\t'），只支持单字节字符
+    pub fn delimiter(mut self, delimiter: char) -> Result<Self> {
+        self.delimiter = u8::try_from(delimiter)
+            .ok()
+            .filter(u8::is_ascii)
+            .with_context(|| format!("CSV分隔符必须是单个ASCII字符: {delimiter:?}"))?;
+        Ok(self)
+    }
+
+    /// 设置表头的语言
+    pub fn lang(mut self, lang: Language) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// 生成CSV文件（"-"表示标准输出）
+    pub fn generate(&self, items: Vec<TreeItem>, output_path: &str) -> Result<()> {
+        self.write(items, open_output(output_path)?)
+            .with_context(|| format!("无法写入CSV文件: {output_path}"))?;
+        Ok(())
+    }
+
+    /// 将项目列表写为CSV（统计行不输出）
+    pub fn write(&self, items: Vec<TreeItem>, writer: impl Write) -> Result<()> {
+        let rows = convert_to_rows(items);
+        let max_level = rows.first().map_or(1, |row| row.max_level);
+
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(writer);
+
+        let mut header: Vec<String> = (1..=max_level).map(|level| format!("L{level}")).collect();
+        header.push(self.lang.text("完整路径", "Full Path").to_string());
+        writer.write_record(&header)?;
+
+        for row in rows.iter().filter(|row| !row.levels[0].starts_with("📊")) {
+            writer.write_record(row.levels.iter().chain([&row.full_path]))?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_write_csv() {
+        let input = ".\n├── src\n│   └── a,b.rs\n└── README.md\n\n1 directory, 2 files\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+
+        let mut output = Vec::new();
+        CsvGenerator::new()
+            .write(items.clone(), &mut output)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "L1,L2,完整路径\nsrc,,src\nsrc,\"a,b.rs\",\"src/a,b.rs\"\nREADME.md,,README.md\n"
+        );
+
+        let mut output = Vec::new();
+        CsvGenerator::new()
+            .delimiter(';')
+            .unwrap()
+            .write(items, &mut output)
+            .unwrap();
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains("src;a,b.rs;src/a,b.rs"));
+        assert!(CsvGenerator::new().delimiter('；').is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! 可插拔的输入方言：每种输入格式实现InputDialect，逐行读入后产出项目列表，
+//! 新增格式只需实现该trait并在input_dialect中注册
+
+use anyhow::Context;
+use std::io;
+
+use crate::error::Result;
+use crate::json_parser::JsonTreeParser;
+use crate::listing::{ListingFormat, ListingParser};
+use crate::ls_parser::LsParser;
+use crate::ncdu_parser::NcduParser;
+use crate::parser::{SkippedLine, TreeItem, TreeItemStream, TreeParser, DETECT_LINES};
+use crate::powershell_parser::PowerShellParser;
+use crate::windows_parser::WindowsTreeParser;
+use crate::xml_parser::XmlTreeParser;
+
+/// 一种输入格式：逐行读入，输入结束时返回解析出的项目
+pub trait InputDialect {
+    /// 方言名称（--format/--dialect的取值）
+    fn name(&self) -> &str;
+
+    /// 输入的说明，用于错误信息，如 "tree -J输出"
+    fn description(&self) -> &str;
+
+    /// 读入一行输入（不含换行符）：TreeDialect读入时即解析，BufferedDialect缓存到finish时解析
+    fn parse(&mut self, line: &str) -> Result<()>;
+
+    /// 输入结束，返回解析出的项目
+    fn finish(&mut self) -> Result<Vec<TreeItem>>;
+
+    /// 无法解析而跳过的行（finish之后有效）
+    fn skipped_lines(&self) -> &[SkippedLine] {
+        &[]
+    }
+}
+
+/// tree文本（unix、ASCII字符集、eza/lsd --tree）：格式按前10000行检测，
+/// 之后的行读入时即解析，不缓存整个输入
+#[derive(Debug, Default)]
+pub struct TreeDialect {
+    include_hidden: bool,
+    prefix: Vec<String>, // 用于检测格式的前部行
+    stream: Option<TreeItemStream<std::vec::IntoIter<io::Result<String>>>>,
+    items: Vec<TreeItem>,
+    skipped_lines: Vec<SkippedLine>,
+}
+
+impl TreeDialect {
+    pub fn new(include_hidden: bool) -> Self {
+        Self {
+            include_hidden,
+            ..Self::default()
+        }
+    }
+
+    /// 按已读入的前部行检测格式，开始逐行解析
+    fn start(&mut self) -> Result<()> {
+        let prefix: Vec<io::Result<String>> = std::mem::take(&mut self.prefix)
+            .into_iter()
+            .map(Ok)
+            .collect();
+        self.stream = Some(TreeParser::new().parse_lines(prefix, self.include_hidden)?);
+        Ok(())
+    }
+}
+
+impl InputDialect for TreeDialect {
+    fn name(&self) -> &str {
+        "unix"
+    }
+
+    fn description(&self) -> &str {
+        "tree输出"
+    }
+
+    fn parse(&mut self, line: &str) -> Result<()> {
+        if let Some(stream) = &mut self.stream {
+            stream.push_line(line);
+            self.items.extend(stream.take_ready());
+            return Ok(());
+        }
+        self.prefix.push(line.to_string());
+        if self.prefix.len() == DETECT_LINES {
+            self.start()?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<Vec<TreeItem>> {
+        if self.stream.is_none() {
+            self.start()?;
+        }
+        let Some(mut stream) = self.stream.take() else {
+            return Ok(Vec::new());
+        };
+        for item in stream.by_ref() {
+            self.items.push(item?);
+        }
+        self.skipped_lines = stream.skipped_lines().to_vec();
+        Ok(std::mem::take(&mut self.items))
+    }
+
+    fn skipped_lines(&self) -> &[SkippedLine] {
+        &self.skipped_lines
+    }
+}
+
+/// 整体解析缓存的输入
+type ParseFn = Box<dyn Fn(&str) -> Result<Vec<TreeItem>>>;
+
+/// 需要完整读入后解析的格式（JSON、Windows tree /F、列表输出等）：逐行缓存，输入结束时一次解析
+pub struct BufferedDialect {
+    name: String,
+    description: String,
+    input: String,
+    parse: ParseFn,
+}
+
+impl BufferedDialect {
+    pub fn new(
+        name: &str,
+        description: &str,
+        parse: impl Fn(&str) -> Result<Vec<TreeItem>> + 'static,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            input: String::new(),
+            parse: Box::new(parse),
+        }
+    }
+}
+
+impl InputDialect for BufferedDialect {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parse(&mut self, line: &str) -> Result<()> {
+        if !self.input.is_empty() {
+            self.input.push('\n');
+        }
+        self.input.push_str(line);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<Vec<TreeItem>> {
+        let input = std::mem::take(&mut self.input);
+        (self.parse)(&input)
+    }
+}
+
+/// 按名称创建已注册的方言：tree文本的unix/ascii/eza/lsd/windows/ls，以及--format的各种格式
+pub fn input_dialect(name: &str, include_hidden: bool) -> Option<Box<dyn InputDialect>> {
+    let buffered = |description: &str, parse: fn(&str, bool) -> Result<Vec<TreeItem>>| {
+        let dialect =
+            BufferedDialect::new(name, description, move |input| parse(input, include_hidden));
+        Some(Box::new(dialect) as Box<dyn InputDialect>)
+    };
+    match name {
+        "tree" | "unix" | "ascii" | "eza" | "lsd" => {
+            Some(Box::new(TreeDialect::new(include_hidden)))
+        }
+        "windows" => buffered("Windows tree输出", |input, include_hidden| {
+            WindowsTreeParser::new().parse(input, include_hidden)
+        }),
+        "ls" => buffered("ls -lR输出", |input, include_hidden| {
+            LsParser::new().parse(input, include_hidden)
+        }),
+        "json" => buffered("tree -J输出", |input, include_hidden| {
+            JsonTreeParser::new().parse(input, include_hidden)
+        }),
+        "xml" => buffered("tree -X输出", |input, include_hidden| {
+            XmlTreeParser::new().parse(input, include_hidden)
+        }),
+        "ncdu" => buffered("ncdu导出文件", |input, include_hidden| {
+            NcduParser::new().parse(input, include_hidden)
+        }),
+        "powershell" => buffered("Get-ChildItem输出", |input, include_hidden| {
+            PowerShellParser::new().parse(input, include_hidden)
+        }),
+        _ => {
+            let format = ListingFormat::from_name(name)?;
+            let dialect = BufferedDialect::new(name, "列表输出", move |input| {
+                ListingParser::new(format).parse(input, include_hidden)
+            });
+            Some(Box::new(dialect))
+        }
+    }
+}
+
+/// 按前部的非空行识别输入格式：json（tree -J）、ncdu、xml（tree -X）、paths（每行一个路径），其余为tree文本
+pub fn detect_format(prefix: &str) -> &'static str {
+    let lines: Vec<&str> = prefix
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let Some(first) = lines.first() else {
+        return "tree";
+    };
+    if first.starts_with(['[', '{']) {
+        return match prefix.contains("\"progname\"") {
+            true => "ncdu",
+            false => "json",
+        };
+    }
+    if first.starts_with('<') {
+        return "xml";
+    }
+    // 没有树形符号、多数行像路径（find、fd、git ls-files的输出）：带分隔符，或是不含空白的文件名；
+    // find输出的 "." 行不计
+    let has_connector = |line: &&str| {
+        line.contains(['├', '└', '│'])
+            || line.contains("|--")
+            || line.contains("`--")
+            || line.contains("+---")
+            || line.contains("\\---")
+    };
+    let looks_like_path =
+        |line: &str| line.contains(['/', '\\']) || !line.contains(char::is_whitespace);
+    let paths: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| !matches!(*line, "." | "./"))
+        .collect();
+    let is_listing = !paths.is_empty()
+        && !lines.iter().any(has_connector)
+        && paths.iter().filter(|line| looks_like_path(line)).count() * 2 > paths.len();
+    match is_listing && !LsParser::detect(prefix) && !WindowsTreeParser::detect(prefix) {
+        true => "paths",
+        false => "tree",
+    }
+}
+
+/// 按前50行识别tree文本的方言：ls、windows、ascii（tree --charset=ascii），其余为unix
+pub fn detect_dialect(prefix: &str) -> &'static str {
+    if LsParser::detect(prefix) {
+        "ls"
+    } else if WindowsTreeParser::detect(prefix) {
+        "windows"
+    } else if !prefix.contains(['├', '└']) && (prefix.contains("|--") || prefix.contains("`--"))
+    {
+        "ascii"
+    } else {
+        "unix"
+    }
+}
+
+/// 用方言解析整个输入
+pub fn parse_with(dialect: &mut dyn InputDialect, input: &str) -> Result<Vec<TreeItem>> {
+    for line in input.lines() {
+        dialect.parse(line)?;
+    }
+    Ok(dialect
+        .finish()
+        .with_context(|| format!("解析{}失败", dialect.description()))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 项目的完整路径（不含统计行）
+    fn paths(items: &[TreeItem]) -> Vec<&str> {
+        items
+            .iter()
+            .filter(|item| !item.name.starts_with("📊"))
+            .map(|item| item.full_path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_input_dialects() {
+        let cases = [
+            ("unix", ".\n├── src\n│   └── a.rs\n└── b.rs\n"),
+            ("ascii", ".\n|-- src\n|   `-- a.rs\n`-- b.rs\n"),
+            ("windows", "C:.\n├───src\n│       a.rs\n│\n    b.rs\n"),
+            (
+                "json",
+                r#"[{"type":"directory","name":".","contents":[{"type":"directory","name":"src","contents":[{"type":"file","name":"a.rs"}]},{"type":"file","name":"b.rs"}]}]"#,
+            ),
+            ("paths", "./src/a.rs\n./b.rs\n"),
+        ];
+        for (name, input) in cases {
+            let mut dialect = input_dialect(name, false).unwrap();
+            let items = parse_with(dialect.as_mut(), input).unwrap();
+            assert_eq!(paths(&items), ["src", "src/a.rs", "b.rs"], "{name}");
+        }
+        assert!(input_dialect("unknown", false).is_none());
+
+        let mut dialect = input_dialect("unix", false).unwrap();
+        parse_with(dialect.as_mut(), ".\n├── a.rs\n   \t? garbled\n└── b.rs\n").unwrap();
+        assert_eq!(dialect.skipped_lines()[0].line_number, 3);
+
+        // 检测格式所需的前部行之后，读入的行即解析出项目
+        let mut dialect = TreeDialect::new(false);
+        dialect.parse(".").unwrap();
+        for idx in 0..DETECT_LINES + 10 {
+            dialect.parse(&format!("├── f{idx}.rs")).unwrap();
+        }
+        assert!(dialect.prefix.is_empty());
+        assert!(dialect.items.len() >= DETECT_LINES);
+        dialect.parse("└── last.rs").unwrap();
+        let items = dialect.finish().unwrap();
+        assert_eq!(paths(&items).len(), DETECT_LINES + 11);
+        assert_eq!(paths(&items).last(), Some(&"last.rs"));
+
+        let mut dialect = input_dialect("json", false).unwrap();
+        let err = parse_with(dialect.as_mut(), "[\n{").unwrap_err();
+        assert!(err.to_string().starts_with("解析tree -J输出失败"));
+
+        assert_eq!(detect_dialect("C:.\n├───src\n"), "windows");
+        assert_eq!(detect_dialect(".\n├── src\n"), "unix");
+        assert_eq!(detect_dialect(".\n|-- src\n`-- b.rs\n"), "ascii");
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format("\n  [\n  {\"type\":\"directory\""), "json");
+        assert_eq!(
+            detect_format("[1,2,{\"progname\":\"ncdu\",\"progver\":\"1.19\"},\n"),
+            "ncdu"
+        );
+        assert_eq!(detect_format("<?xml version=\"1.0\"?>\n<tree>"), "xml");
+        assert_eq!(detect_format("./src/a.rs\n./b/c.rs\n"), "paths");
+        assert_eq!(detect_format("src\\a.rs\nb\\c.rs\n"), "paths");
+        // find的输出以 "." 开头，git ls-files在顶层的文件不带分隔符
+        assert_eq!(detect_format(".\n./src\n./src/a.rs\n./b.rs\n"), "paths");
+        assert_eq!(
+            detect_format("Cargo.toml\nREADME.md\nsrc/lib.rs\nsrc/main.rs\n"),
+            "paths"
+        );
+        assert_eq!(detect_format("Cargo.toml\nREADME.md\n"), "paths");
+        assert_eq!(detect_format("hello world\nsome notes here\n"), "tree");
+        assert_eq!(detect_format(".\n\n0 directories, 0 files\n"), "tree");
+        assert_eq!(detect_format(".\n├── src/\n│   └── a.rs\n"), "tree");
+        assert_eq!(detect_format("project/\n|-- src/\n"), "tree");
+        assert_eq!(detect_format("C:.\n+---src\n"), "tree");
+        assert_eq!(detect_format(""), "tree");
+    }
+}
@@ -0,0 +1,224 @@
+//! 两份tree快照的对比（diff子命令、--baseline及verify子命令）
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::i18n::Language;
+use crate::parser::TreeItem;
+
+/// 项目在两份快照之间的变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    Added,     // 只在新快照中
+    Removed,   // 只在旧快照中
+    Resized,   // 文件大小变化
+    Unchanged, // 两份快照中相同
+}
+
+impl Change {
+    /// 显示文本，也用于条件格式的匹配
+    pub fn label(self, lang: Language) -> &'static str {
+        let (zh, en) = match self {
+            Change::Added => ("新增", "Added"),
+            Change::Removed => ("删除", "Removed"),
+            Change::Resized => ("大小变化", "Resized"),
+            Change::Unchanged => ("未变", "Unchanged"),
+        };
+        lang.text(zh, en)
+    }
+}
+
+/// 各类变化的项目数
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub resized: usize,
+    pub unchanged: usize,
+}
+
+impl DiffSummary {
+    /// 汇总文本，如 "新增 3, 删除 1, 大小变化 2, 未变 40"
+    pub fn text(&self, lang: Language) -> String {
+        [
+            (Change::Added, self.added),
+            (Change::Removed, self.removed),
+            (Change::Resized, self.resized),
+            (Change::Unchanged, self.unchanged),
+        ]
+        .iter()
+        .map(|(change, count)| format!("{} {count}", change.label(lang)))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// 对齐两份快照（统计行被忽略）：以新快照的顺序为准，删除的项目插在旧快照中
+/// 前一个项目之后，仍位于原来的上级目录下。每个项目标记change，
+/// 大小变化的文件在old_size中记录原来的大小；末尾为各类变化数的统计行。
+/// 只有两份快照都有多个根目录时才按根目录区分项目（目录遍历的项目没有根目录名称，
+/// 而读回的JSON/tree文本快照为 "."）
+pub fn diff_trees(
+    old: Vec<TreeItem>,
+    new: Vec<TreeItem>,
+    lang: Language,
+) -> (Vec<TreeItem>, DiffSummary) {
+    let old: Vec<TreeItem> = old
+        .into_iter()
+        .filter(|item| !item.name.starts_with("📊"))
+        .collect();
+    let mut new: Vec<TreeItem> = new
+        .into_iter()
+        .filter(|item| !item.name.starts_with("📊"))
+        .collect();
+    let multi_root = |items: &[TreeItem]| {
+        let roots: HashSet<_> = items.iter().map(|item| &item.root).collect();
+        roots.len() > 1
+    };
+    let by_root = multi_root(&old) && multi_root(&new);
+    let key = |item: &TreeItem| {
+        (
+            item.root.clone().filter(|_| by_root),
+            item.full_path.clone(),
+        )
+    };
+    let positions: HashMap<_, usize> = new
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (key(item), index))
+        .collect();
+
+    let mut summary = DiffSummary::default();
+    let mut matched = HashSet::new();
+    // removed[i]为插在新快照第i-1项之后的删除项目（removed[0]在最前面）
+    let mut removed: Vec<Vec<TreeItem>> = vec![Vec::new(); new.len() + 1];
+    let mut anchor = 0;
+    for item in old {
+        match positions.get(&key(&item)) {
+            Some(&index) => {
+                let current = &mut new[index];
+                let resized = item.is_file && current.is_file && item.size != current.size;
+                if resized {
+                    current.change = Some(Change::Resized);
+                    current.old_size = item.size;
+                    summary.resized += 1;
+                } else {
+                    current.change = Some(Change::Unchanged);
+                    summary.unchanged += 1;
+                }
+                matched.insert(index);
+                anchor = index + 1;
+            }
+            None => {
+                removed[anchor].push(TreeItem {
+                    change: Some(Change::Removed),
+                    ..item
+                });
+                summary.removed += 1;
+            }
+        }
+    }
+
+    let mut items = Vec::with_capacity(new.len() + summary.removed);
+    let mut removed = removed.into_iter();
+    items.extend(removed.next().unwrap_or_default());
+    for ((index, mut item), removed_after) in new.into_iter().enumerate().zip(removed) {
+        if !matched.contains(&index) {
+            item.change = Some(Change::Added);
+            summary.added += 1;
+        }
+        items.push(item);
+        items.extend(removed_after);
+    }
+    items.push(TreeItem::stats(&summary.text(lang)));
+    (items, summary)
+}
+
+/// 表格与磁盘上实际目录的校验结果（verify子命令）
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    pub missing: Vec<String>,                // 表格中有、磁盘上没有的路径
+    pub unlisted: Vec<String>,               // 磁盘上有、表格中没有的路径
+    pub mismatched: Vec<(String, u64, u64)>, // (路径, 表格中的大小, 实际大小)
+}
+
+impl VerifyReport {
+    /// 对比表格中的项目与遍历磁盘得到的项目；表格中没有大小的文件不检查大小
+    pub fn new(sheet: Vec<TreeItem>, disk: Vec<TreeItem>) -> Self {
+        let (items, _) = diff_trees(sheet, disk, Language::Zh);
+        let mut report = Self::default();
+        for item in items {
+            match item.change {
+                Some(Change::Removed) => report.missing.push(item.full_path),
+                Some(Change::Added) => report.unlisted.push(item.full_path),
+                Some(Change::Resized) => {
+                    if let (Some(expected), Some(actual)) = (item.old_size, item.size) {
+                        report.mismatched.push((item.full_path, expected, actual));
+                    }
+                }
+                _ => {}
+            }
+        }
+        report
+    }
+
+    /// 是否完全一致
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.unlisted.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_diff_trees() {
+        let old = ".\n├── [        100]  README.md\n├── [       4096]  old\n│   └── [         10]  gone.txt\n└── [       4096]  src\n    ├── [         20]  lib.rs\n    └── [         30]  main.rs\n";
+        let new = ".\n├── [        100]  README.md\n└── [       4096]  src\n    ├── [         25]  lib.rs\n    ├── [         30]  main.rs\n    └── [          5]  new.rs\n";
+        let parser = TreeParser::new();
+        let (items, summary) = diff_trees(
+            parser.parse(old, false).unwrap(),
+            parser.parse(new, false).unwrap(),
+            Language::Zh,
+        );
+
+        let (stats, items) = items.split_last().unwrap();
+        assert_eq!(stats.name, "📊 统计: 新增 1, 删除 2, 大小变化 1, 未变 3");
+        let changes: Vec<(&str, Change)> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.change.unwrap()))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("README.md", Change::Unchanged),
+                ("old", Change::Removed),
+                ("old/gone.txt", Change::Removed),
+                ("src", Change::Unchanged),
+                ("src/lib.rs", Change::Resized),
+                ("src/main.rs", Change::Unchanged),
+                ("src/new.rs", Change::Added),
+            ]
+        );
+        assert_eq!(items[4].old_size, Some(20));
+        assert_eq!((summary.added, summary.removed), (1, 2));
+    }
+
+    #[test]
+    fn test_verify_report() {
+        let sheet = ".\n├── [        100]  README.md\n├── [         10]  gone.txt\n└── [         20]  lib.rs\n";
+        let disk = ".\n├── [        100]  README.md\n├── [         25]  lib.rs\n└── [          5]  new.rs\n";
+        let parser = TreeParser::new();
+        let report = VerifyReport::new(
+            parser.parse(sheet, false).unwrap(),
+            parser.parse(disk, false).unwrap(),
+        );
+        assert_eq!(report.missing, ["gone.txt"]);
+        assert_eq!(report.unlisted, ["new.rs"]);
+        assert_eq!(report.mismatched, [("lib.rs".to_string(), 20, 25)]);
+        assert!(!report.is_ok());
+    }
+}
@@ -0,0 +1,363 @@
+//! Docker镜像文件系统清单（--docker-image）：读取docker save导出的tar，按顺序叠加各层
+
+use anyhow::Context;
+use chrono::{DateTime, Local};
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::error::{bail, Result};
+use crate::flat::{build_tree, mode_string, FlatEntry};
+use crate::parser::TreeItem;
+
+/// 文件最后一次被修改时所在的镜像层
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageLayer {
+    pub index: usize,               // 层序号（从1开始，基础镜像在前）
+    pub created_by: Option<String>, // 生成该层的指令（镜像配置的history）
+}
+
+/// 层内的一个条目
+#[derive(Debug)]
+enum LayerEntry {
+    File(Box<FlatEntry>),
+    Whiteout(String), // 删除下层的该路径（.wh.name）
+    Opaque(String),   // 清空下层的该目录（.wh..wh..opq）
+}
+
+/// Docker镜像读取器
+#[derive(Debug, Default)]
+pub struct DockerImageReader;
+
+impl DockerImageReader {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 读取镜像：已存在的.tar/.tar.gz文件按docker save的导出读取，
+    /// 否则作为镜像名称，本地没有时先docker pull，再通过docker save读取
+    pub fn read_image(&self, image: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let path = Path::new(image);
+        if path.is_file() {
+            let file = File::open(path)
+                .with_context(|| format!("无法打开镜像文件: {}", path.display()))?;
+            let reader: Box<dyn Read> = if image.ends_with(".gz") || image.ends_with(".tgz") {
+                Box::new(GzDecoder::new(BufReader::new(file)))
+            } else {
+                Box::new(BufReader::new(file))
+            };
+            return self.read_tarball(reader, include_hidden);
+        }
+
+        let exists = Command::new("docker")
+            .args(["image", "inspect", image])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .context("无法运行docker命令，请确认已安装Docker，或改用docker save导出的tar文件")?
+            .success();
+        if !exists {
+            let status = Command::new("docker")
+                .args(["pull", image])
+                .stdout(Stdio::null())
+                .status()
+                .context("无法运行docker pull")?;
+            if !status.success() {
+                bail!("拉取镜像失败: {image}");
+            }
+        }
+
+        let mut child = Command::new("docker")
+            .args(["save", image])
+            .stdout(Stdio::piped())
+            .spawn()
+            .context("无法运行docker save")?;
+        let stdout = child.stdout.take().context("无法读取docker save的输出")?;
+        let items = self.read_tarball(BufReader::new(stdout), include_hidden);
+        let status = child.wait().context("等待docker save结束失败")?;
+        if !status.success() {
+            bail!("docker save执行失败: {image}");
+        }
+        items
+    }
+
+    /// 读取docker save导出的tar（传统格式及OCI格式均可）：一次顺序读取，
+    /// 各层只保留条目的元数据，读完后按manifest.json中的顺序叠加
+    pub fn read_tarball(&self, reader: impl Read, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let mut archive = tar::Archive::new(reader);
+        let mut documents: HashMap<String, Value> = HashMap::new();
+        let mut layers: HashMap<String, Vec<LayerEntry>> = HashMap::new();
+        for entry in archive.entries().context("无法读取镜像tar")? {
+            let entry = entry.context("无法读取镜像tar")?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = entry.path()?.to_string_lossy().into_owned();
+            let mut reader = BufReader::new(entry);
+            let head = reader.fill_buf()?.first().copied();
+            if name.ends_with(".json") || head == Some(b'{') {
+                let mut content = String::new();
+                reader.read_to_string(&mut content)?;
+                if let Ok(document) = serde_json::from_str(&content) {
+                    documents.insert(name, document);
+                }
+            } else if name.ends_with("layer.tar") || name.starts_with("blobs/") {
+                let entries = self
+                    .layer_entries(reader)
+                    .with_context(|| format!("无法读取镜像层: {name}"))?;
+                layers.insert(name, entries);
+            }
+        }
+
+        let Some(manifest) = documents
+            .get("manifest.json")
+            .and_then(|manifest| manifest.get(0))
+        else {
+            bail!("没有找到manifest.json，请确认输入为docker save导出的tar");
+        };
+        let layer_names: Vec<&str> = manifest["Layers"]
+            .as_array()
+            .map(|layers| layers.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        // history中没有empty_layer标记的记录依次对应各层
+        let commands: Vec<Option<String>> = manifest["Config"]
+            .as_str()
+            .and_then(|config| documents.get(config))
+            .and_then(|config| config["history"].as_array())
+            .map(|history| {
+                history
+                    .iter()
+                    .filter(|record| record["empty_layer"].as_bool() != Some(true))
+                    .map(|record| record["created_by"].as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut filesystem: BTreeMap<String, FlatEntry> = BTreeMap::new();
+        for (index, name) in layer_names.iter().enumerate() {
+            let Some(entries) = layers.remove(*name) else {
+                bail!("镜像tar中缺少层: {name}");
+            };
+            let layer = ImageLayer {
+                index: index + 1,
+                created_by: commands.get(index).cloned().flatten(),
+            };
+            // 标记在tar中的位置不固定：先对下层应用whiteout/opaque，再加入本层文件
+            for entry in &entries {
+                match entry {
+                    LayerEntry::Whiteout(path) => {
+                        filesystem.remove(path);
+                        remove_children(&mut filesystem, path);
+                    }
+                    LayerEntry::Opaque(path) => remove_children(&mut filesystem, path),
+                    LayerEntry::File(_) => {}
+                }
+            }
+            for entry in entries {
+                if let LayerEntry::File(entry) = entry {
+                    let entry = FlatEntry {
+                        layer: Some(layer.clone()),
+                        ..*entry
+                    };
+                    filesystem.insert(entry.path.clone(), entry);
+                }
+            }
+        }
+        if filesystem.is_empty() {
+            bail!("镜像中没有文件");
+        }
+        Ok(build_tree(
+            filesystem.into_values().collect(),
+            include_hidden,
+        ))
+    }
+
+    /// 一层中的条目（层可能是gzip压缩的）
+    fn layer_entries(&self, mut reader: impl BufRead) -> Result<Vec<LayerEntry>> {
+        if reader.fill_buf()?.starts_with(&[0x1f, 0x8b]) {
+            self.layer_tar(GzDecoder::new(reader))
+        } else {
+            self.layer_tar(reader)
+        }
+    }
+
+    /// 读取层tar中的条目，识别whiteout标记
+    fn layer_tar(&self, reader: impl Read) -> Result<Vec<LayerEntry>> {
+        let mut archive = tar::Archive::new(reader);
+        let mut entries = Vec::new();
+        for entry in archive.entries()? {
+            let entry = entry?;
+            let header = entry.header();
+            let path = entry.path()?.to_string_lossy().into_owned();
+            let path = path.trim_start_matches("./").trim_end_matches('/');
+            let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+            if name == ".wh..wh..opq" {
+                entries.push(LayerEntry::Opaque(parent.to_string()));
+                continue;
+            }
+            if let Some(name) = name.strip_prefix(".wh.") {
+                let path = if parent.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{parent}/{name}")
+                };
+                entries.push(LayerEntry::Whiteout(path));
+                continue;
+            }
+            if path.is_empty() || path == "." {
+                continue;
+            }
+
+            let entry_type = header.entry_type();
+            let is_dir = entry_type.is_dir();
+            let type_char = match () {
+                _ if is_dir => 'd',
+                _ if entry_type.is_symlink() => 'l',
+                _ => '-',
+            };
+            entries.push(LayerEntry::File(Box::new(FlatEntry {
+                path: path.to_string(),
+                is_dir,
+                size: (!is_dir).then(|| header.size().ok()).flatten(),
+                modified: header
+                    .mtime()
+                    .ok()
+                    .and_then(|seconds| DateTime::from_timestamp(seconds as i64, 0))
+                    .map(|datetime| datetime.with_timezone(&Local).naive_local()),
+                permissions: header.mode().ok().map(|mode| mode_string(type_char, mode)),
+                owner: header.uid().ok().map(|uid| uid.to_string()),
+                group: header.gid().ok().map(|gid| gid.to_string()),
+                link_target: entry_type
+                    .is_symlink()
+                    .then(|| entry.link_name().ok().flatten())
+                    .flatten()
+                    .map(|target| target.to_string_lossy().into_owned()),
+                ..Default::default()
+            })));
+        }
+        Ok(entries)
+    }
+}
+
+/// 删除目录下（不含目录本身）的全部条目
+fn remove_children(filesystem: &mut BTreeMap<String, FlatEntry>, dir: &str) {
+    let prefix = if dir.is_empty() {
+        String::new()
+    } else {
+        format!("{dir}/")
+    };
+    let children: Vec<String> = filesystem
+        .range(prefix.clone()..)
+        .map(|(path, _)| path)
+        .take_while(|path| path.starts_with(&prefix))
+        .cloned()
+        .collect();
+    for path in children {
+        filesystem.remove(&path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构建内存中的tar
+    fn tar_bytes(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *content).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_read_docker_save() {
+        let base = tar_bytes(&[
+            ("etc/os-release", b"ID=test"),
+            ("tmp/cache/a.bin", b"1234"),
+            ("usr/bin/old", b"x"),
+        ]);
+        let app = tar_bytes(&[
+            ("app/main.py", b"print('hi')"),
+            ("tmp/cache/.wh..wh..opq", b""),
+            ("usr/bin/.wh.old", b""),
+            ("etc/os-release", b"ID=patched"),
+        ]);
+        let manifest =
+            br#"[{"Config":"config.json","RepoTags":["demo:1"],"Layers":["l1/layer.tar","l2/layer.tar"]}]"#;
+        let config = br#"{"history":[{"created_by":"ADD rootfs /"},{"created_by":"ENV A=1","empty_layer":true},{"created_by":"COPY app /app"}]}"#;
+        let image = tar_bytes(&[
+            ("l1/layer.tar", &base),
+            ("l2/layer.tar", &app),
+            ("config.json", config),
+            ("manifest.json", manifest),
+        ]);
+
+        let items = DockerImageReader::new()
+            .read_tarball(image.as_slice(), false)
+            .unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| {
+                (
+                    item.full_path.as_str(),
+                    item.size,
+                    item.layer.as_ref().map(|layer| layer.index),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("app", None, None),
+                ("app/main.py", Some(11), Some(2)),
+                ("etc", None, None),
+                ("etc/os-release", Some(10), Some(2)),
+                ("📊 统计: 2 directories, 2 files", None, None),
+            ]
+        );
+        assert_eq!(
+            items[1].layer.as_ref().unwrap().created_by.as_deref(),
+            Some("COPY app /app")
+        );
+    }
+
+    #[test]
+    fn test_opaque_after_same_layer_file() {
+        let base = tar_bytes(&[("tmp/cache/a.bin", b"1234")]);
+        // "-new.bin" 排在 ".wh..wh..opq" 之前，但属于同一层，不应被清除
+        let app = tar_bytes(&[
+            ("tmp/cache/-new.bin", b"12"),
+            ("tmp/cache/.wh..wh..opq", b""),
+        ]);
+        let manifest = br#"[{"Layers":["l1/layer.tar","l2/layer.tar"]}]"#;
+        let image = tar_bytes(&[
+            ("l1/layer.tar", &base),
+            ("l2/layer.tar", &app),
+            ("manifest.json", manifest),
+        ]);
+
+        let items = DockerImageReader::new()
+            .read_tarball(image.as_slice(), false)
+            .unwrap();
+        let paths: Vec<_> = items.iter().map(|item| item.full_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "tmp",
+                "tmp/cache",
+                "tmp/cache/-new.bin",
+                "📊 统计: 2 directories, 1 files"
+            ]
+        );
+    }
+}
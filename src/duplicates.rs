@@ -0,0 +1,165 @@
+//! 重复文件检测（--find-duplicates）：按大小及校验和分组
+
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::hash::HashAlgorithm;
+use crate::parser::{EntryKind, TreeItem};
+
+/// 一组内容相同的文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateGroup {
+    pub id: usize,          // 组编号（从1开始，按可节省的空间从大到小）
+    pub size: u64,          // 单个文件的字节数
+    pub paths: Vec<String>, // 各文件的完整路径
+}
+
+impl DuplicateGroup {
+    /// 只保留一份时可节省的字节数
+    pub fn savings(&self) -> u64 {
+        self.size * (self.paths.len() as u64).saturating_sub(1)
+    }
+
+    /// 从已标记组编号的项目中收集各组（按组编号排列）
+    pub fn from_items<'a>(items: impl IntoIterator<Item = &'a TreeItem>) -> Vec<Self> {
+        let mut groups: BTreeMap<usize, Self> = BTreeMap::new();
+        for item in items {
+            let Some(id) = item.duplicate_group else {
+                continue;
+            };
+            groups
+                .entry(id)
+                .or_insert_with(|| DuplicateGroup {
+                    id,
+                    size: item.size.unwrap_or(0),
+                    paths: Vec::new(),
+                })
+                .paths
+                .push(item.full_path.clone());
+        }
+        groups.into_values().collect()
+    }
+}
+
+/// 查找root下内容相同的普通文件，为其写入item.duplicate_group。
+/// 只有大小相同的非空文件才计算SHA-256校验和（已有--hash结果时直接使用），
+/// 每完成一个文件调用progress(已完成数, 需计算的文件数)
+pub fn find_duplicates(
+    root: &Path,
+    items: &mut [TreeItem],
+    progress: impl Fn(usize, usize) + Sync,
+) -> Vec<DuplicateGroup> {
+    let mut by_size: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (index, item) in items.iter().enumerate() {
+        if item.kind != EntryKind::File || item.name.starts_with("📊") {
+            continue;
+        }
+        if let Some(size) = item.size.filter(|size| *size > 0) {
+            by_size.entry(size).or_default().push(index);
+        }
+    }
+    let candidates: Vec<usize> = by_size
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .flatten()
+        .collect();
+
+    // 并行计算候选文件的校验和，无法读取的文件不参与分组
+    let total = candidates.len();
+    let done = AtomicUsize::new(0);
+    let files: &[TreeItem] = items;
+    let hashes: Vec<(usize, String)> = candidates
+        .par_iter()
+        .filter_map(|&index| {
+            let item = &files[index];
+            let hash = match &item.hash {
+                Some(hash) => Some(hash.value.clone()),
+                None => HashAlgorithm::Sha256
+                    .hash_file(&root.join(&item.full_path))
+                    .ok(),
+            };
+            progress(done.fetch_add(1, Ordering::Relaxed) + 1, total);
+            hash.map(|hash| (index, hash))
+        })
+        .collect();
+
+    let mut by_content: HashMap<(u64, String), Vec<usize>> = HashMap::new();
+    for (index, hash) in hashes {
+        let size = files[index].size.unwrap_or(0);
+        by_content.entry((size, hash)).or_default().push(index);
+    }
+    let mut sets: Vec<Vec<usize>> = by_content
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .map(|mut indices| {
+            indices.sort_unstable();
+            indices
+        })
+        .collect();
+    // 可节省空间大的组在前，相同时按首个文件在列表中的位置
+    let savings =
+        |indices: &Vec<usize>| files[indices[0]].size.unwrap_or(0) * (indices.len() as u64 - 1);
+    sets.sort_by_key(|indices| (std::cmp::Reverse(savings(indices)), indices[0]));
+
+    let mut groups = Vec::with_capacity(sets.len());
+    for (position, indices) in sets.into_iter().enumerate() {
+        let id = position + 1;
+        let mut paths = Vec::with_capacity(indices.len());
+        for &index in &indices {
+            items[index].duplicate_group = Some(id);
+            paths.push(items[index].full_path.clone());
+        }
+        groups.push(DuplicateGroup {
+            id,
+            size: items[indices[0]].size.unwrap_or(0),
+            paths,
+        });
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_find_duplicates() {
+        let root = std::env::temp_dir().join("tree_to_excel_duplicates_test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let files = [
+            ("a.txt", "hello"),
+            ("b.txt", "hello"),
+            ("c.txt", "world"),
+            ("d.txt", "abc"),
+        ];
+        let mut items: Vec<TreeItem> = files
+            .iter()
+            .map(|(name, content)| {
+                fs::write(root.join(name), content).unwrap();
+                TreeItem {
+                    name: name.to_string(),
+                    level: 1,
+                    is_file: true,
+                    full_path: name.to_string(),
+                    size: Some(content.len() as u64),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let groups = find_duplicates(&root, &mut items, |_, _| {});
+        fs::remove_dir_all(&root).unwrap();
+
+        // c.txt大小相同但内容不同
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].paths, ["a.txt", "b.txt"]);
+        assert_eq!(groups[0].savings(), 5);
+        assert_eq!(items[1].duplicate_group, Some(1));
+        assert_eq!(items[2].duplicate_group, None);
+        assert_eq!(DuplicateGroup::from_items(&items), groups);
+    }
+}
@@ -0,0 +1,232 @@
+//! 输入编码：重定向到文件的Windows tree输出常为GBK或CP437编码（PowerShell重定向为UTF-16），
+//! 读入时转换为UTF-8（--encoding）
+
+use encoding_rs::{Decoder, Encoding, GBK, UTF_8, WINDOWS_1252};
+use std::io::{self, BufRead, BufReader, Read};
+
+/// CP437（DOS英文代码页）0x80~0xFF对应的字符，encoding_rs不支持该代码页
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', //
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', //
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', //
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐', //
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', //
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', //
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', //
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Charset {
+    #[default]
+    Auto,
+    Cp437,
+    Encoding(&'static Encoding),
+}
+
+/// 输入的字符编码，默认按开头的内容自动识别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InputEncoding(Charset);
+
+impl InputEncoding {
+    /// 按名称选择，如 "auto"、"utf-8"、"gbk"、"cp437"、"latin1"、"utf-16le"、"shift_jis"（不区分大小写）
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label.trim().to_lowercase();
+        match label.as_str() {
+            "auto" => Some(Self(Charset::Auto)),
+            "cp437" | "ibm437" | "437" | "oem" => Some(Self(Charset::Cp437)),
+            "utf8" => Some(Self(Charset::Encoding(UTF_8))),
+            _ => Encoding::for_label(label.as_bytes())
+                .map(|encoding| Self(Charset::Encoding(encoding))),
+        }
+    }
+
+    /// 编码名称
+    pub fn name(&self) -> &'static str {
+        match self.0 {
+            Charset::Auto => "auto",
+            Charset::Cp437 => "CP437",
+            Charset::Encoding(encoding) => encoding.name(),
+        }
+    }
+
+    /// 按输入开头的字节识别：BOM、UTF-8、CP437（含Windows tree的 "├───"）、GBK，其余按Latin-1
+    pub fn detect(bytes: &[u8]) -> Self {
+        if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+            return Self(Charset::Encoding(encoding));
+        }
+        // 末尾可能截断在多字节字符中间
+        let is_utf8 = match std::str::from_utf8(bytes) {
+            Ok(_) => true,
+            Err(err) => err.error_len().is_none(),
+        };
+        if is_utf8 {
+            return Self(Charset::Encoding(UTF_8));
+        }
+        let has_box = |lead: u8| {
+            bytes
+                .windows(4)
+                .any(|window| window == [lead, 0xC4, 0xC4, 0xC4])
+        };
+        if has_box(0xC3) || has_box(0xC0) {
+            return Self(Charset::Cp437);
+        }
+        let complete = bytes
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(bytes, |end| &bytes[..end]);
+        if GBK
+            .decode_without_bom_handling_and_without_replacement(complete)
+            .is_some()
+        {
+            return Self(Charset::Encoding(GBK));
+        }
+        Self(Charset::Encoding(WINDOWS_1252))
+    }
+
+    /// 转换为UTF-8（无法解码的字节替换为U+FFFD）
+    pub fn decode(self, bytes: &[u8]) -> String {
+        let mut text = String::new();
+        let mut reader = self.reader(bytes);
+        match reader.read_to_string(&mut text) {
+            Ok(_) => text,
+            Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+
+    /// 包装输入，逐块转换为UTF-8；自动识别时按缓冲区中开头的内容判断
+    pub fn reader<'a>(self, mut reader: impl BufRead + 'a) -> Box<dyn BufRead + 'a> {
+        let charset = match self.0 {
+            Charset::Auto => match reader.fill_buf() {
+                Ok(prefix) => Self::detect(prefix).0,
+                Err(_) => Charset::Encoding(UTF_8),
+            },
+            charset => charset,
+        };
+        let decoder = match charset {
+            // UTF-8无需转换（带BOM时仍需去掉BOM）
+            Charset::Encoding(encoding)
+                if encoding == UTF_8
+                    && !reader
+                        .fill_buf()
+                        .is_ok_and(|buf| buf.starts_with(b"\xEF\xBB\xBF")) =>
+            {
+                return Box::new(reader);
+            }
+            Charset::Encoding(encoding) => Some(encoding.new_decoder()),
+            Charset::Cp437 | Charset::Auto => None,
+        };
+        log::info!("🔤 按{}编码读取输入", Self(charset).name());
+        Box::new(BufReader::new(DecodingReader {
+            inner: reader,
+            decoder,
+            decoded: Vec::new(),
+            pos: 0,
+            finished: false,
+        }))
+    }
+}
+
+/// 逐块转换为UTF-8的读取器；decoder为None时按CP437转换
+struct DecodingReader<R> {
+    inner: R,
+    decoder: Option<Decoder>,
+    decoded: Vec<u8>,
+    pos: usize,
+    finished: bool,
+}
+
+impl<R: BufRead> DecodingReader<R> {
+    /// 读入并转换下一块输入
+    fn fill(&mut self) -> io::Result<()> {
+        let src = self.inner.fill_buf()?;
+        let last = src.is_empty();
+        self.decoded.clear();
+        self.pos = 0;
+        let consumed = match &mut self.decoder {
+            Some(decoder) => {
+                let capacity = decoder
+                    .max_utf8_buffer_length(src.len())
+                    .unwrap_or(src.len() * 3 + 16);
+                self.decoded.resize(capacity, 0);
+                let (_, read, written, _) = decoder.decode_to_utf8(src, &mut self.decoded, last);
+                self.decoded.truncate(written);
+                read
+            }
+            None => {
+                let text: String = src
+                    .iter()
+                    .map(|&byte| match byte {
+                        0x00..=0x7F => byte as char,
+                        _ => CP437_HIGH[usize::from(byte - 0x80)],
+                    })
+                    .collect();
+                self.decoded = text.into_bytes();
+                src.len()
+            }
+        };
+        self.inner.consume(consumed);
+        self.finished = last;
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.decoded.len() {
+            if self.finished {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+        let len = buf.len().min(self.decoded.len() - self.pos);
+        buf[..len].copy_from_slice(&self.decoded[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::windows_parser::WindowsTreeParser;
+
+    #[test]
+    fn test_detect_and_decode() {
+        // cmd下tree /F重定向的CP437输出
+        let cp437 = b"C:.\r\n\xC3\xC4\xC4\xC4src\r\n\xB3       main.rs\r\n\xC0\xC4\xC4\xC4docs\r\n";
+        let encoding = InputEncoding::detect(cp437);
+        assert_eq!(encoding.name(), "CP437");
+        let text = encoding.decode(cp437);
+        assert!(text.contains("├───src"));
+        let items = WindowsTreeParser::new().parse(&text, false).unwrap();
+        assert_eq!(items[1].full_path, "src/main.rs");
+
+        // 中文Windows的GBK输出
+        let (gbk, _, _) = GBK.encode("C:.\r\n├───文档\r\n│       说明.txt\r\n");
+        let encoding = InputEncoding::detect(&gbk);
+        assert_eq!(encoding.name(), "GBK");
+        assert!(encoding.decode(&gbk).contains("说明.txt"));
+
+        // PowerShell重定向的UTF-16LE（带BOM）
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("├── a.rs\n".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let encoding = InputEncoding::detect(&utf16);
+        assert_eq!(encoding.name(), "UTF-16LE");
+        assert_eq!(encoding.decode(&utf16), "├── a.rs\n");
+
+        assert_eq!(
+            InputEncoding::detect("├── 文件.txt".as_bytes()).name(),
+            "UTF-8"
+        );
+        let latin1 = InputEncoding::from_label("latin1").unwrap();
+        assert_eq!(latin1.decode(b"caf\xE9"), "café");
+        assert_eq!(
+            InputEncoding::default().decode(b"caf\xE9 au lait"),
+            "café au lait"
+        );
+        assert!(InputEncoding::from_label("nope").is_none());
+    }
+}
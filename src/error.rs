@@ -0,0 +1,220 @@
+//! 库的错误类型：调用方可按类别匹配，解析错误带有出错的行号
+
+use std::error::Error as StdError;
+use std::io;
+
+/// 库函数返回的结果
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// 与anyhow::bail!相同，所在函数可返回anyhow::Result或本库的Result
+macro_rules! bail {
+    ($($arg:tt)*) => {
+        return Err(anyhow::anyhow!($($arg)*).into())
+    };
+}
+pub(crate) use bail;
+
+/// 库的错误类别
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// 输入无法解析；line为出错的行号（从1开始），无法确定时为None
+    #[error("{reason}")]
+    Parse { line: Option<usize>, reason: String },
+    /// 生成Excel失败
+    #[error("{context}")]
+    Excel {
+        context: String,
+        #[source]
+        source: rust_xlsxwriter::XlsxError,
+    },
+    /// 读写文件失败
+    #[error("{context}")]
+    Io {
+        context: String,
+        #[source]
+        source: io::Error,
+    },
+    /// 其他错误（参数无效、外部命令失败等）
+    #[error("{0}")]
+    Other(String),
+}
+
+impl Error {
+    /// 解析错误
+    pub(crate) fn parse(line: Option<usize>, reason: impl Into<String>) -> Self {
+        Error::Parse {
+            line,
+            reason: reason.into(),
+        }
+    }
+
+    /// 出错的行号（仅解析错误）
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Error::Parse { line, .. } => *line,
+            _ => None,
+        }
+    }
+
+    /// 在说明前加上外层的上下文
+    fn with_context(self, outer: &str) -> Self {
+        if outer.is_empty() {
+            return self;
+        }
+        let join = |inner: String| format!("{outer}: {inner}");
+        match self {
+            Error::Parse { line, reason } => Error::Parse {
+                line,
+                reason: join(reason),
+            },
+            Error::Excel { context, source } => Error::Excel {
+                context: join(context),
+                source,
+            },
+            Error::Io { context, source } => Error::Io {
+                context: join(context),
+                source,
+            },
+            Error::Other(message) => Error::Other(join(message)),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io {
+            context: "读写文件失败".to_string(),
+            source,
+        }
+    }
+}
+
+impl From<rust_xlsxwriter::XlsxError> for Error {
+    fn from(source: rust_xlsxwriter::XlsxError) -> Self {
+        Error::Excel {
+            context: "Excel生成失败".to_string(),
+            source,
+        }
+    }
+}
+
+/// 其他依赖库的错误同样按错误链归类（如JSON语法错误归为Parse）
+macro_rules! from_foreign {
+    ($($source:ty),*) => {
+        $(impl From<$source> for Error {
+            fn from(err: $source) -> Self {
+                anyhow::Error::from(err).into()
+            }
+        })*
+    };
+}
+
+from_foreign!(
+    serde_json::Error,
+    serde_yaml::Error,
+    toml::de::Error,
+    toml::ser::Error,
+    csv::Error,
+    zip::result::ZipError,
+    git2::Error
+);
+#[cfg(feature = "ssh")]
+from_foreign!(ssh2::Error);
+
+/// 模块内部用anyhow附加上下文，返回给调用方时按错误链中的原因归类
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        if let Some(line) = err.chain().find_map(parse_line) {
+            return Error::Parse {
+                line,
+                reason: format!("{err:#}"),
+            };
+        }
+        // 嵌套的Error之外的上下文；没有嵌套时为整条错误链
+        let mut outer: Vec<String> = err
+            .chain()
+            .take_while(|cause| cause.downcast_ref::<Error>().is_none())
+            .map(ToString::to_string)
+            .collect();
+        let err = match err.downcast::<Error>() {
+            Ok(inner) => return inner.with_context(&outer.join(": ")),
+            Err(err) => err,
+        };
+        let message = format!("{err:#}");
+        outer.pop(); // 最底层的原因由source提供
+        let context = |default: &str| match outer.is_empty() {
+            true => default.to_string(),
+            false => outer.join(": "),
+        };
+        let err = match err.downcast::<rust_xlsxwriter::XlsxError>() {
+            Ok(source) => {
+                return Error::Excel {
+                    context: context("Excel生成失败"),
+                    source,
+                }
+            }
+            Err(err) => err,
+        };
+        match err.downcast::<io::Error>() {
+            Ok(source) => Error::Io {
+                context: context("读写文件失败"),
+                source,
+            },
+            Err(_) => Error::Other(message),
+        }
+    }
+}
+
+/// 错误链中的一环是否为解析错误，是则返回其行号
+fn parse_line(cause: &(dyn StdError + 'static)) -> Option<Option<usize>> {
+    if let Some(err) = cause.downcast_ref::<Error>() {
+        return matches!(err, Error::Parse { .. }).then(|| err.line());
+    }
+    if let Some(err) = cause.downcast_ref::<serde_json::Error>() {
+        return (!err.is_io()).then_some(Some(err.line()));
+    }
+    if let Some(err) = cause.downcast_ref::<roxmltree::Error>() {
+        return Some(Some(err.pos().row as usize));
+    }
+    if let Some(err) = cause.downcast_ref::<csv::Error>() {
+        return (!err.is_io_error()).then(|| err.position().map(|pos| pos.line() as usize));
+    }
+    if let Some(err) = cause.downcast_ref::<serde_yaml::Error>() {
+        return Some(err.location().map(|location| location.line()));
+    }
+    cause.downcast_ref::<toml::de::Error>().map(|_| None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, JsonTreeParser, LsParser};
+    use anyhow::Context;
+
+    #[test]
+    fn test_error_kinds() {
+        let err = JsonTreeParser::new()
+            .parse("[\n{\"type\": ", false)
+            .unwrap_err();
+        assert!(matches!(err, Error::Parse { line: Some(2), .. }));
+        assert!(err.to_string().starts_with("无法解析tree -J输出的JSON: "));
+
+        let err = LsParser::new().parse("hello", false).unwrap_err();
+        assert!(matches!(err, Error::Parse { line: None, .. }));
+
+        let err = Config::load(std::path::Path::new("/nonexistent/tree2excel.toml")).unwrap_err();
+        let Error::Io { context, source } = &err else {
+            panic!("应为Io错误: {err:?}");
+        };
+        assert!(context.starts_with("无法读取配置文件"));
+        assert_eq!(source.kind(), io::ErrorKind::NotFound);
+
+        // 外层附加的上下文保留在说明中
+        let err = Error::from(Err::<(), _>(err).context("加载失败").unwrap_err());
+        assert!(
+            matches!(&err, Error::Io { context, .. } if context.starts_with("加载失败: 无法读取配置文件"))
+        );
+        let err = Error::from(anyhow::anyhow!("其他"));
+        assert!(matches!(err, Error::Other(message) if message == "其他"));
+    }
+}
@@ -0,0 +1,2953 @@
+//! Excel工作簿生成
+
+use anyhow::Context;
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use rust_xlsxwriter::{
+    Chart, ChartDataLabel, ConditionalFormatFormula, DataValidation, ExcelDateTime, Format, Url,
+    Workbook, Worksheet,
+};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::column::{ColumnProvider, ModifiedColumn, NotesColumn, PathColumn, SizeColumn};
+use crate::diff::Change;
+use crate::docker::ImageLayer;
+use crate::duplicates::DuplicateGroup;
+use crate::error::{bail, Result};
+use crate::file_kind;
+use crate::git::GitInfo;
+use crate::hash::{FileHash, HashAlgorithm};
+use crate::i18n::Language;
+use crate::loc;
+use crate::output::{open_output, CellKind, CellValue};
+use crate::parser::TreeItem;
+use crate::stats::{DirectoryStats, TopItems, TreeStats, MAX_PATH_LEN};
+use crate::theme::Theme;
+
+/// 流式生成时合并单元格检测默认向前查看的行数
+const MERGE_WINDOW: usize = 10_000;
+
+/// 扩展名分布图表最多显示的扩展名数
+const CHART_EXTENSIONS: usize = 10;
+
+/// Excel超链接的最大长度
+const MAX_URL_LEN: usize = 2079;
+
+/// Excel单元格的最大缩进级别
+const MAX_INDENT: usize = 15;
+
+/// 自动列宽的上限（字符数）
+const MAX_AUTOFIT_WIDTH: f64 = 100.0;
+
+/// Excel分组（大纲）的最大层数
+const OUTLINE_LEVELS: usize = 7;
+
+/// 每个工作表最多写入的行数（Excel上限1,048,576行，去掉表头行）
+const SHEET_ROWS: usize = 1_048_575;
+
+/// Excel行数据  
+#[derive(Debug, Default)]
+pub(crate) struct ExcelRow {
+    pub(crate) levels: Vec<String>, // 每个层级的名称，如["src", "bin", "file.rs"]
+    pub(crate) full_path: String,   // 完整路径
+    pub(crate) max_level: usize,    // 最大层级深度
+    pub(crate) is_file: bool,
+    pub(crate) size: Option<u64>,               // 字节数
+    pub(crate) modified: Option<NaiveDateTime>, // 修改时间
+    pub(crate) permissions: Option<String>,     // 权限
+    pub(crate) owner: Option<String>,           // 所有者
+    pub(crate) group: Option<String>,           // 所属组
+    pub(crate) link_target: Option<String>,     // 符号链接目标
+    pub(crate) root: Option<String>,            // 所属根目录（多根输入时）
+    pub(crate) error: Option<String>,           // 读取错误
+    pub(crate) rollup: Option<DirectoryStats>,  // 目录的汇总（含子目录的大小、文件数、子目录数）
+    pub(crate) git: Option<GitInfo>,            // git状态及最后提交
+    pub(crate) hash: Option<FileHash>,          // 文件校验和
+    pub(crate) sniffed_type: Option<String>,    // 按内容识别出的类型
+    pub(crate) lines: Option<usize>,            // 文本文件的行数
+    pub(crate) duplicate_group: Option<usize>,  // 重复文件的组编号
+    pub(crate) change: Option<Change>,          // 与另一份快照相比的变化
+    pub(crate) old_size: Option<u64>,           // 旧快照中的大小
+    pub(crate) storage_class: Option<String>,   // 对象存储的存储类别
+    pub(crate) layer: Option<ImageLayer>,       // 最后修改该文件的镜像层
+    pub(crate) custom: Vec<CellValue>,          // 自定义列（ColumnProvider）的值
+}
+
+/// 可在--col-width中指定宽度的附加列名
+const COLUMN_KEYS: [&str; 32] = [
+    "root",
+    "path",
+    "change",
+    "permissions",
+    "owner",
+    "group",
+    "target",
+    "file_type",
+    "lines",
+    "language",
+    "size",
+    "size_human",
+    "old_size",
+    "total_size",
+    "files",
+    "subdirs",
+    "modified",
+    "storage_class",
+    "layer",
+    "layer_command",
+    "git_status",
+    "last_commit",
+    "author",
+    "hash",
+    "duplicate",
+    "secret",
+    "status",
+    "notes",
+    "name",
+    "depth",
+    "type",
+    "parent",
+];
+
+/// 层级列之后的附加列
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Root,                // 根目录
+    Path,                // 完整路径
+    Change,              // 与另一份快照相比的变化
+    Permissions,         // 权限
+    Owner,               // 所有者
+    Group,               // 所属组
+    LinkTarget,          // 链接目标
+    FileKind,            // 文件类型（如"Rust源文件"、"PNG图片"）
+    Lines,               // 文本文件的行数
+    CodeLanguage,        // 编程语言
+    Size,                // 大小（字节数）
+    SizeHuman,           // 大小（易读，如1.4 MiB）
+    OldSize,             // 旧快照中的大小
+    TotalSize,           // 目录的总大小（含子目录）
+    FileCount,           // 目录中的文件数（含子目录）
+    DirCount,            // 目录中的子目录数（含子目录）
+    Modified,            // 修改时间
+    StorageClass,        // 对象存储的存储类别
+    Layer,               // 镜像层序号
+    LayerCommand,        // 生成镜像层的指令
+    GitStatus,           // git状态
+    LastCommit,          // 最后提交时间
+    Author,              // 最后提交的作者
+    Hash(HashAlgorithm), // 文件校验和
+    Duplicate,           // 重复文件的组编号
+    Secret,              // 安全标记
+    Status,              // 状态（下拉列表）
+    Notes,               // 备注
+    Name,                // 名称（扁平布局）
+    Depth,               // 深度（扁平布局）
+    Type,                // 类型（扁平布局）
+    Parent,              // 上级目录（扁平布局）
+    Extra(usize),        // 用户指定的空白列（--extra-col）
+    Custom(usize),       // 库的使用者注册的自定义列（ColumnProvider）
+}
+
+impl Column {
+    /// 表头名称
+    fn header(self, lang: Language) -> &'static str {
+        let (zh, en) = match self {
+            Column::Root => ("根目录", "Root"),
+            Column::Change => ("变更", "Change"),
+            Column::Permissions => ("权限", "Permissions"),
+            Column::Owner => ("所有者", "Owner"),
+            Column::Group => ("所属组", "Group"),
+            Column::LinkTarget => ("链接目标", "Link Target"),
+            Column::FileKind => ("文件类型", "File Type"),
+            Column::Lines => ("行数", "Lines"),
+            Column::CodeLanguage => ("语言", "Language"),
+            Column::SizeHuman => ("大小（易读）", "Size (Human)"),
+            Column::OldSize => ("原大小", "Old Size"),
+            Column::TotalSize => ("总大小", "Total Size"),
+            Column::FileCount => ("文件数", "Files"),
+            Column::DirCount => ("子目录数", "Subdirs"),
+            Column::StorageClass => ("存储类别", "Storage Class"),
+            Column::Layer => ("镜像层", "Layer"),
+            Column::LayerCommand => ("层指令", "Layer Command"),
+            Column::GitStatus => ("Git状态", "Git Status"),
+            Column::LastCommit => ("最后提交", "Last Commit"),
+            Column::Author => ("最后作者", "Last Author"),
+            Column::Hash(algorithm) => (algorithm.name(), algorithm.name()),
+            Column::Duplicate => ("重复组", "Duplicate"),
+            Column::Secret => ("安全标记", "Security Flag"),
+            Column::Status => ("状态", "Status"),
+            Column::Name => ("名称", "Name"),
+            Column::Depth => ("深度", "Depth"),
+            Column::Type => ("类型", "Type"),
+            Column::Parent => ("上级目录", "Parent"),
+            // 名称由用户指定或由ColumnProvider提供，见ExcelGenerator::column_header
+            Column::Path
+            | Column::Size
+            | Column::Modified
+            | Column::Notes
+            | Column::Extra(_)
+            | Column::Custom(_) => ("", ""),
+        };
+        lang.text(zh, en)
+    }
+
+    /// --col-width中使用的列名
+    fn key(self) -> &'static str {
+        match self {
+            Column::Root => "root",
+            Column::Path => "path",
+            Column::Change => "change",
+            Column::Permissions => "permissions",
+            Column::Owner => "owner",
+            Column::Group => "group",
+            Column::LinkTarget => "target",
+            Column::FileKind => "file_type",
+            Column::Lines => "lines",
+            Column::CodeLanguage => "language",
+            Column::Size => "size",
+            Column::SizeHuman => "size_human",
+            Column::OldSize => "old_size",
+            Column::TotalSize => "total_size",
+            Column::FileCount => "files",
+            Column::DirCount => "subdirs",
+            Column::Modified => "modified",
+            Column::StorageClass => "storage_class",
+            Column::Layer => "layer",
+            Column::LayerCommand => "layer_command",
+            Column::GitStatus => "git_status",
+            Column::LastCommit => "last_commit",
+            Column::Author => "author",
+            Column::Hash(_) => "hash",
+            Column::Duplicate => "duplicate",
+            Column::Secret => "secret",
+            Column::Status => "status",
+            Column::Notes => "notes",
+            Column::Name => "name",
+            Column::Depth => "depth",
+            Column::Type => "type",
+            Column::Parent => "parent",
+            Column::Extra(_) | Column::Custom(_) => "",
+        }
+    }
+
+    /// 列宽
+    fn width(self) -> f64 {
+        match self {
+            Column::Root => 20.0,
+            Column::Permissions => 12.0,
+            Column::Owner | Column::Group => 12.0,
+            Column::LinkTarget => 40.0,
+            Column::FileKind => 18.0,
+            Column::Lines => 10.0,
+            Column::CodeLanguage => 14.0,
+            Column::SizeHuman => 12.0,
+            Column::OldSize => 14.0,
+            Column::Change => 12.0,
+            Column::TotalSize => 14.0,
+            Column::FileCount | Column::DirCount => 10.0,
+            Column::StorageClass => 16.0,
+            Column::Layer => 8.0,
+            Column::LayerCommand => 50.0,
+            Column::GitStatus => 10.0,
+            Column::LastCommit => 18.0,
+            Column::Author => 16.0,
+            Column::Hash(algorithm) => algorithm.hex_len() as f64 + 2.0,
+            Column::Duplicate => 10.0,
+            Column::Secret => 16.0,
+            Column::Status => 12.0,
+            Column::Name => 40.0,
+            Column::Depth => 8.0,
+            Column::Type => 10.0,
+            Column::Parent => 50.0,
+            // 由ColumnProvider提供，见ExcelGenerator::column_provider
+            Column::Path
+            | Column::Size
+            | Column::Modified
+            | Column::Notes
+            | Column::Extra(_)
+            | Column::Custom(_) => 20.0,
+        }
+    }
+}
+
+/// 文件名按非字母数字字符及驼峰边界拆分出的小写单词，如 "apiToken_v2.txt" 为 api、token、v2、txt
+fn name_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous_lower = false;
+    for ch in name.chars() {
+        let boundary = !ch.is_alphanumeric() || (previous_lower && ch.is_uppercase());
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if ch.is_alphanumeric() {
+            word.extend(ch.to_lowercase());
+        }
+        previous_lower = ch.is_lowercase() || ch.is_ascii_digit();
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// 根据文件名判断是否疑似包含密钥/凭据，返回分类说明（只用于文件，目录名不判断）
+pub fn detect_secret(name: &str) -> Option<&'static str> {
+    let lower = name.to_ascii_lowercase();
+
+    // SSH私钥（公钥.pub不算）
+    if matches!(
+        lower.as_str(),
+        "id_rsa" | "id_dsa" | "id_ecdsa" | "id_ed25519"
+    ) {
+        return Some("SSH私钥");
+    }
+
+    // 证书/私钥容器
+    if [".pem", ".key", ".p12", ".pfx", ".jks", ".keystore", ".ppk"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+    {
+        return Some("证书/私钥");
+    }
+
+    // 环境变量文件（.env、.env.local等，.env.example除外）
+    if (lower == ".env" || lower.starts_with(".env.")) && !lower.ends_with(".example") {
+        return Some("环境变量文件");
+    }
+
+    // 常见凭据配置文件
+    if matches!(
+        lower.as_str(),
+        ".npmrc"
+            | ".pypirc"
+            | ".netrc"
+            | ".htpasswd"
+            | ".pgpass"
+            | "credentials"
+            | "credentials.json"
+    ) {
+        return Some("凭据文件");
+    }
+
+    // 文件名中含有敏感关键字（整个单词，可为复数，如 "db_password.txt"、"secrets.yaml"；"tokenizer.rs"不算）
+    let keywords = ["password", "passwd", "secret", "token"];
+    if name_words(name).iter().any(|word| {
+        keywords
+            .iter()
+            .any(|keyword| word == keyword || word.strip_suffix('s') == Some(keyword))
+    }) {
+        return Some("疑似凭据");
+    }
+
+    None
+}
+
+/// Excel格式配置
+struct ExcelFormats {
+    header_format: Format,
+    dir_format: Format,
+    file_format: Format,
+    link_format: Format,
+    path_format: Format,
+    notes_format: Format,
+    secret_format: Format,
+    duplicate_format: Format,
+    error_format: Format,
+    hyperlink_format: Format,
+    size_format: Format,
+    human_size_format: Format,
+    date_format: Format,
+    text_format: Format,
+    stats_format: Format,
+    category_formats: BTreeMap<String, Format>, // 各文件类别的文件名格式
+}
+
+impl ExcelFormats {
+    fn new(theme: &Theme) -> Self {
+        Self {
+            header_format: theme.format(&theme.header),
+            dir_format: theme
+                .format(&theme.directory)
+                .set_align(rust_xlsxwriter::FormatAlign::Center)
+                .set_align(rust_xlsxwriter::FormatAlign::VerticalCenter),
+            file_format: theme.format(&theme.file),
+            link_format: theme.format(&theme.link),
+            path_format: theme.format(&theme.path),
+            notes_format: theme.format(&theme.notes),
+            secret_format: theme.format(&theme.secret),
+            duplicate_format: theme.format(&theme.duplicate),
+            error_format: theme.format(&theme.error),
+            hyperlink_format: theme.format(&theme.hyperlink),
+            size_format: theme.format(&theme.plain).set_num_format("#,##0"),
+            human_size_format: theme
+                .format(&theme.plain)
+                .set_align(rust_xlsxwriter::FormatAlign::Right),
+            date_format: theme
+                .format(&theme.plain)
+                .set_num_format("yyyy-mm-dd hh:mm"),
+            text_format: theme.format(&theme.plain),
+            stats_format: theme.format(&theme.stats),
+            category_formats: theme.category_formats(),
+        }
+    }
+
+    /// 单元格用途对应的格式
+    fn for_kind(&self, kind: CellKind) -> &Format {
+        match kind {
+            CellKind::Header => &self.header_format,
+            CellKind::Directory => &self.dir_format,
+            CellKind::File => &self.file_format,
+            CellKind::Path => &self.path_format,
+            CellKind::Size => &self.size_format,
+            CellKind::Date => &self.date_format,
+            CellKind::Notes => &self.notes_format,
+            CellKind::Stats => &self.stats_format,
+        }
+    }
+}
+
+/// 扩展名分布图表类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    Pie, // 饼图
+    Bar, // 条形图
+}
+
+/// 层级的呈现方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    #[default]
+    Merged, // 上级目录合并单元格
+    Outline, // 每行只写项目本身，目录下的行分组，可用+/-折叠展开
+    Flat,    // 没有层级列和合并单元格，每行写出名称、深度、类型及上级目录，便于数据透视表
+    Indent,  // 单个名称列，按层级缩进，接近tree命令的原始样式
+}
+
+/// 大小列的呈现方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizeFormat {
+    Bytes, // 只输出字节数（数字，可排序求和）
+    Human, // 只输出易读的大小，如 "1.4 MiB"
+    #[default]
+    Both, // 两列都输出
+}
+
+/// Excel生成器，通过ExcelGenerator::builder()配置
+pub struct ExcelGenerator {
+    scan_secrets: bool,                           // 是否输出安全标记列
+    stats_sheet: bool,                            // 是否增加"统计"工作表
+    chart: Option<(ChartKind, bool)>, // 统计工作表中的扩展名分布图表（类型, 是否按大小）
+    top: Option<usize>,               // "最大文件"和"最长路径"工作表的行数
+    hyperlink_base: Option<PathBuf>,  // 完整路径超链接到的本地目录
+    link_prefix: Option<String>,      // 文件完整路径超链接的网址前缀
+    link_names: bool,                 // 文件名单元格也写为超链接
+    theme: Theme,                     // 配色主题
+    color_by_type: bool,              // 按文件类别为文件名着色
+    detect_type: bool,                // 是否输出文件类型列
+    layout: Layout,                   // 层级的呈现方式
+    size_format: SizeFormat,          // 大小列的呈现方式
+    autofit: bool,                    // 按内容自动计算列宽
+    col_widths: HashMap<String, f64>, // 手动指定的列宽（列名小写，如"l1"、"path"）
+    lang: Language,                   // 表头、统计标签等的语言
+    extra_cols: Vec<String>,          // 备注列之后的空白列名称
+    status_values: Vec<String>,       // 状态列下拉列表的选项（为空时不输出状态列）
+    max_level_cap: usize,             // 层级列数上限（更深的层级合并到最后一个层级列）
+    merge_window: usize,              // 流式生成时合并单元格检测向前查看的行数
+    custom_columns: Vec<Arc<dyn ColumnProvider>>, // 自定义列，位于状态列和备注列之前
+    path_column: PathColumn,          // 完整路径列的表头和列宽
+    size_column: SizeColumn,          // 大小列的表头和列宽
+    modified_column: ModifiedColumn,  // 修改时间列的表头和列宽
+    notes_column: NotesColumn,        // 备注列的表头和列宽
+}
+
+/// ExcelGenerator的构建器：各选项链式设置，build()时统一校验
+#[derive(Clone, Default)]
+pub struct ExcelGeneratorBuilder {
+    scan_secrets: bool,
+    stats_sheet: bool,
+    chart: Option<(ChartKind, bool)>,
+    top: Option<usize>,
+    hyperlink_base: Option<PathBuf>,
+    link_prefix: Option<String>,
+    link_names: bool,
+    theme: Theme,
+    color_by_type: bool,
+    detect_type: bool,
+    layout: Layout,
+    size_format: SizeFormat,
+    autofit: bool,
+    col_widths: Vec<String>, // 列宽设置（build时解析，可引用任意顺序设置的附加列）
+    lang: Language,
+    extra_cols: Vec<String>,
+    status_values: Option<Vec<String>>,
+    max_level_cap: Option<usize>,
+    merge_window: Option<usize>,
+    custom_columns: Vec<Arc<dyn ColumnProvider>>,
+}
+
+impl ExcelGeneratorBuilder {
+    /// 是否输出安全标记列（疑似密钥、证书等敏感文件）
+    pub fn scan_secrets(mut self, enabled: bool) -> Self {
+        self.scan_secrets = enabled;
+        self
+    }
+
+    /// 将完整路径列写为指向base下对应文件的file://超链接
+    pub fn hyperlinks(mut self, base: PathBuf) -> Self {
+        self.hyperlink_base = Some(base);
+        self
+    }
+
+    /// 将文件的完整路径写为网址前缀+路径的超链接（如GitHub的 .../blob/main/）
+    pub fn link_prefix(mut self, prefix: String) -> Self {
+        self.link_prefix = Some(prefix);
+        self
+    }
+
+    /// 开启超链接时文件名单元格也写为超链接
+    pub fn link_names(mut self, enabled: bool) -> Self {
+        self.link_names = enabled;
+        self
+    }
+
+    /// 设置配色主题
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// 增加"文件类型"列：按内容识别出的类型（目录遍历时）或扩展名给出易读的名称，如"PNG图片"
+    pub fn detect_type(mut self, enabled: bool) -> Self {
+        self.detect_type = enabled;
+        self
+    }
+
+    /// 按主题中的文件类别（代码、文档、图片、二进制、配置等）为文件名单元格着色
+    pub fn color_by_type(mut self, enabled: bool) -> Self {
+        self.color_by_type = enabled;
+        self
+    }
+
+    /// 设置层级的呈现方式
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// 设置大小列的呈现方式：字节数、易读的大小或两者
+    pub fn size_format(mut self, size_format: SizeFormat) -> Self {
+        self.size_format = size_format;
+        self
+    }
+
+    /// 按各列最长的内容自动设置列宽（低内存模式按开头的行估算）
+    pub fn autofit(mut self, enabled: bool) -> Self {
+        self.autofit = enabled;
+        self
+    }
+
+    /// 手动指定列宽，如 "L1=30,L2=25,path=80"（列名不区分大小写，优先于自动列宽）
+    pub fn col_widths(mut self, spec: &str) -> Self {
+        self.col_widths.push(spec.to_string());
+        self
+    }
+
+    /// 设置表头、统计标签等输出内容的语言
+    pub fn lang(mut self, lang: Language) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// 在备注列之后追加空白列（如"负责人"、"审核状态"），供审核时填写
+    pub fn extra_cols(mut self, names: Vec<String>) -> Self {
+        self.extra_cols = names;
+        self
+    }
+
+    /// 在备注列之前增加"状态"列，数据行只能从下拉列表中选择values中的值
+    pub fn status_values(mut self, values: Vec<String>) -> Self {
+        self.status_values = Some(values);
+        self
+    }
+
+    /// 增加"统计"工作表：扩展名分布、各目录文件数、总大小及最大深度
+    pub fn stats_sheet(mut self, enabled: bool) -> Self {
+        self.stats_sheet = enabled;
+        self
+    }
+
+    /// 在"统计"工作表中嵌入扩展名分布图表（按文件数或大小），同时启用统计工作表
+    pub fn chart(mut self, kind: ChartKind, by_size: bool) -> Self {
+        self.stats_sheet = true;
+        self.chart = Some((kind, by_size));
+        self
+    }
+
+    /// 增加"最大文件"和"最长路径"工作表，各列出前n项；超过Windows路径长度上限的路径标出警告
+    pub fn top(mut self, n: usize) -> Self {
+        self.top = Some(n);
+        self
+    }
+
+    /// 层级列最多n列，更深的层级合并到最后一个层级列（如 "c/d/e.txt"）
+    pub fn max_level_cap(mut self, n: usize) -> Self {
+        self.max_level_cap = Some(n);
+        self
+    }
+
+    /// 流式生成（generate_streaming）时合并单元格检测向前查看的行数，默认10000；
+    /// 窗口越大能合并的范围越长，占用的内存也越多
+    pub fn merge_window(mut self, rows: usize) -> Self {
+        self.merge_window = Some(rows);
+        self
+    }
+
+    /// 追加自定义列（如Jira编号、按CSV映射的负责人），可多次调用，位于状态列和备注列之前
+    pub fn column(mut self, provider: impl ColumnProvider + 'static) -> Self {
+        self.custom_columns.push(Arc::new(provider));
+        self
+    }
+
+    /// 校验列宽、状态列表等设置并生成ExcelGenerator
+    pub fn build(self) -> Result<ExcelGenerator> {
+        if self.max_level_cap == Some(0) {
+            bail!("层级列数上限至少为1");
+        }
+        if self.merge_window == Some(0) {
+            bail!("合并检测窗口至少为1行");
+        }
+        let mut col_widths = HashMap::new();
+        for entry in self
+            .col_widths
+            .iter()
+            .flat_map(|spec| spec.split(','))
+            .filter(|entry| !entry.trim().is_empty())
+        {
+            let (key, width) = entry
+                .split_once('=')
+                .with_context(|| format!("列宽格式应为 列名=宽度: {entry}"))?;
+            let key = key.trim().to_lowercase();
+            let width: f64 = width
+                .trim()
+                .parse()
+                .ok()
+                .filter(|width| (0.0..=255.0).contains(width))
+                .with_context(|| format!("无效的列宽（0-255）: {entry}"))?;
+
+            let is_level = key
+                .strip_prefix('l')
+                .and_then(|level| level.parse::<usize>().ok())
+                .is_some_and(|level| level >= 1);
+            let is_extra = self
+                .extra_cols
+                .iter()
+                .map(String::as_str)
+                .chain(self.custom_columns.iter().map(|column| column.name()))
+                .any(|name| name.to_lowercase() == key);
+            if !is_level && !is_extra && !COLUMN_KEYS.contains(&key.as_str()) {
+                bail!(
+                    "未知的列名: {key}（可用: L1、L2…、{}）",
+                    COLUMN_KEYS.join("、")
+                );
+            }
+            col_widths.insert(key, width);
+        }
+
+        let status_values = match &self.status_values {
+            Some(values) => {
+                let values: Vec<String> = values
+                    .iter()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+                    .collect();
+                if values.is_empty() {
+                    bail!("状态列表不能为空");
+                }
+                // Excel限制下拉列表（含逗号）最多255个字符
+                DataValidation::new()
+                    .allow_list_strings(&values)
+                    .context("状态列表过长（含逗号最多255个字符）")?;
+                values
+            }
+            None => Vec::new(),
+        };
+        Ok(self.finish(col_widths, status_values))
+    }
+
+    /// 生成ExcelGenerator（列宽及状态列表已校验）
+    fn finish(
+        self,
+        col_widths: HashMap<String, f64>,
+        status_values: Vec<String>,
+    ) -> ExcelGenerator {
+        ExcelGenerator {
+            scan_secrets: self.scan_secrets,
+            stats_sheet: self.stats_sheet,
+            chart: self.chart,
+            top: self.top,
+            hyperlink_base: self.hyperlink_base,
+            link_prefix: self.link_prefix,
+            link_names: self.link_names,
+            theme: self.theme,
+            color_by_type: self.color_by_type,
+            detect_type: self.detect_type,
+            layout: self.layout,
+            size_format: self.size_format,
+            autofit: self.autofit,
+            col_widths,
+            lang: self.lang,
+            extra_cols: self.extra_cols,
+            status_values,
+            max_level_cap: self.max_level_cap.unwrap_or(usize::MAX),
+            merge_window: self.merge_window.unwrap_or(MERGE_WINDOW),
+            custom_columns: self.custom_columns,
+            path_column: PathColumn::new(self.lang),
+            size_column: SizeColumn::new(self.lang),
+            modified_column: ModifiedColumn::new(self.lang),
+            notes_column: NotesColumn::new(self.lang),
+        }
+    }
+}
+
+impl ExcelGenerator {
+    /// 默认配置的生成器，其他选项通过builder()设置
+    pub fn new(scan_secrets: bool) -> Self {
+        Self::builder()
+            .scan_secrets(scan_secrets)
+            .finish(HashMap::new(), Vec::new())
+    }
+
+    /// 链式设置各选项后调用build()，如
+    /// `ExcelGenerator::builder().layout(Layout::Outline).autofit(true).build()?`
+    pub fn builder() -> ExcelGeneratorBuilder {
+        ExcelGeneratorBuilder::default()
+    }
+
+    /// 确定层级列之后的附加列（大小列仅在输入提供大小时输出）
+    fn columns(&self, rows: &[ExcelRow]) -> Vec<Column> {
+        // 根目录列仅在多根输入（tree dirA dirB）时输出
+        let mut columns = Vec::new();
+        let first_root = rows.iter().find_map(|row| row.root.as_ref());
+        if rows
+            .iter()
+            .any(|row| row.root.is_some() && row.root.as_ref() != first_root)
+        {
+            columns.push(Column::Root);
+        }
+        if self.layout == Layout::Indent {
+            columns.push(Column::Name);
+        }
+        columns.push(Column::Path);
+        if rows.iter().any(|row| row.change.is_some()) {
+            columns.push(Column::Change);
+        }
+        if self.layout == Layout::Flat {
+            columns.extend([Column::Name, Column::Depth, Column::Type, Column::Parent]);
+        }
+        if rows.iter().any(|row| row.permissions.is_some()) {
+            columns.push(Column::Permissions);
+        }
+        if rows.iter().any(|row| row.owner.is_some()) {
+            columns.push(Column::Owner);
+        }
+        if rows.iter().any(|row| row.group.is_some()) {
+            columns.push(Column::Group);
+        }
+        if rows.iter().any(|row| row.link_target.is_some()) {
+            columns.push(Column::LinkTarget);
+        }
+        if self.detect_type {
+            columns.push(Column::FileKind);
+        }
+        if rows.iter().any(|row| row.lines.is_some()) {
+            columns.extend([Column::Lines, Column::CodeLanguage]);
+        }
+        if rows.iter().any(|row| row.size.is_some()) {
+            if self.size_format != SizeFormat::Human {
+                columns.push(Column::Size);
+            }
+            if self.size_format != SizeFormat::Bytes {
+                columns.push(Column::SizeHuman);
+            }
+        }
+        if rows.iter().any(|row| row.old_size.is_some()) {
+            columns.push(Column::OldSize);
+        }
+        if rows.iter().any(|row| row.rollup.is_some()) {
+            columns.extend([Column::TotalSize, Column::FileCount, Column::DirCount]);
+        }
+        if rows.iter().any(|row| row.modified.is_some()) {
+            columns.push(Column::Modified);
+        }
+        if rows.iter().any(|row| row.storage_class.is_some()) {
+            columns.push(Column::StorageClass);
+        }
+        if rows.iter().any(|row| row.layer.is_some()) {
+            columns.extend([Column::Layer, Column::LayerCommand]);
+        }
+        if rows.iter().any(|row| row.git.is_some()) {
+            columns.extend([Column::GitStatus, Column::LastCommit, Column::Author]);
+        }
+        if let Some(hash) = rows.iter().find_map(|row| row.hash.as_ref()) {
+            columns.push(Column::Hash(hash.algorithm));
+        }
+        if rows.iter().any(|row| row.duplicate_group.is_some()) {
+            columns.push(Column::Duplicate);
+        }
+        if self.scan_secrets {
+            columns.push(Column::Secret);
+        }
+        columns.extend((0..self.custom_columns.len()).map(Column::Custom));
+        if !self.status_values.is_empty() {
+            columns.push(Column::Status);
+        }
+        columns.push(Column::Notes);
+        columns.extend((0..self.extra_cols.len()).map(Column::Extra));
+        columns
+    }
+
+    /// 生成Excel文件
+    pub fn generate(&self, items: Vec<TreeItem>, output_path: &str) -> Result<()> {
+        let mut workbook = Workbook::new();
+        let stats = self.stats_sheet.then(|| TreeStats::from_items(&items));
+        let top = self.top.map(|limit| TopItems::from_items(limit, &items));
+        let duplicates = DuplicateGroup::from_items(&items);
+        let mut used_names = Vec::new();
+        self.write_sheet(&mut workbook, None, &mut used_names, items)?;
+        if let Some(stats) = stats {
+            self.write_stats_sheet(&mut workbook, &mut used_names, &stats)?;
+        }
+        if let Some(top) = top {
+            self.write_top_sheets(&mut workbook, &mut used_names, &top)?;
+        }
+        self.write_duplicates_sheet(&mut workbook, &mut used_names, &duplicates)?;
+
+        // 保存文件（"-"表示写到标准输出）
+        save_workbook(&mut workbook, output_path)?;
+
+        Ok(())
+    }
+
+    /// 生成包含多个工作表的Excel文件，每组(名称, 项目列表)写入一个工作表
+    pub fn generate_sheets(
+        &self,
+        sheets: Vec<(String, Vec<TreeItem>)>,
+        output_path: &str,
+    ) -> Result<()> {
+        let mut workbook = Workbook::new();
+        let mut used_names = Vec::new();
+        let stats = self
+            .stats_sheet
+            .then(|| TreeStats::from_items(sheets.iter().flat_map(|(_, items)| items)));
+        let top = self
+            .top
+            .map(|limit| TopItems::from_items(limit, sheets.iter().flat_map(|(_, items)| items)));
+        let duplicates = DuplicateGroup::from_items(sheets.iter().flat_map(|(_, items)| items));
+
+        for (name, items) in sheets {
+            self.write_sheet(&mut workbook, Some(&name), &mut used_names, items)?;
+        }
+        if let Some(stats) = stats {
+            self.write_stats_sheet(&mut workbook, &mut used_names, &stats)?;
+        }
+        if let Some(top) = top {
+            self.write_top_sheets(&mut workbook, &mut used_names, &top)?;
+        }
+        self.write_duplicates_sheet(&mut workbook, &mut used_names, &duplicates)?;
+
+        // 保存文件（"-"表示写到标准输出）
+        save_workbook(&mut workbook, output_path)?;
+
+        Ok(())
+    }
+
+    /// 生成每组一个工作表的Excel文件，并在最前面增加"目录"索引工作表，
+    /// 列出各工作表的超链接及其目录数、文件数；各组中的统计行移到索引工作表末尾
+    pub fn generate_with_index(
+        &self,
+        sheets: Vec<(String, Vec<TreeItem>)>,
+        output_path: &str,
+    ) -> Result<()> {
+        let mut workbook = Workbook::new();
+        let index_name = self.lang.text("目录", "Index");
+        let mut used_names = vec![index_name.to_string()];
+        workbook.add_worksheet().set_name(index_name)?;
+        let stats = self
+            .stats_sheet
+            .then(|| TreeStats::from_items(sheets.iter().flat_map(|(_, items)| items)));
+        let top = self
+            .top
+            .map(|limit| TopItems::from_items(limit, sheets.iter().flat_map(|(_, items)| items)));
+        let duplicates = DuplicateGroup::from_items(sheets.iter().flat_map(|(_, items)| items));
+
+        // 写入各组工作表，记录索引行（工作表名称, 目录数, 文件数）
+        let mut entries = Vec::new();
+        let mut stats_items = Vec::new();
+        for (name, items) in sheets {
+            let (stats, items): (Vec<TreeItem>, Vec<TreeItem>) = items
+                .into_iter()
+                .partition(|item| item.name.starts_with("📊"));
+            stats_items.extend(stats);
+
+            let file_count = items.iter().filter(|item| item.is_file).count();
+            let dir_count = items.len() - file_count;
+            let start = used_names.len();
+            self.write_sheet(&mut workbook, Some(&name), &mut used_names, items)?;
+            entries.push((used_names[start].clone(), dir_count, file_count));
+        }
+        if let Some(stats) = stats {
+            self.write_stats_sheet(&mut workbook, &mut used_names, &stats)?;
+        }
+        if let Some(top) = top {
+            self.write_top_sheets(&mut workbook, &mut used_names, &top)?;
+        }
+        self.write_duplicates_sheet(&mut workbook, &mut used_names, &duplicates)?;
+
+        let formats = ExcelFormats::new(&self.theme);
+
+        let index = workbook.worksheet_from_index(0)?;
+        let headers = [
+            (self.lang.text("工作表", "Sheet"), 30.0),
+            (self.lang.text("目录数", "Directories"), 12.0),
+            (self.lang.text("文件数", "Files"), 12.0),
+        ];
+        for (col, (header, width)) in headers.into_iter().enumerate() {
+            index.write_with_format(0, col as u16, header, &formats.header_format)?;
+            index.set_column_width(col as u16, width)?;
+        }
+
+        let mut current_row = 1u32;
+        for (sheet_name, dir_count, file_count) in entries {
+            // 工作表名称中的单引号需要写成两个
+            let link = Url::new(format!("internal:'{}'!A1", sheet_name.replace('\'', "''")));
+            index.write_url_with_text(current_row, 0, link, &sheet_name)?;
+            index.write_with_format(current_row, 1, dir_count as f64, &formats.size_format)?;
+            index.write_with_format(current_row, 2, file_count as f64, &formats.size_format)?;
+            current_row += 1;
+        }
+        for stats_item in stats_items {
+            index.set_row_height(current_row, 20.0)?;
+            index.merge_range(
+                current_row,
+                0,
+                current_row,
+                2,
+                &self.lang.stats_label(&stats_item.name),
+                &formats.stats_format,
+            )?;
+            current_row += 1;
+        }
+        let _ = index.set_freeze_panes(1, 0);
+
+        // 保存文件（"-"表示写到标准输出）
+        save_workbook(&mut workbook, output_path)?;
+
+        Ok(())
+    }
+
+    /// 流式生成Excel文件（constant memory模式）：逐行转换并写入，不保留全部行数据。
+    /// 层级列数和附加列按前merge_window行确定，更深的层级合并到最后一个层级列；
+    /// 合并单元格只在向前merge_window行内检测，更长的范围只在首行写出名称。
+    /// 超过Excel行数上限时自动续写到Sheet2、Sheet3…，每个工作表重复表头。
+    /// 每写入一行调用一次progress(已写入行数)，返回写入的数据行数
+    pub fn generate_streaming<I>(
+        &self,
+        items: I,
+        output_path: &str,
+        mut progress: impl FnMut(usize),
+    ) -> Result<usize>
+    where
+        I: IntoIterator<Item = Result<TreeItem>>,
+    {
+        if self.layout != Layout::Merged {
+            bail!("低内存模式只支持合并单元格布局");
+        }
+
+        let mut items = items.into_iter();
+        let mut stats_items = Vec::new();
+        let mut stats = TreeStats::new();
+        let mut top = self.top.map(TopItems::new);
+
+        // 读入前部确定层级列数和附加列
+        let mut prefix = Vec::new();
+        let mut exhausted = true;
+        for item in items.by_ref() {
+            let item = item?;
+            if self.stats_sheet {
+                stats.add(&item);
+            }
+            if let Some(top) = &mut top {
+                top.add(&item);
+            }
+            if item.name.starts_with("📊") {
+                stats_items.push(item);
+            } else {
+                prefix.push(item);
+            }
+            if prefix.len() >= self.merge_window {
+                exhausted = false;
+                break;
+            }
+        }
+        let max_level = prefix
+            .iter()
+            .map(|item| item.level)
+            .max()
+            .unwrap_or(1)
+            .min(self.max_level_cap);
+        let mut window: VecDeque<ExcelRow> = prefix
+            .into_iter()
+            .map(|item| self.to_row(item, max_level))
+            .collect();
+        let columns = self.columns(window.make_contiguous());
+
+        // 自动列宽按向前查看窗口中的行估算
+        let mut workbook = Workbook::new();
+        let mut worksheet = workbook.add_worksheet_with_constant_memory();
+        let sample: Vec<&ExcelRow> = window.iter().collect();
+        self.setup_worksheet(worksheet, max_level, &columns, &sample)?;
+        let formats = ExcelFormats::new(&self.theme);
+        let total_cols = max_level + columns.len();
+
+        let mut previous: Option<ExcelRow> = None;
+        let mut row_count = 0;
+        let mut sheet_rows = 0;
+        loop {
+            // 补足向前查看的窗口
+            while !exhausted && window.len() < self.merge_window {
+                match items.next() {
+                    Some(item) => {
+                        let item = item?;
+                        if self.stats_sheet {
+                            stats.add(&item);
+                        }
+                        if let Some(top) = &mut top {
+                            top.add(&item);
+                        }
+                        if item.name.starts_with("📊") {
+                            stats_items.push(item);
+                        } else {
+                            window.push_back(self.to_row(item, max_level));
+                        }
+                    }
+                    None => exhausted = true,
+                }
+            }
+
+            let Some(row) = window.pop_front() else {
+                break;
+            };
+
+            // 当前工作表已满时续写到下一个工作表，合并范围从新工作表首行重新开始
+            if sheet_rows == SHEET_ROWS {
+                self.add_status_validation(worksheet, max_level, &columns, sheet_rows)?;
+                self.finish_sheet(worksheet, sheet_rows, total_cols)?;
+                worksheet = workbook.add_worksheet_with_constant_memory();
+                let sample: Vec<&ExcelRow> = window.iter().collect();
+                self.setup_worksheet(worksheet, max_level, &columns, &sample)?;
+                previous = None;
+                sheet_rows = 0;
+            }
+            let row_num = sheet_rows as u32 + 1;
+            let rows_left = SHEET_ROWS - sheet_rows - 1;
+            let leaf_idx = row.levels.iter().rposition(|name| !name.is_empty());
+            for (level_idx, level_name) in row.levels.iter().enumerate() {
+                if level_name.is_empty() {
+                    continue;
+                }
+
+                // 与上一行属于同一父目录下的同一项目时，已包含在上一行开始的范围内
+                let same_run = |other: &ExcelRow| {
+                    other.root == row.root && other.levels[..=level_idx] == row.levels[..=level_idx]
+                };
+                if previous.as_ref().is_some_and(same_run) {
+                    continue;
+                }
+
+                // 向前查看连续相同的行数，范围在窗口或工作表内结束时才能合并
+                let following = window.iter().take_while(|other| same_run(other)).count();
+                let closed = following < window.len() || exhausted || following >= rows_left;
+                let following = following.min(rows_left);
+                let col = level_idx as u16;
+                if following > 0 && closed {
+                    worksheet.merge_range(
+                        row_num,
+                        col,
+                        row_num + following as u32,
+                        col,
+                        level_name,
+                        &formats.dir_format,
+                    )?;
+                } else {
+                    let is_leaf = Some(level_idx) == leaf_idx;
+                    self.write_level_cell(worksheet, row_num, col, &row, is_leaf, &formats)?;
+                }
+            }
+
+            for (offset, column) in columns.iter().enumerate() {
+                let col = (max_level + offset) as u16;
+                self.write_column_cell(worksheet, row_num, col, *column, &row, &formats)?;
+            }
+
+            previous = Some(row);
+            row_count += 1;
+            sheet_rows += 1;
+            progress(row_count);
+        }
+
+        self.add_status_validation(worksheet, max_level, &columns, sheet_rows)?;
+
+        // 写入统计行（当前工作表放不下时写到新工作表）
+        if sheet_rows + stats_items.len() > SHEET_ROWS {
+            self.finish_sheet(worksheet, sheet_rows, total_cols)?;
+            worksheet = workbook.add_worksheet_with_constant_memory();
+            self.setup_worksheet(worksheet, max_level, &columns, &[])?;
+            sheet_rows = 0;
+        }
+        let mut current_row = sheet_rows as u32 + 1;
+        for stats_item in stats_items {
+            worksheet.set_row_height(current_row, 20.0)?;
+            worksheet.merge_range(
+                current_row,
+                0,
+                current_row,
+                (total_cols - 1) as u16,
+                &self.lang.stats_label(&stats_item.name),
+                &formats.stats_format,
+            )?;
+            current_row += 1;
+        }
+        self.finish_sheet(worksheet, current_row as usize - 1, total_cols)?;
+        let mut used_names = Vec::new();
+        if self.stats_sheet {
+            self.write_stats_sheet(&mut workbook, &mut used_names, &stats)?;
+        }
+        if let Some(top) = top {
+            self.write_top_sheets(&mut workbook, &mut used_names, &top)?;
+        }
+
+        // 保存文件（"-"表示写到标准输出）
+        save_workbook(&mut workbook, output_path)?;
+
+        Ok(row_count)
+    }
+
+    /// 写入"统计"工作表：概览（A-B列）、扩展名分布（D-F列）、各目录文件数（H-K列），
+    /// 数值均写为数字；大小列仅在输入提供大小时输出
+    fn write_stats_sheet(
+        &self,
+        workbook: &mut Workbook,
+        used_names: &mut Vec<String>,
+        stats: &TreeStats,
+    ) -> Result<()> {
+        let sheet_name = self.sheet_name(self.lang.text("统计", "Statistics"), used_names);
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(&sheet_name)?;
+        used_names.push(sheet_name.clone());
+
+        let formats = ExcelFormats::new(&self.theme);
+        let has_size = stats.total_size.is_some();
+
+        let mut write_headers = |first_col: u16, headers: &[(&str, f64)]| -> Result<()> {
+            for (offset, (header, width)) in headers.iter().enumerate() {
+                let col = first_col + offset as u16;
+                worksheet.write_with_format(0, col, *header, &formats.header_format)?;
+                worksheet.set_column_width(col, *width)?;
+            }
+            Ok(())
+        };
+        let lang = self.lang;
+        let files = lang.text("文件数", "Files");
+        write_headers(
+            0,
+            &[
+                (lang.text("项目", "Item"), 14.0),
+                (lang.text("数值", "Value"), 16.0),
+            ],
+        )?;
+        write_headers(
+            3,
+            &[(lang.text("扩展名", "Extension"), 14.0), (files, 10.0)],
+        )?;
+        write_headers(
+            7,
+            &[
+                (lang.text("目录", "Directory"), 40.0),
+                (files, 10.0),
+                (lang.text("含子目录文件数", "Files (Recursive)"), 16.0),
+            ],
+        )?;
+        if has_size {
+            write_headers(5, &[(lang.text("大小", "Size"), 16.0)])?;
+            write_headers(
+                10,
+                &[(lang.text("大小（含子目录）", "Size (Recursive)"), 18.0)],
+            )?;
+        }
+
+        // 概览
+        let mut overview = vec![
+            (files, stats.file_count as f64),
+            (lang.text("目录数", "Directories"), stats.dir_count as f64),
+            (lang.text("最大深度", "Max Depth"), stats.max_depth as f64),
+        ];
+        if let Some(total_size) = stats.total_size {
+            overview.push((lang.text("总大小", "Total Size"), total_size as f64));
+        }
+        for (row, (label, value)) in overview.into_iter().enumerate() {
+            let row = row as u32 + 1;
+            worksheet.write_with_format(row, 0, label, &formats.text_format)?;
+            worksheet.write_with_format(row, 1, value, &formats.size_format)?;
+        }
+
+        // 扩展名分布（按大小作图时按大小排列）
+        let by_size = has_size && self.chart.is_some_and(|(_, by_size)| by_size);
+        let mut extensions = stats.extensions();
+        if by_size {
+            extensions.sort_by_key(|extension| std::cmp::Reverse(extension.size));
+        }
+        let extension_count = extensions.len();
+        for (row, extension) in extensions.into_iter().enumerate() {
+            let row = row as u32 + 1;
+            let label = if extension.extension.is_empty() {
+                lang.text("(无扩展名)", "(no extension)").to_string()
+            } else {
+                format!(".{}", extension.extension)
+            };
+            worksheet.write_with_format(row, 3, &label, &formats.text_format)?;
+            worksheet.write_with_format(
+                row,
+                4,
+                extension.file_count as f64,
+                &formats.size_format,
+            )?;
+            if has_size {
+                worksheet.write_with_format(row, 5, extension.size as f64, &formats.size_format)?;
+            }
+        }
+
+        // 各目录文件数
+        for (row, (path, directory)) in stats.directories().enumerate() {
+            let row = row as u32 + 1;
+            worksheet.write_with_format(row, 7, path, &formats.path_format)?;
+            worksheet.write_with_format(
+                row,
+                8,
+                directory.file_count as f64,
+                &formats.size_format,
+            )?;
+            worksheet.write_with_format(
+                row,
+                9,
+                directory.total_file_count as f64,
+                &formats.size_format,
+            )?;
+            if has_size {
+                worksheet.write_with_format(
+                    row,
+                    10,
+                    directory.total_size as f64,
+                    &formats.size_format,
+                )?;
+            }
+        }
+
+        // 扩展名分布图表：取前CHART_EXTENSIONS个扩展名，放在表格右侧
+        if let (Some((kind, _)), true) = (self.chart, extension_count > 0) {
+            let last_row = extension_count.min(CHART_EXTENSIONS) as u32;
+            let (value_col, title) = if by_size {
+                (5, lang.text("扩展名分布（大小）", "Extensions by Size"))
+            } else {
+                (4, lang.text("扩展名分布（文件数）", "Extensions by Count"))
+            };
+
+            let mut chart = match kind {
+                ChartKind::Pie => Chart::new_pie(),
+                ChartKind::Bar => Chart::new_bar(),
+            };
+            chart.title().set_name(title);
+            let series = chart
+                .add_series()
+                .set_categories((sheet_name.as_str(), 1, 3, last_row, 3))
+                .set_values((sheet_name.as_str(), 1, value_col, last_row, value_col));
+            match kind {
+                ChartKind::Pie => {
+                    series.set_data_label(ChartDataLabel::new().show_percentage());
+                }
+                ChartKind::Bar => {
+                    series.set_data_label(ChartDataLabel::new().show_value());
+                    chart.legend().set_hidden();
+                }
+            }
+            worksheet.insert_chart(1, 12, &chart)?;
+        }
+
+        let _ = worksheet.set_freeze_panes(1, 0);
+        Ok(())
+    }
+
+    /// 写入"最大文件"（输入有大小时）和"最长路径"工作表，
+    /// 超过Windows路径长度上限（260个字符）的路径及其长度以错误格式标出
+    fn write_top_sheets(
+        &self,
+        workbook: &mut Workbook,
+        used_names: &mut Vec<String>,
+        top: &TopItems,
+    ) -> Result<()> {
+        let lang = self.lang;
+        let formats = ExcelFormats::new(&self.theme);
+        let rank = (lang.text("排名", "Rank"), 8.0);
+        let path = (lang.text("完整路径", "Full Path"), 80.0);
+
+        let largest = top.largest();
+        if !largest.is_empty() {
+            let sheet_name = self.sheet_name(lang.text("最大文件", "Largest Files"), used_names);
+            let worksheet = workbook.add_worksheet();
+            worksheet.set_name(&sheet_name)?;
+            used_names.push(sheet_name);
+
+            let headers = [
+                rank,
+                path,
+                (lang.text("大小", "Size"), 16.0),
+                (lang.text("大小（易读）", "Size (Human)"), 12.0),
+            ];
+            for (col, (header, width)) in headers.into_iter().enumerate() {
+                worksheet.write_with_format(0, col as u16, header, &formats.header_format)?;
+                worksheet.set_column_width(col as u16, width)?;
+            }
+            for (index, (path, size)) in largest.into_iter().enumerate() {
+                let row = index as u32 + 1;
+                worksheet.write_with_format(row, 0, row as f64, &formats.text_format)?;
+                worksheet.write_with_format(row, 1, path, &formats.path_format)?;
+                worksheet.write_with_format(row, 2, size as f64, &formats.size_format)?;
+                worksheet.write_with_format(
+                    row,
+                    3,
+                    human_size(size),
+                    &formats.human_size_format,
+                )?;
+            }
+            let _ = worksheet.set_freeze_panes(1, 0);
+        }
+
+        let sheet_name = self.sheet_name(lang.text("最长路径", "Longest Paths"), used_names);
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(&sheet_name)?;
+        used_names.push(sheet_name);
+
+        let headers = [
+            rank,
+            path,
+            (lang.text("长度", "Length"), 10.0),
+            (lang.text("深度", "Depth"), 8.0),
+        ];
+        for (col, (header, width)) in headers.into_iter().enumerate() {
+            worksheet.write_with_format(0, col as u16, header, &formats.header_format)?;
+            worksheet.set_column_width(col as u16, width)?;
+        }
+        for (index, (path, length, depth)) in top.longest().into_iter().enumerate() {
+            let row = index as u32 + 1;
+            let (path_format, length_format) = if length > MAX_PATH_LEN {
+                (&formats.error_format, &formats.error_format)
+            } else {
+                (&formats.path_format, &formats.text_format)
+            };
+            worksheet.write_with_format(row, 0, row as f64, &formats.text_format)?;
+            worksheet.write_with_format(row, 1, path, path_format)?;
+            worksheet.write_with_format(row, 2, length as f64, length_format)?;
+            worksheet.write_with_format(row, 3, depth as f64, &formats.text_format)?;
+        }
+        let _ = worksheet.set_freeze_panes(1, 0);
+        Ok(())
+    }
+
+    /// 写入"重复文件"工作表（没有重复文件时不写入）：每组列出各文件的路径及大小，
+    /// 组的首行写出副本数及只保留一份时可节省的空间，末行为合计
+    fn write_duplicates_sheet(
+        &self,
+        workbook: &mut Workbook,
+        used_names: &mut Vec<String>,
+        groups: &[DuplicateGroup],
+    ) -> Result<()> {
+        if groups.is_empty() {
+            return Ok(());
+        }
+        let lang = self.lang;
+        let sheet_name = self.sheet_name(lang.text("重复文件", "Duplicates"), used_names);
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(&sheet_name)?;
+        used_names.push(sheet_name);
+
+        let formats = ExcelFormats::new(&self.theme);
+        let headers = [
+            (lang.text("重复组", "Group"), 10.0),
+            (lang.text("完整路径", "Full Path"), 60.0),
+            (lang.text("大小", "Size"), 14.0),
+            (lang.text("副本数", "Copies"), 10.0),
+            (lang.text("可节省", "Savings"), 14.0),
+        ];
+        for (col, (header, width)) in headers.into_iter().enumerate() {
+            worksheet.write_with_format(0, col as u16, header, &formats.header_format)?;
+            worksheet.set_column_width(col as u16, width)?;
+        }
+
+        let mut current_row = 1u32;
+        for group in groups {
+            for (index, path) in group.paths.iter().enumerate() {
+                worksheet.write_with_format(
+                    current_row,
+                    0,
+                    group.id as f64,
+                    &formats.duplicate_format,
+                )?;
+                worksheet.write_with_format(current_row, 1, path, &formats.path_format)?;
+                worksheet.write_with_format(
+                    current_row,
+                    2,
+                    group.size as f64,
+                    &formats.size_format,
+                )?;
+                if index == 0 {
+                    let copies = group.paths.len() as f64;
+                    let savings = group.savings() as f64;
+                    worksheet.write_with_format(current_row, 3, copies, &formats.size_format)?;
+                    worksheet.write_with_format(current_row, 4, savings, &formats.size_format)?;
+                } else {
+                    worksheet.write_with_format(current_row, 3, "", &formats.size_format)?;
+                    worksheet.write_with_format(current_row, 4, "", &formats.size_format)?;
+                }
+                current_row += 1;
+            }
+        }
+
+        // 合计：重复组数及可节省的总空间
+        let total: u64 = groups.iter().map(DuplicateGroup::savings).sum();
+        let label = format!(
+            "{} ({})",
+            lang.text("合计可节省", "Total Savings"),
+            human_size(total)
+        );
+        worksheet.merge_range(
+            current_row,
+            0,
+            current_row,
+            3,
+            &label,
+            &formats.stats_format,
+        )?;
+        let total_format = formats.stats_format.clone().set_num_format("#,##0");
+        worksheet.write_with_format(current_row, 4, total as f64, &total_format)?;
+
+        let _ = worksheet.set_freeze_panes(1, 0);
+        worksheet.autofilter(0, 0, current_row - 1, 4)?;
+        Ok(())
+    }
+
+    /// 生成合法且不重复的工作表名称（最长31个字符，不能包含 []:*?/\）
+    fn sheet_name(&self, name: &str, used_names: &[String]) -> String {
+        let base: String = name
+            .chars()
+            .map(|c| if "[]:*?/\\".contains(c) { '_' } else { c })
+            .take(31)
+            .collect::<String>()
+            .trim_matches('\'')
+            .to_string();
+        let base = if base.is_empty() {
+            "Sheet".to_string()
+        } else {
+            base
+        };
+
+        // 工作表名称不区分大小写，重名时追加序号
+        let is_used = |candidate: &str| {
+            used_names
+                .iter()
+                .any(|used| used.to_lowercase() == candidate.to_lowercase())
+        };
+        let mut candidate = base.clone();
+        let mut index = 2;
+        while is_used(&candidate) {
+            let suffix = format!(" ({index})");
+            let prefix: String = base.chars().take(31 - suffix.chars().count()).collect();
+            candidate = format!("{prefix}{suffix}");
+            index += 1;
+        }
+        candidate
+    }
+
+    /// 将项目列表写入工作表，超过Excel行数上限时自动续写到后续工作表。
+    /// 指定名称时工作表以该名称（续写的为"名称 (2)"…）命名，否则使用默认的Sheet1、Sheet2…
+    fn write_sheet(
+        &self,
+        workbook: &mut Workbook,
+        name: Option<&str>,
+        used_names: &mut Vec<String>,
+        items: Vec<TreeItem>,
+    ) -> Result<()> {
+        // 输入有大小时（目录遍历或tree -s/--du）汇总各目录，多根输入按根目录分别汇总
+        let mut rollups: HashMap<Option<String>, TreeStats> = HashMap::new();
+        if items.iter().any(|item| item.is_file && item.size.is_some()) {
+            for item in &items {
+                rollups.entry(item.root.clone()).or_default().add(item);
+            }
+        }
+
+        // 转换为Excel行数据（先转换以获取max_level，扁平和缩进布局没有层级列）
+        let custom: Vec<Vec<CellValue>> =
+            items.iter().map(|item| self.custom_values(item)).collect();
+        let mut rows = convert_to_rows_capped(items, self.max_level_cap);
+        for (row, custom) in rows.iter_mut().zip(custom) {
+            row.custom = custom;
+        }
+        for row in rows.iter_mut().filter(|row| !row.is_file) {
+            row.rollup = rollups
+                .get(&row.root)
+                .and_then(|stats| stats.directory(&row.full_path))
+                .cloned();
+        }
+        let max_level = if matches!(self.layout, Layout::Flat | Layout::Indent) {
+            0
+        } else if rows.is_empty() {
+            1
+        } else {
+            rows[0].max_level
+        };
+
+        let columns = self.columns(&rows);
+
+        // 分离统计行和数据行
+        let (stats_rows, data_rows): (Vec<&ExcelRow>, Vec<&ExcelRow>) =
+            rows.iter().partition(|row| row.levels[0].starts_with("📊"));
+
+        for (data_range, with_stats) in split_pages(data_rows.len(), stats_rows.len(), SHEET_ROWS) {
+            let worksheet = workbook.add_worksheet();
+            if let Some(name) = name {
+                let sheet_name = self.sheet_name(name, used_names);
+                worksheet
+                    .set_name(&sheet_name)
+                    .with_context(|| format!("无效的工作表名称: {sheet_name}"))?;
+                used_names.push(sheet_name);
+            }
+
+            // 设置标题和格式
+            let page_rows = &data_rows[data_range];
+            self.setup_worksheet(worksheet, max_level, &columns, page_rows)?;
+
+            // 写入数据（统计行只写在最后一个工作表）
+            let stats: &[&ExcelRow] = if with_stats { &stats_rows } else { &[] };
+            self.write_data(worksheet, page_rows, stats, max_level, &columns)?;
+        }
+
+        Ok(())
+    }
+
+    /// 设置工作表：写入表头并设置列宽（开启自动列宽时按rows的内容计算）
+    fn setup_worksheet(
+        &self,
+        worksheet: &mut Worksheet,
+        max_level: usize,
+        columns: &[Column],
+        rows: &[&ExcelRow],
+    ) -> Result<()> {
+        let formats = ExcelFormats::new(&self.theme);
+
+        // 动态生成表头
+        let mut col = 0;
+
+        // 层级列：L1, L2, L3, ...
+        for level in 1..=max_level {
+            let header = format!("L{level}");
+            let width = self.level_width(level, &header, rows);
+            worksheet.write_with_format(0, col as u16, &header, &formats.header_format)?;
+            worksheet.set_column_width(col as u16, width)?;
+            col += 1;
+        }
+
+        // 附加列：完整路径、大小、备注等
+        for column in columns {
+            let width = self.column_width(*column, rows);
+            worksheet.write_with_format(
+                0,
+                col as u16,
+                self.column_header(*column),
+                &formats.header_format,
+            )?;
+            worksheet.set_column_width(col as u16, width)?;
+            col += 1;
+        }
+
+        Ok(())
+    }
+
+    /// 层级列的列宽：手动指定 > 自动列宽 > 默认20
+    fn level_width(&self, level: usize, header: &str, rows: &[&ExcelRow]) -> f64 {
+        if let Some(width) = self.col_widths.get(&format!("l{level}")) {
+            return *width;
+        }
+        if !self.autofit {
+            return 20.0;
+        }
+        let names = rows.iter().map(|row| row.levels[level - 1].as_str());
+        fit_width(std::iter::once(header).chain(names))
+    }
+
+    /// 附加列的列宽：手动指定 > 自动列宽 > 默认宽度
+    fn column_width(&self, column: Column, rows: &[&ExcelRow]) -> f64 {
+        let key = match column {
+            Column::Extra(idx) => self.extra_cols[idx].to_lowercase(),
+            Column::Custom(idx) => self.custom_columns[idx].name().to_lowercase(),
+            _ => column.key().to_string(),
+        };
+        if let Some(width) = self.col_widths.get(&key) {
+            return *width;
+        }
+        if !self.autofit {
+            return self
+                .column_provider(column)
+                .map_or_else(|| column.width(), ColumnProvider::width);
+        }
+        let texts: Vec<String> = rows
+            .iter()
+            .map(|row| self.column_text(column, row))
+            .collect();
+        fit_width(
+            std::iter::once(self.column_header(column)).chain(texts.iter().map(String::as_str)),
+        )
+    }
+
+    /// 由ColumnProvider提供表头和列宽的列：完整路径、大小、修改时间、备注及自定义列
+    fn column_provider(&self, column: Column) -> Option<&dyn ColumnProvider> {
+        match column {
+            Column::Path => Some(&self.path_column),
+            Column::Size => Some(&self.size_column),
+            Column::Modified => Some(&self.modified_column),
+            Column::Notes => Some(&self.notes_column),
+            Column::Custom(idx) => Some(self.custom_columns[idx].as_ref()),
+            _ => None,
+        }
+    }
+
+    /// 附加列的表头（空白列为用户指定的名称）
+    fn column_header(&self, column: Column) -> &str {
+        match column {
+            Column::Extra(idx) => &self.extra_cols[idx],
+            _ => self
+                .column_provider(column)
+                .map_or_else(|| column.header(self.lang), ColumnProvider::name),
+        }
+    }
+
+    /// 文件类型列的文本（目录为空）
+    fn file_kind(&self, row: &ExcelRow) -> String {
+        if !row.is_file {
+            return String::new();
+        }
+        if row.link_target.is_some() {
+            return self.lang.text("符号链接", "Symlink").to_string();
+        }
+        let name = row.full_path.rsplit('/').next().unwrap_or_default();
+        file_kind::describe(name, row.sniffed_type.as_deref(), self.lang)
+    }
+
+    /// 语言列的文本：已统计行数的文本文件按扩展名识别的编程语言
+    fn code_language(&self, row: &ExcelRow) -> &'static str {
+        if row.lines.is_none() {
+            return "";
+        }
+        let name = row.full_path.rsplit('/').next().unwrap_or_default();
+        loc::language(name).unwrap_or("")
+    }
+
+    /// ColumnProvider提供的列在该行的值，内置列与write_table共用同一份取值
+    fn provider_value(&self, column: Column, row: &ExcelRow) -> CellValue {
+        if let Column::Custom(idx) = column {
+            return row.custom[idx].clone();
+        }
+        let Some(provider) = self.column_provider(column) else {
+            return CellValue::Empty;
+        };
+        // 内置列只用到这些字段
+        let item = TreeItem {
+            name: row
+                .full_path
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            is_file: row.is_file,
+            full_path: row.full_path.clone(),
+            size: row.size,
+            modified: row.modified,
+            error: row.error.clone(),
+            ..Default::default()
+        };
+        provider.value(&item)
+    }
+
+    /// 写入单元格的值（日期超出Excel支持范围时留空）
+    fn write_value(
+        &self,
+        worksheet: &mut Worksheet,
+        row_num: u32,
+        col: u16,
+        value: &CellValue,
+        format: &Format,
+    ) -> Result<()> {
+        match value {
+            CellValue::Empty => worksheet.write_blank(row_num, col, format)?,
+            CellValue::Text(text) => worksheet.write_with_format(row_num, col, text, format)?,
+            CellValue::Number(number) => {
+                worksheet.write_with_format(row_num, col, *number, format)?
+            }
+            CellValue::DateTime(datetime) => match self.to_excel_datetime(*datetime) {
+                Some(datetime) => worksheet.write_with_format(row_num, col, &datetime, format)?,
+                None => worksheet.write_blank(row_num, col, format)?,
+            },
+        };
+        Ok(())
+    }
+
+    /// 附加列单元格显示的文本（用于计算自动列宽）
+    fn column_text(&self, column: Column, row: &ExcelRow) -> String {
+        let name = row.full_path.rsplit('/').next().unwrap_or_default();
+        match column {
+            Column::Root => row.root.clone().unwrap_or_default(),
+            Column::Path | Column::Notes | Column::Custom(_) => {
+                value_text(&self.provider_value(column, row))
+            }
+            Column::Permissions => row.permissions.clone().unwrap_or_default(),
+            Column::Owner => row.owner.clone().unwrap_or_default(),
+            Column::Group => row.group.clone().unwrap_or_default(),
+            Column::LinkTarget => row.link_target.clone().unwrap_or_default(),
+            Column::FileKind => self.file_kind(row),
+            Column::Lines => row.lines.map_or(String::new(), |lines| {
+                let digits = lines.to_string();
+                "0".repeat(digits.len() + (digits.len() - 1) / 3)
+            }),
+            Column::CodeLanguage => self.code_language(row).to_string(),
+            // 千分位格式的数字
+            Column::Size => row.size.map_or(String::new(), |size| {
+                let digits = size.to_string();
+                "0".repeat(digits.len() + (digits.len() - 1) / 3)
+            }),
+            Column::SizeHuman => row.size.map_or(String::new(), human_size),
+            Column::OldSize => row.old_size.map_or(String::new(), |size| {
+                if self.size_format == SizeFormat::Human {
+                    human_size(size)
+                } else {
+                    let digits = size.to_string();
+                    "0".repeat(digits.len() + (digits.len() - 1) / 3)
+                }
+            }),
+            Column::Change => row
+                .change
+                .map_or("", |change| change.label(self.lang))
+                .to_string(),
+            Column::TotalSize => row.rollup.as_ref().map_or(String::new(), |rollup| {
+                if self.size_format == SizeFormat::Human {
+                    human_size(rollup.total_size)
+                } else {
+                    let digits = rollup.total_size.to_string();
+                    "0".repeat(digits.len() + (digits.len() - 1) / 3)
+                }
+            }),
+            Column::FileCount => row
+                .rollup
+                .as_ref()
+                .map_or(String::new(), |rollup| rollup.total_file_count.to_string()),
+            Column::DirCount => row
+                .rollup
+                .as_ref()
+                .map_or(String::new(), |rollup| rollup.total_dir_count.to_string()),
+            Column::Modified => row
+                .modified
+                .map_or(String::new(), |_| "yyyy-mm-dd hh:mm".to_string()),
+            Column::StorageClass => row.storage_class.clone().unwrap_or_default(),
+            Column::Layer => row
+                .layer
+                .as_ref()
+                .map_or(String::new(), |layer| layer.index.to_string()),
+            Column::LayerCommand => row
+                .layer
+                .as_ref()
+                .and_then(|layer| layer.created_by.clone())
+                .unwrap_or_default(),
+            Column::GitStatus => row
+                .git
+                .as_ref()
+                .map_or(String::new(), |git| git.status.label(self.lang).to_string()),
+            Column::LastCommit => row
+                .git
+                .as_ref()
+                .and_then(|git| git.last_commit)
+                .map_or(String::new(), |_| "yyyy-mm-dd hh:mm".to_string()),
+            Column::Author => row
+                .git
+                .as_ref()
+                .and_then(|git| git.author.clone())
+                .unwrap_or_default(),
+            Column::Hash(_) => row
+                .hash
+                .as_ref()
+                .map_or(String::new(), |hash| hash.value.clone()),
+            Column::Duplicate => row
+                .duplicate_group
+                .map_or(String::new(), |group| group.to_string()),
+            Column::Secret => detect_secret(name)
+                .filter(|_| row.is_file)
+                .map_or("", |label| self.lang.secret_label(label))
+                .to_string(),
+            Column::Status => self
+                .status_values
+                .iter()
+                .max_by_key(|value| value.chars().count())
+                .cloned()
+                .unwrap_or_default(),
+            Column::Extra(_) => String::new(),
+            // 缩进布局每级缩进约3个字符
+            Column::Name if self.layout == Layout::Indent => {
+                let indent = (row.full_path.split('/').count() - 1).min(MAX_INDENT);
+                format!("{}{name}", " ".repeat(indent * 3))
+            }
+            Column::Name => name.to_string(),
+            Column::Depth => row.full_path.split('/').count().to_string(),
+            Column::Type => self.lang.text("目录", "Directory").to_string(),
+            Column::Parent => parent_path(&row.full_path).to_string(),
+        }
+    }
+
+    /// 写入Excel数据（支持层级合并单元格）
+    fn write_data(
+        &self,
+        worksheet: &mut Worksheet,
+        data_rows: &[&ExcelRow],
+        stats_rows: &[&ExcelRow],
+        max_level: usize,
+        columns: &[Column],
+    ) -> Result<()> {
+        if data_rows.is_empty() && stats_rows.is_empty() {
+            return Ok(());
+        }
+
+        // 创建格式配置
+        let formats = ExcelFormats::new(&self.theme);
+
+        let mut current_row = 1u32;
+
+        // 写入数据行，实现层级合并单元格
+        self.write_data_with_merging(
+            worksheet,
+            data_rows,
+            max_level,
+            columns,
+            &formats,
+            &mut current_row,
+        )?;
+        self.add_status_validation(worksheet, max_level, columns, data_rows.len())?;
+        self.add_change_highlight(worksheet, max_level, columns, data_rows.len())?;
+
+        // 写入统计行
+        for stats_row in stats_rows {
+            let total_cols = max_level + columns.len();
+
+            // 设置统计行行高为20
+            worksheet.set_row_height(current_row, 20.0)?;
+
+            worksheet.merge_range(
+                current_row,
+                0,
+                current_row,
+                (total_cols - 1) as u16,
+                &self.lang.stats_label(&stats_row.levels[0]),
+                &formats.stats_format,
+            )?;
+            current_row += 1;
+        }
+
+        self.finish_sheet(
+            worksheet,
+            current_row as usize - 1,
+            max_level + columns.len(),
+        )
+    }
+
+    /// 为第1行到last_row行的状态列设置下拉列表
+    fn add_status_validation(
+        &self,
+        worksheet: &mut Worksheet,
+        max_level: usize,
+        columns: &[Column],
+        last_row: usize,
+    ) -> Result<()> {
+        let Some(offset) = columns.iter().position(|column| *column == Column::Status) else {
+            return Ok(());
+        };
+        if last_row == 0 {
+            return Ok(());
+        }
+        let col = (max_level + offset) as u16;
+        let validation = DataValidation::new().allow_list_strings(&self.status_values)?;
+        worksheet.add_data_validation(1, col, last_row as u32, col, &validation)?;
+        Ok(())
+    }
+
+    /// 按变更列为第1行到last_row行整行着色：新增为绿色，删除为红色，大小变化为黄色
+    /// （使用条件格式，排序或筛选后颜色仍跟随各行）
+    fn add_change_highlight(
+        &self,
+        worksheet: &mut Worksheet,
+        max_level: usize,
+        columns: &[Column],
+        last_row: usize,
+    ) -> Result<()> {
+        let Some(offset) = columns.iter().position(|column| *column == Column::Change) else {
+            return Ok(());
+        };
+        if last_row == 0 {
+            return Ok(());
+        }
+        let col = rust_xlsxwriter::column_number_to_name((max_level + offset) as u16);
+        let last_col = (max_level + columns.len() - 1) as u16;
+        let highlights = [
+            (Change::Added, &self.theme.added),
+            (Change::Removed, &self.theme.removed),
+            (Change::Resized, &self.theme.changed),
+        ];
+        for (change, style) in highlights {
+            let rule = format!("=${col}2=\"{}\"", change.label(self.lang));
+            let conditional_format = ConditionalFormatFormula::new()
+                .set_rule(rule.as_str())
+                .set_format(self.theme.format(style));
+            worksheet.add_conditional_format(
+                1,
+                0,
+                last_row as u32,
+                last_col,
+                &conditional_format,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// 冻结首行并为已写入的行设置自动筛选
+    fn finish_sheet(
+        &self,
+        worksheet: &mut Worksheet,
+        last_row: usize,
+        total_cols: usize,
+    ) -> Result<()> {
+        let _ = worksheet.set_freeze_panes(1, 0);
+        if last_row > 0 {
+            worksheet.autofilter(0, 0, last_row as u32, (total_cols - 1) as u16)?;
+        }
+        Ok(())
+    }
+
+    /// 写入数据并实现层级合并单元格
+    fn write_data_with_merging(
+        &self,
+        worksheet: &mut Worksheet,
+        rows: &[&ExcelRow],
+        max_level: usize,
+        columns: &[Column],
+        formats: &ExcelFormats,
+        current_row: &mut u32,
+    ) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        // 大纲布局按合并范围确定每个目录下的行
+        let outline = self.layout == Layout::Outline;
+        if outline {
+            worksheet.group_symbols_above(true);
+        }
+        let spans = if outline {
+            level_spans(rows)
+        } else {
+            Vec::new()
+        };
+
+        // 先写入所有单元格内容
+        for (row_idx, row) in rows.iter().enumerate() {
+            let row_num = *current_row + row_idx as u32;
+
+            // 层级列：写入每个层级的内容（最后一个非空层级是项目本身，大纲布局只写项目本身）
+            let leaf_idx = row.levels.iter().rposition(|name| !name.is_empty());
+            for (level_idx, level_name) in row.levels.iter().enumerate().take(max_level) {
+                let is_leaf = Some(level_idx) == leaf_idx;
+                if !level_name.is_empty() && (is_leaf || !outline) {
+                    self.write_level_cell(
+                        worksheet,
+                        row_num,
+                        level_idx as u16,
+                        row,
+                        is_leaf,
+                        formats,
+                    )?;
+                }
+            }
+
+            // 附加列
+            for (offset, column) in columns.iter().enumerate() {
+                let col = (max_level + offset) as u16;
+                self.write_column_cell(worksheet, row_num, col, *column, row, formats)?;
+            }
+
+            // 目录下的行分组（超过Excel的7层上限的目录不再分组）
+            if let Some(leaf_idx) = leaf_idx.filter(|&idx| outline && idx < OUTLINE_LEVELS) {
+                let children = spans[row_idx][leaf_idx] - 1;
+                if children > 0 {
+                    worksheet.group_rows(row_num + 1, row_num + children as u32)?;
+                }
+            }
+        }
+        if outline {
+            *current_row += rows.len() as u32;
+            return Ok(());
+        }
+
+        // 然后实现合并单元格逻辑
+        for level_idx in 0..max_level {
+            self.merge_level_column(
+                worksheet,
+                rows,
+                level_idx,
+                *current_row,
+                &formats.dir_format,
+            )?;
+        }
+
+        *current_row += rows.len() as u32;
+        Ok(())
+    }
+
+    /// 层级列单元格的格式（项目本身按类型区分，上级目录统一为目录格式）
+    fn level_format<'a>(
+        &self,
+        row: &ExcelRow,
+        is_leaf: bool,
+        formats: &'a ExcelFormats,
+    ) -> &'a Format {
+        if is_leaf && row.error.is_some() {
+            &formats.error_format
+        } else if is_leaf && row.link_target.is_some() {
+            &formats.link_format
+        } else if is_leaf && row.is_file {
+            self.color_by_type
+                .then(|| {
+                    self.theme
+                        .category(row.full_path.rsplit('/').next().unwrap_or_default())
+                })
+                .flatten()
+                .and_then(|category| formats.category_formats.get(category))
+                .unwrap_or(&formats.file_format)
+        } else {
+            &formats.dir_format
+        }
+    }
+
+    /// 写入层级列的单元格，开启link_names时文件名单元格写为超链接
+    fn write_level_cell(
+        &self,
+        worksheet: &mut Worksheet,
+        row_num: u32,
+        col: u16,
+        row: &ExcelRow,
+        is_leaf: bool,
+        formats: &ExcelFormats,
+    ) -> Result<()> {
+        let level_name = &row.levels[col as usize];
+        let format = self.level_format(row, is_leaf, formats);
+        match self.row_link(row) {
+            Some(link) if self.link_names && is_leaf && row.is_file => {
+                worksheet.write_url_with_format(
+                    row_num,
+                    col,
+                    Url::new(link).set_text(level_name),
+                    format,
+                )?;
+            }
+            _ => {
+                worksheet.write_with_format(row_num, col, level_name, format)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 行对应的超链接地址（未开启超链接或地址过长时为None）
+    fn row_link(&self, row: &ExcelRow) -> Option<String> {
+        let link = if let Some(prefix) = &self.link_prefix {
+            // 网址前缀只用于文件（目录在GitHub等网站上的地址格式不同）
+            if !row.is_file {
+                return None;
+            }
+            format!("{prefix}{}", encode_url_path(&row.full_path))
+        } else {
+            file_url(&self.hyperlink_base.as_ref()?.join(&row.full_path))
+        };
+        Some(link).filter(|link| link.len() <= MAX_URL_LEN)
+    }
+
+    /// 写入附加列的单元格
+    fn write_column_cell(
+        &self,
+        worksheet: &mut Worksheet,
+        row_num: u32,
+        col: u16,
+        column: Column,
+        row: &ExcelRow,
+        formats: &ExcelFormats,
+    ) -> Result<()> {
+        match column {
+            Column::Root => {
+                let root = row.root.as_deref().unwrap_or("");
+                worksheet.write_with_format(row_num, col, root, &formats.dir_format)?;
+            }
+            Column::Path => {
+                let value = self.provider_value(column, row);
+                match (self.row_link(row), &value) {
+                    (Some(link), CellValue::Text(text)) => {
+                        let url = Url::new(link).set_text(text);
+                        worksheet.write_url_with_format(
+                            row_num,
+                            col,
+                            url,
+                            &formats.hyperlink_format,
+                        )?;
+                    }
+                    _ => {
+                        // 重复文件的路径高亮显示
+                        let format = if row.duplicate_group.is_some() {
+                            &formats.duplicate_format
+                        } else {
+                            &formats.path_format
+                        };
+                        self.write_value(worksheet, row_num, col, &value, format)?;
+                    }
+                }
+            }
+            Column::Permissions => {
+                let permissions = row.permissions.as_deref().unwrap_or("");
+                worksheet.write_with_format(row_num, col, permissions, &formats.text_format)?;
+            }
+            Column::Owner => {
+                let owner = row.owner.as_deref().unwrap_or("");
+                worksheet.write_with_format(row_num, col, owner, &formats.text_format)?;
+            }
+            Column::Group => {
+                let group = row.group.as_deref().unwrap_or("");
+                worksheet.write_with_format(row_num, col, group, &formats.text_format)?;
+            }
+            Column::LinkTarget => {
+                let target = row.link_target.as_deref().unwrap_or("");
+                worksheet.write_with_format(row_num, col, target, &formats.text_format)?;
+            }
+            Column::FileKind => {
+                let kind = self.file_kind(row);
+                worksheet.write_with_format(row_num, col, &kind, &formats.text_format)?;
+            }
+            Column::Lines => match row.lines {
+                Some(lines) => {
+                    worksheet.write_with_format(
+                        row_num,
+                        col,
+                        lines as f64,
+                        &formats.size_format,
+                    )?;
+                }
+                None => {
+                    worksheet.write_with_format(row_num, col, "", &formats.size_format)?;
+                }
+            },
+            Column::CodeLanguage => {
+                let language = self.code_language(row);
+                worksheet.write_with_format(row_num, col, language, &formats.text_format)?;
+            }
+            Column::Size => {
+                // 写入数字以便Excel排序/求和
+                let value = self.provider_value(column, row);
+                self.write_value(worksheet, row_num, col, &value, &formats.size_format)?;
+            }
+            Column::OldSize => match row.old_size {
+                // 只选择易读大小时原大小也写为易读格式
+                Some(size) if self.size_format == SizeFormat::Human => {
+                    let size = human_size(size);
+                    worksheet.write_with_format(row_num, col, &size, &formats.human_size_format)?;
+                }
+                Some(size) => {
+                    worksheet.write_with_format(row_num, col, size as f64, &formats.size_format)?;
+                }
+                None => {
+                    worksheet.write_with_format(row_num, col, "", &formats.size_format)?;
+                }
+            },
+            Column::Change => {
+                let change = row.change.map_or("", |change| change.label(self.lang));
+                worksheet.write_with_format(row_num, col, change, &formats.text_format)?;
+            }
+            Column::SizeHuman => {
+                let size = row.size.map_or(String::new(), human_size);
+                worksheet.write_with_format(row_num, col, &size, &formats.human_size_format)?;
+            }
+            Column::TotalSize | Column::FileCount | Column::DirCount => match &row.rollup {
+                // 只选择易读大小时总大小也写为易读格式，其余写入数字以便排序/求和
+                Some(rollup)
+                    if column == Column::TotalSize && self.size_format == SizeFormat::Human =>
+                {
+                    let size = human_size(rollup.total_size);
+                    worksheet.write_with_format(row_num, col, &size, &formats.human_size_format)?;
+                }
+                Some(rollup) => {
+                    let value = match column {
+                        Column::TotalSize => rollup.total_size as f64,
+                        Column::FileCount => rollup.total_file_count as f64,
+                        _ => rollup.total_dir_count as f64,
+                    };
+                    worksheet.write_with_format(row_num, col, value, &formats.size_format)?;
+                }
+                None => {
+                    worksheet.write_with_format(row_num, col, "", &formats.size_format)?;
+                }
+            },
+            Column::Modified => {
+                // 写入真正的日期值，便于Excel排序/筛选
+                let value = self.provider_value(column, row);
+                self.write_value(worksheet, row_num, col, &value, &formats.date_format)?;
+            }
+            Column::StorageClass => {
+                let class = row.storage_class.as_deref().unwrap_or("");
+                worksheet.write_with_format(row_num, col, class, &formats.text_format)?;
+            }
+            Column::Layer => match &row.layer {
+                Some(layer) => {
+                    worksheet.write_with_format(
+                        row_num,
+                        col,
+                        layer.index as f64,
+                        &formats.size_format,
+                    )?;
+                }
+                None => {
+                    worksheet.write_with_format(row_num, col, "", &formats.size_format)?;
+                }
+            },
+            Column::LayerCommand => {
+                let command = row
+                    .layer
+                    .as_ref()
+                    .and_then(|layer| layer.created_by.as_deref())
+                    .unwrap_or("");
+                worksheet.write_with_format(row_num, col, command, &formats.text_format)?;
+            }
+            Column::GitStatus => {
+                let status = row
+                    .git
+                    .as_ref()
+                    .map_or("", |git| git.status.label(self.lang));
+                worksheet.write_with_format(row_num, col, status, &formats.text_format)?;
+            }
+            Column::LastCommit => match row
+                .git
+                .as_ref()
+                .and_then(|git| git.last_commit)
+                .and_then(|time| self.to_excel_datetime(time))
+            {
+                Some(datetime) => {
+                    worksheet.write_with_format(row_num, col, &datetime, &formats.date_format)?;
+                }
+                None => {
+                    worksheet.write_with_format(row_num, col, "", &formats.date_format)?;
+                }
+            },
+            Column::Author => {
+                let author = row
+                    .git
+                    .as_ref()
+                    .and_then(|git| git.author.as_deref())
+                    .unwrap_or("");
+                worksheet.write_with_format(row_num, col, author, &formats.text_format)?;
+            }
+            Column::Hash(_) => {
+                let hash = row.hash.as_ref().map_or("", |hash| hash.value.as_str());
+                worksheet.write_with_format(row_num, col, hash, &formats.text_format)?;
+            }
+            Column::Duplicate => match row.duplicate_group {
+                Some(group) => {
+                    worksheet.write_with_format(
+                        row_num,
+                        col,
+                        group as f64,
+                        &formats.duplicate_format,
+                    )?;
+                }
+                None => {
+                    worksheet.write_with_format(row_num, col, "", &formats.text_format)?;
+                }
+            },
+            Column::Secret => {
+                let name = row.full_path.rsplit('/').next().unwrap_or_default();
+                let label = detect_secret(name).filter(|_| row.is_file);
+                match label {
+                    Some(label) => {
+                        let label = self.lang.secret_label(label);
+                        worksheet.write_with_format(row_num, col, label, &formats.secret_format)?;
+                    }
+                    None => {
+                        worksheet.write_with_format(row_num, col, "", &formats.notes_format)?;
+                    }
+                }
+            }
+            Column::Name => {
+                // 与层级列中的项目本身相同的格式，link_names时文件名写为超链接
+                let name = row.full_path.rsplit('/').next().unwrap_or_default();
+                let mut format = self.level_format(row, true, formats).clone();
+                if self.layout == Layout::Indent {
+                    // 缩进布局按深度缩进（第一层不缩进）
+                    let indent = (row.full_path.split('/').count() - 1).min(MAX_INDENT);
+                    format = format
+                        .set_align(rust_xlsxwriter::FormatAlign::Left)
+                        .set_indent(indent as u8);
+                }
+                match self.row_link(row) {
+                    Some(link) if self.link_names && row.is_file => {
+                        let url = Url::new(link).set_text(name);
+                        worksheet.write_url_with_format(row_num, col, url, &format)?;
+                    }
+                    _ => {
+                        worksheet.write_with_format(row_num, col, name, &format)?;
+                    }
+                }
+            }
+            Column::Depth => {
+                let depth = row.full_path.split('/').count();
+                worksheet.write_with_format(row_num, col, depth as f64, &formats.text_format)?;
+            }
+            Column::Type => {
+                let kind = if row.link_target.is_some() {
+                    self.lang.text("链接", "Link")
+                } else if row.is_file {
+                    self.lang.text("文件", "File")
+                } else {
+                    self.lang.text("目录", "Directory")
+                };
+                worksheet.write_with_format(row_num, col, kind, &formats.text_format)?;
+            }
+            Column::Parent => {
+                let parent = parent_path(&row.full_path);
+                worksheet.write_with_format(row_num, col, parent, &formats.text_format)?;
+            }
+            Column::Notes => {
+                // 读取失败的目录在备注中标出错误
+                let format = if row.error.is_some() {
+                    &formats.error_format
+                } else {
+                    &formats.notes_format
+                };
+                let value = self.provider_value(column, row);
+                self.write_value(worksheet, row_num, col, &value, format)?;
+            }
+            Column::Status | Column::Extra(_) => {
+                worksheet.write_with_format(row_num, col, "", &formats.notes_format)?;
+            }
+            Column::Custom(idx) => {
+                let format = formats.for_kind(self.custom_columns[idx].kind());
+                self.write_value(worksheet, row_num, col, &row.custom[idx], format)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 项目在各自定义列中的值（统计行没有）
+    fn custom_values(&self, item: &TreeItem) -> Vec<CellValue> {
+        if item.name.starts_with("📊") {
+            return vec![CellValue::Empty; self.custom_columns.len()];
+        }
+        self.custom_columns
+            .iter()
+            .map(|column| column.value(item))
+            .collect()
+    }
+
+    /// 将项目转换为ExcelRow，同时取得自定义列的值
+    fn to_row(&self, item: TreeItem, max_level: usize) -> ExcelRow {
+        let custom = self.custom_values(&item);
+        ExcelRow {
+            custom,
+            ..convert_row(item, max_level)
+        }
+    }
+
+    /// 转换为Excel日期时间（超出Excel支持范围时返回None）
+    fn to_excel_datetime(&self, datetime: NaiveDateTime) -> Option<ExcelDateTime> {
+        ExcelDateTime::from_ymd(
+            u16::try_from(datetime.year()).ok()?,
+            datetime.month() as u8,
+            datetime.day() as u8,
+        )
+        .and_then(|date| {
+            date.and_hms(
+                datetime.hour() as u16,
+                datetime.minute() as u8,
+                datetime.second(),
+            )
+        })
+        .ok()
+    }
+
+    /// 合并指定层级列的单元格
+    fn merge_level_column(
+        &self,
+        worksheet: &mut Worksheet,
+        rows: &[&ExcelRow],
+        level_idx: usize,
+        start_row: u32,
+        dir_format: &Format,
+    ) -> Result<()> {
+        let mut i = 0;
+        while i < rows.len() {
+            let current_value = &rows[i].levels[level_idx];
+
+            // 跳过空值
+            if current_value.is_empty() {
+                i += 1;
+                continue;
+            }
+
+            // 找到相同值的连续范围，考虑前面层级的约束
+            let mut j = i + 1;
+            while j < rows.len() {
+                // 检查当前层级值是否相同
+                if rows[j].levels[level_idx] != *current_value {
+                    break;
+                }
+
+                // 检查前面的层级是否也相同（重要：确保是同一个父目录下）
+                let mut same_parent = rows[i].root == rows[j].root;
+                for prev_level in 0..level_idx {
+                    if rows[i].levels[prev_level] != rows[j].levels[prev_level] {
+                        same_parent = false;
+                        break;
+                    }
+                }
+
+                if !same_parent {
+                    break;
+                }
+
+                j += 1;
+            }
+
+            // 如果有多行相同值，进行合并
+            if j - i > 1 {
+                let start_merge_row = start_row + i as u32;
+                let end_merge_row = start_row + (j - 1) as u32;
+
+                worksheet.merge_range(
+                    start_merge_row,
+                    level_idx as u16,
+                    end_merge_row,
+                    level_idx as u16,
+                    current_value,
+                    dir_format,
+                )?;
+            }
+
+            i = j;
+        }
+
+        Ok(())
+    }
+}
+
+/// 将TreeItem转换为ExcelRow
+pub(crate) fn convert_to_rows(items: Vec<TreeItem>) -> Vec<ExcelRow> {
+    convert_to_rows_capped(items, usize::MAX)
+}
+
+/// 将TreeItem转换为ExcelRow，层级列最多max_level_cap列
+fn convert_to_rows_capped(items: Vec<TreeItem>, max_level_cap: usize) -> Vec<ExcelRow> {
+    // 首先找出最大层级深度
+    let max_level = items
+        .iter()
+        .filter(|item| !item.name.starts_with("📊"))
+        .map(|item| item.level)
+        .max()
+        .unwrap_or(1)
+        .min(max_level_cap);
+
+    items
+        .into_iter()
+        .map(|item| convert_row(item, max_level))
+        .collect()
+}
+
+/// 将单个TreeItem转换为ExcelRow（超过max_level的层级合并到最后一个层级列）
+fn convert_row(item: TreeItem, max_level: usize) -> ExcelRow {
+    let mut levels = vec!["".to_string(); max_level];
+
+    // 统计信息特殊处理
+    if item.name.starts_with("📊") {
+        levels[0] = item.name.clone();
+
+        return ExcelRow {
+            levels,
+            full_path: item.name,
+            max_level,
+            ..Default::default()
+        };
+    }
+
+    let mut segments = level_names(&item);
+    if segments.len() > max_level {
+        let overflow = segments.split_off(max_level - 1).join("/");
+        segments.push(overflow);
+    }
+    for (i, segment) in segments.into_iter().enumerate() {
+        levels[i] = segment;
+    }
+
+    ExcelRow {
+        levels,
+        full_path: item.full_path,
+        max_level,
+        is_file: item.is_file,
+        size: item.size,
+        modified: item.modified,
+        permissions: item.permissions,
+        owner: item.owner,
+        group: item.group,
+        link_target: item.link_target,
+        root: item.root,
+        error: item.error,
+        rollup: None,
+        git: item.git,
+        hash: item.hash,
+        sniffed_type: item.sniffed_type,
+        lines: item.lines,
+        duplicate_group: item.duplicate_group,
+        change: item.change,
+        old_size: item.old_size,
+        storage_class: item.storage_class,
+        layer: item.layer,
+        custom: Vec::new(),
+    }
+}
+
+/// 单元格值显示的文本（用于计算自动列宽）
+fn value_text(value: &CellValue) -> String {
+    match value {
+        CellValue::Empty => String::new(),
+        CellValue::Text(text) => text.clone(),
+        CellValue::Number(number) => number.to_string(),
+        CellValue::DateTime(datetime) => datetime.format("%Y-%m-%d %H:%M").to_string(),
+    }
+}
+
+/// 保存工作簿，"-"表示将xlsx内容写到标准输出
+fn save_workbook(workbook: &mut Workbook, output_path: &str) -> Result<()> {
+    if output_path == "-" {
+        let buffer = workbook.save_to_buffer().context("无法生成Excel文件")?;
+        open_output(output_path)?.write_all(&buffer)?;
+    } else {
+        workbook
+            .save(output_path)
+            .with_context(|| format!("无法保存Excel文件: {output_path}"))?;
+    }
+    Ok(())
+}
+
+/// 本地路径对应的file://地址，如 file:///C:/work/a.txt。
+/// rust_xlsxwriter会去掉"file:///"前缀，Unix绝对路径需要保留开头的'/'（file:////home/a.txt）
+fn file_url(path: &Path) -> String {
+    format!("file:///{}", path.to_string_lossy().replace('\\', "/"))
+}
+
+/// 对URL路径进行百分号编码（保留'/'和不需要编码的字符）
+fn encode_url_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// 按最长的文本计算列宽（中日韩等宽字符按2个字符计）
+fn fit_width<'a>(texts: impl Iterator<Item = &'a str>) -> f64 {
+    let longest = texts
+        .map(|text| {
+            text.chars()
+                .map(|c| if c.len_utf8() >= 3 { 2 } else { 1 })
+                .sum::<usize>()
+        })
+        .max()
+        .unwrap_or(0);
+    (longest as f64 + 2.0).min(MAX_AUTOFIT_WIDTH)
+}
+
+/// 易读的大小（1024进制），如 "512 B"、"1.4 MiB"
+fn human_size(size: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{size} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// 完整路径的上级目录，第一层的项目为"."
+fn parent_path(full_path: &str) -> &str {
+    full_path.rsplit_once('/').map_or(".", |(parent, _)| parent)
+}
+
+/// 项目各层级的名称，如["src", "bin", "file.rs"]：上级目录取自完整路径，
+/// 这样即使目录行被过滤（如--files-only），层级列仍然完整
+pub(crate) fn level_names(item: &TreeItem) -> Vec<String> {
+    let mut segments: Vec<String> = item
+        .full_path
+        .rsplitn(item.level.max(1), '/')
+        .skip(1)
+        .map(str::to_string)
+        .collect();
+    segments.reverse();
+    segments.push(item.name.clone());
+    segments
+}
+
+/// 计算层级列的合并范围（与Excel中的合并单元格一致）：spans[行][层级]为从该行开始
+/// 合并的行数，0表示被上方的合并范围覆盖，空单元格为1
+pub(crate) fn level_spans(rows: &[&ExcelRow]) -> Vec<Vec<usize>> {
+    let mut spans: Vec<Vec<usize>> = rows.iter().map(|row| vec![1; row.levels.len()]).collect();
+    for i in 0..rows.len() {
+        for level_idx in 0..rows[i].levels.len() {
+            if rows[i].levels[level_idx].is_empty() || spans[i][level_idx] == 0 {
+                continue;
+            }
+            // 同一根目录且前面各层级相同的连续行合并
+            let same_run = |other: &&ExcelRow| {
+                other.root == rows[i].root
+                    && other.levels[..=level_idx] == rows[i].levels[..=level_idx]
+            };
+            let following = rows[i + 1..]
+                .iter()
+                .take_while(|other| same_run(other))
+                .count();
+            spans[i][level_idx] = following + 1;
+            for span in &mut spans[i + 1..=i + following] {
+                span[level_idx] = 0;
+            }
+        }
+    }
+    spans
+}
+
+/// 按每个工作表的行数上限拆分数据行，返回每个工作表的数据行范围及是否写入统计行
+/// （统计行写在最后一个工作表，放不下时单独占一个工作表）
+fn split_pages(
+    data_count: usize,
+    stats_count: usize,
+    limit: usize,
+) -> Vec<(std::ops::Range<usize>, bool)> {
+    let mut pages: Vec<_> = (0..data_count)
+        .step_by(limit)
+        .map(|start| (start..(start + limit).min(data_count), false))
+        .collect();
+    match pages.last_mut() {
+        Some((range, with_stats)) if range.len() + stats_count <= limit => *with_stats = true,
+        _ => pages.push((data_count..data_count, true)),
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+    use calamine::{open_workbook, Reader, Xlsx};
+
+    #[test]
+    fn test_file_url() {
+        assert_eq!(
+            file_url(Path::new("/home/user/a b.txt")),
+            "file:////home/user/a b.txt"
+        );
+        assert_eq!(
+            file_url(Path::new("C:\\work\\a.txt")),
+            "file:///C:/work/a.txt"
+        );
+    }
+
+    #[test]
+    fn test_encode_url_path() {
+        assert_eq!(encode_url_path("src/main.rs"), "src/main.rs");
+        assert_eq!(encode_url_path("docs/a b#1.md"), "docs/a%20b%231.md");
+        assert_eq!(encode_url_path("文档.md"), "%E6%96%87%E6%A1%A3.md");
+    }
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(0), "0 B");
+        assert_eq!(human_size(1023), "1023 B");
+        assert_eq!(human_size(1536), "1.5 KiB");
+        assert_eq!(human_size(1_468_006), "1.4 MiB");
+        assert_eq!(human_size(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+
+    #[test]
+    fn test_parent_path() {
+        assert_eq!(parent_path("src/bin/main.rs"), "src/bin");
+        assert_eq!(parent_path("README.md"), ".");
+    }
+
+    #[test]
+    fn test_fit_width() {
+        assert_eq!(fit_width(["L1", "src", "main.rs"].into_iter()), 9.0);
+        assert_eq!(fit_width(["完整路径"].into_iter()), 10.0);
+        assert_eq!(fit_width(["x".repeat(300).as_str()].into_iter()), 100.0);
+    }
+
+    #[test]
+    fn test_col_widths() {
+        let generator = ExcelGenerator::builder()
+            .col_widths("L1=30, l2=25,PATH=80")
+            .build()
+            .unwrap();
+        assert_eq!(generator.col_widths["l1"], 30.0);
+        assert_eq!(generator.col_widths["l2"], 25.0);
+        assert_eq!(generator.col_widths["path"], 80.0);
+        for spec in ["foo=10", "L0=10", "path=abc"] {
+            assert!(ExcelGenerator::builder().col_widths(spec).build().is_err());
+        }
+        // --extra-col追加的列也可以指定宽度（与设置顺序无关）
+        let generator = ExcelGenerator::builder()
+            .col_widths("负责人=12")
+            .extra_cols(vec!["负责人".to_string()])
+            .build()
+            .unwrap();
+        assert_eq!(generator.col_widths["负责人"], 12.0);
+    }
+
+    #[test]
+    fn test_status_values() {
+        let generator = ExcelGenerator::builder()
+            .status_values(vec![
+                "保留".to_string(),
+                " 删除".to_string(),
+                "".to_string(),
+            ])
+            .build()
+            .unwrap();
+        assert_eq!(generator.status_values, ["保留", "删除"]);
+        assert!(ExcelGenerator::builder()
+            .status_values(vec![])
+            .build()
+            .is_err());
+        assert!(ExcelGenerator::builder()
+            .status_values(vec!["x".repeat(300)])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_detect_secret() {
+        let cases = [
+            ("id_rsa", Some("SSH私钥")),
+            ("id_rsa.pub", None),
+            ("server.pem", Some("证书/私钥")),
+            ("tls.KEY", Some("证书/私钥")),
+            (".env", Some("环境变量文件")),
+            (".env.local", Some("环境变量文件")),
+            (".env.example", None),
+            (".npmrc", Some("凭据文件")),
+            ("credentials.json", Some("凭据文件")),
+            ("db_password.txt", Some("疑似凭据")),
+            ("secrets.yaml", Some("疑似凭据")),
+            ("github-token", Some("疑似凭据")),
+            ("apiToken.json", Some("疑似凭据")),
+            ("passwd", Some("疑似凭据")),
+            ("tokenizer.rs", None),
+            ("secretary.md", None),
+            ("passwordless_login.md", None),
+            ("keyboard.rs", None),
+            ("main.rs", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(detect_secret(name), expected, "{name}");
+        }
+
+        // 只标记文件，secrets_manager等目录不标记
+        let input = ".\n├── secrets_manager\n│   └── main.rs\n└── db_password.txt\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let path = std::env::temp_dir().join("tree_to_excel_secrets.xlsx");
+        ExcelGenerator::new(true)
+            .generate(items, path.to_str().unwrap())
+            .unwrap();
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        let range = workbook.worksheet_range_at(0).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let headers: Vec<String> = range
+            .rows()
+            .next()
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let col = headers
+            .iter()
+            .position(|header| header == "安全标记")
+            .unwrap();
+        let flags: Vec<String> = (1..=3)
+            .map(|row| range.get((row, col)).unwrap().to_string())
+            .collect();
+        assert_eq!(flags, ["", "", "疑似凭据"]);
+    }
+
+    #[test]
+    fn test_sheet_name() {
+        let generator = ExcelGenerator::new(false);
+        let long = "abcdefghij".repeat(4);
+        let cases = [
+            ("src", vec![], "src"),
+            ("a/b:c*d?e[f]g\\h", vec![], "a_b_c_d_e_f_g_h"),
+            ("'quoted'", vec![], "quoted"),
+            ("", vec![], "Sheet"),
+            ("'''", vec![], "Sheet"),
+            (long.as_str(), vec![], &long[..31]),
+            // 截断后末尾的单引号也去掉
+            (
+                "abcdefghijabcdefghijabcdefghij'x",
+                vec![],
+                "abcdefghijabcdefghijabcdefghij",
+            ),
+            // 不区分大小写地去重
+            ("a", vec!["A"], "a (2)"),
+            ("a", vec!["a", "A (2)"], "a (3)"),
+            (
+                long.as_str(),
+                vec![&long[..31]],
+                "abcdefghijabcdefghijabcdefg (2)",
+            ),
+        ];
+        for (name, used, expected) in cases {
+            let used: Vec<String> = used.into_iter().map(String::from).collect();
+            let sheet_name = generator.sheet_name(name, &used);
+            assert_eq!(sheet_name, expected, "{name}");
+            assert!(Workbook::new()
+                .add_worksheet()
+                .set_name(&sheet_name)
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_max_level_cap() {
+        let input = "a\n└── b\n    └── c\n        └── d.txt\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let rows = convert_to_rows_capped(items, 2);
+        let file = rows.iter().find(|row| row.is_file).unwrap();
+        assert_eq!(file.levels, ["b", "c/d.txt"]);
+        assert!(ExcelGenerator::builder().max_level_cap(0).build().is_err());
+    }
+
+    /// 工作表各行（从表头开始）的大纲层级
+    fn outline_levels(path: &Path) -> Vec<u8> {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut sheet = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("xl/worksheets/sheet1.xml").unwrap(),
+            &mut sheet,
+        )
+        .unwrap();
+        sheet
+            .split("<row ")
+            .skip(1)
+            .map(|row| {
+                let tag = &row[..row.find('>').unwrap()];
+                tag.split_once("outlineLevel=\"").map_or(0, |(_, rest)| {
+                    rest[..rest.find('"').unwrap()].parse().unwrap()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_outline_levels() {
+        let path = std::env::temp_dir().join("tree_to_excel_outline.xlsx");
+        let generator = ExcelGenerator::builder()
+            .layout(Layout::Outline)
+            .build()
+            .unwrap();
+
+        // 每个目录下的行分为一组，嵌套目录的组层级递增
+        let input = ".\n├── a\n│   ├── b\n│   │   └── c.txt\n│   └── d.txt\n└── e.txt\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        generator.generate(items, path.to_str().unwrap()).unwrap();
+        assert_eq!(outline_levels(&path), [0, 0, 1, 2, 1, 0, 0]);
+
+        // 超过7层的目录不再分组，最深的行停在第7层
+        let mut input = String::from(".\n");
+        for depth in 0..9 {
+            input.push_str(&format!("{}└── d{depth}\n", "    ".repeat(depth)));
+        }
+        input.push_str(&format!("{}└── f.txt\n", "    ".repeat(9)));
+        let items = TreeParser::new().parse(&input, false).unwrap();
+        generator.generate(items, path.to_str().unwrap()).unwrap();
+        let levels = outline_levels(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(levels, [0, 0, 1, 2, 3, 4, 5, 6, 7, 7, 7, 0]);
+    }
+
+    #[test]
+    fn test_streaming_merge_window() {
+        let input = ".\n├── big\n│   ├── 1.rs\n│   ├── 2.rs\n│   ├── 3.rs\n│   ├── 4.rs\n│   └── 5.rs\n├── small\n│   └── s.rs\n└── z.rs\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let path = std::env::temp_dir().join("tree_to_excel_merge_window.xlsx");
+        let generator = ExcelGenerator::builder().merge_window(3).build().unwrap();
+        let rows = generator
+            .generate_streaming(items.into_iter().map(Ok), path.to_str().unwrap(), |_| {})
+            .unwrap();
+        assert_eq!(rows, 9);
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        let merges = workbook.worksheet_merge_cells_at(0).unwrap().unwrap();
+        let range = workbook.worksheet_range_at(0).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let merges: Vec<_> = merges.iter().map(|dims| (dims.start, dims.end)).collect();
+
+        // big下的6行超出窗口，不合并，只在首行写出名称；small下的2行在窗口内，合并
+        assert!(!merges.iter().any(|&(start, _)| start == (1, 0)));
+        assert_eq!(range.get((1, 0)).unwrap().to_string(), "big");
+        assert_eq!(range.get((2, 0)).unwrap().to_string(), "");
+        assert!(merges.contains(&((7, 0), (8, 0))));
+        assert!(ExcelGenerator::builder().merge_window(0).build().is_err());
+    }
+
+    #[test]
+    fn test_split_pages() {
+        assert_eq!(split_pages(3, 1, 10), [(0..3, true)]);
+        assert_eq!(split_pages(0, 1, 10), [(0..0, true)]);
+        assert_eq!(
+            split_pages(25, 1, 10),
+            [(0..10, false), (10..20, false), (20..25, true)]
+        );
+        // 最后一个工作表放不下统计行
+        assert_eq!(
+            split_pages(20, 1, 10),
+            [(0..10, false), (10..20, false), (20..20, true)]
+        );
+    }
+}
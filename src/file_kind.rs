@@ -0,0 +1,164 @@
+//! 文件类型识别（--detect-type）：按文件内容的魔数或扩展名给出易读的类型名称
+
+use rayon::prelude::*;
+use std::path::Path;
+
+use crate::i18n::Language;
+use crate::parser::{EntryKind, TreeItem};
+
+/// 扩展名（或小写的完整文件名）及对应的类型名称（中文, 英文）
+const KINDS: &[(&[&str], &str, &str)] = &[
+    // 源代码及脚本
+    (&["rs"], "Rust源文件", "Rust source"),
+    (&["py", "pyw"], "Python源文件", "Python source"),
+    (
+        &["js", "mjs", "cjs"],
+        "JavaScript源文件",
+        "JavaScript source",
+    ),
+    (&["ts", "mts"], "TypeScript源文件", "TypeScript source"),
+    (&["jsx", "tsx"], "React组件", "React component"),
+    (&["go"], "Go源文件", "Go source"),
+    (&["java"], "Java源文件", "Java source"),
+    (&["kt", "kts"], "Kotlin源文件", "Kotlin source"),
+    (&["c"], "C源文件", "C source"),
+    (&["h"], "C头文件", "C header"),
+    (&["cpp", "cc", "cxx"], "C++源文件", "C++ source"),
+    (&["hpp", "hh", "hxx"], "C++头文件", "C++ header"),
+    (&["cs"], "C#源文件", "C# source"),
+    (&["rb"], "Ruby源文件", "Ruby source"),
+    (&["php"], "PHP源文件", "PHP source"),
+    (&["swift"], "Swift源文件", "Swift source"),
+    (&["sh", "bash", "zsh"], "Shell脚本", "Shell script"),
+    (&["ps1"], "PowerShell脚本", "PowerShell script"),
+    (&["bat", "cmd"], "批处理文件", "Batch file"),
+    (&["sql"], "SQL脚本", "SQL script"),
+    (&["html", "htm"], "HTML文档", "HTML document"),
+    (&["css", "scss", "less"], "样式表", "Stylesheet"),
+    (&["vue"], "Vue组件", "Vue component"),
+    (&["makefile"], "Makefile", "Makefile"),
+    (&["dockerfile"], "Dockerfile", "Dockerfile"),
+    // 文档及数据
+    (&["md", "markdown"], "Markdown文档", "Markdown document"),
+    (&["txt"], "文本文件", "Text file"),
+    (&["json"], "JSON数据", "JSON data"),
+    (&["yaml", "yml"], "YAML配置", "YAML config"),
+    (&["toml"], "TOML配置", "TOML config"),
+    (&["ini", "cfg", "conf"], "配置文件", "Config file"),
+    (&["xml"], "XML文档", "XML document"),
+    (&["csv"], "CSV表格", "CSV table"),
+    (&["log"], "日志文件", "Log file"),
+    (&["lock"], "锁文件", "Lock file"),
+    (&["pdf"], "PDF文档", "PDF document"),
+    (&["doc", "docx"], "Word文档", "Word document"),
+    (&["xls", "xlsx"], "Excel工作簿", "Excel workbook"),
+    (
+        &["ppt", "pptx"],
+        "PowerPoint演示文稿",
+        "PowerPoint presentation",
+    ),
+    (
+        &["odt", "ods", "odp"],
+        "OpenDocument文档",
+        "OpenDocument file",
+    ),
+    (&["sqlite", "db"], "SQLite数据库", "SQLite database"),
+    // 图片、音频、视频、字体
+    (&["png"], "PNG图片", "PNG image"),
+    (&["jpg", "jpeg"], "JPEG图片", "JPEG image"),
+    (&["gif"], "GIF图片", "GIF image"),
+    (&["bmp"], "BMP图片", "BMP image"),
+    (&["webp"], "WebP图片", "WebP image"),
+    (&["svg"], "SVG图片", "SVG image"),
+    (&["ico"], "图标", "Icon"),
+    (&["tif", "tiff"], "TIFF图片", "TIFF image"),
+    (&["mp3"], "MP3音频", "MP3 audio"),
+    (&["wav"], "WAV音频", "WAV audio"),
+    (&["flac"], "FLAC音频", "FLAC audio"),
+    (&["ogg"], "Ogg音频", "Ogg audio"),
+    (&["mp4", "m4v"], "MP4视频", "MP4 video"),
+    (&["mkv"], "MKV视频", "MKV video"),
+    (&["avi"], "AVI视频", "AVI video"),
+    (&["mov"], "QuickTime视频", "QuickTime video"),
+    (&["webm"], "WebM视频", "WebM video"),
+    (&["ttf", "otf", "woff", "woff2"], "字体", "Font"),
+    // 压缩包及二进制
+    (&["zip"], "ZIP压缩包", "ZIP archive"),
+    (&["gz", "tgz"], "Gzip压缩包", "Gzip archive"),
+    (&["tar"], "TAR归档", "TAR archive"),
+    (&["bz2"], "Bzip2压缩包", "Bzip2 archive"),
+    (&["xz"], "XZ压缩包", "XZ archive"),
+    (&["zst"], "Zstandard压缩包", "Zstandard archive"),
+    (&["7z"], "7-Zip压缩包", "7-Zip archive"),
+    (&["rar"], "RAR压缩包", "RAR archive"),
+    (&["jar"], "Java归档", "Java archive"),
+    (&["class"], "Java类文件", "Java class file"),
+    (&["exe"], "Windows可执行文件", "Windows executable"),
+    (&["dll"], "Windows动态库", "Windows DLL"),
+    (&["so"], "共享库", "Shared library"),
+    (&["elf"], "ELF可执行文件", "ELF executable"),
+    (&["wasm"], "WebAssembly模块", "WebAssembly module"),
+    (&["deb"], "Debian软件包", "Debian package"),
+    (&["rpm"], "RPM软件包", "RPM package"),
+    (&["iso"], "光盘镜像", "Disk image"),
+];
+
+/// 按文件开头的魔数识别类型，返回对应的扩展名（如 "png"、"zip"、"elf"）
+pub fn sniff(path: &Path) -> Option<&'static str> {
+    infer::get_from_path(path)
+        .ok()
+        .flatten()
+        .map(|kind| kind.extension())
+}
+
+/// 并行识别root下各普通文件的内容类型，写入item.sniffed_type
+pub fn sniff_items(root: &Path, items: &mut [TreeItem]) {
+    items
+        .par_iter_mut()
+        .filter(|item| item.kind == EntryKind::File && !item.name.starts_with("📊"))
+        .for_each(|item| {
+            item.sniffed_type = sniff(&root.join(&item.full_path)).map(str::to_string);
+        });
+}
+
+/// 文件的类型名称：优先按内容识别出的类型，其次按扩展名，都不认识时为 "XXX文件"
+pub fn describe(name: &str, sniffed: Option<&str>, lang: Language) -> String {
+    let lower = name.to_lowercase();
+    let extension = match lower.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => Some(ext),
+        _ => None,
+    };
+    let keys = [sniffed, extension, Some(lower.trim_start_matches('.'))];
+    for key in keys.into_iter().flatten() {
+        if let Some((_, zh, en)) = KINDS.iter().find(|(names, _, _)| names.contains(&key)) {
+            return lang.text(zh, en).to_string();
+        }
+    }
+    match sniffed.or(extension) {
+        Some(ext) => {
+            let ext = ext.to_uppercase();
+            lang.text(&format!("{ext}文件"), &format!("{ext} file"))
+                .to_string()
+        }
+        None => lang.text("文件", "File").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe("main.rs", None, Language::En), "Rust source");
+        assert_eq!(describe("logo.PNG", None, Language::Zh), "PNG图片");
+        // 按内容识别的类型优先于扩展名
+        assert_eq!(
+            describe("photo.txt", Some("png"), Language::En),
+            "PNG image"
+        );
+        assert_eq!(describe("Makefile", None, Language::En), "Makefile");
+        assert_eq!(describe("data.abc", None, Language::Zh), "ABC文件");
+        assert_eq!(describe("LICENSE", None, Language::En), "File");
+    }
+}
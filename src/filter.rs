@@ -0,0 +1,407 @@
+//! 解析结果过滤（--include/--exclude/--max-depth/--dirs-only等），对所有输入格式生效
+
+use anyhow::Context;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::RegexSet;
+
+use crate::error::Result;
+use crate::i18n::Language;
+use crate::parser::TreeItem;
+
+/// 项目列表过滤器
+#[derive(Debug, Default)]
+pub struct TreeFilter {
+    include: Option<GlobSet>,        // 只保留匹配的文件（及其所在目录）
+    exclude: Option<GlobSet>,        // 排除匹配的项目及其子项目
+    exclude_regex: Option<RegexSet>, // 按正则排除项目及其子项目
+    extensions: Vec<String>,         // 只保留这些扩展名的文件（小写，不含 "."）
+    max_depth: Option<usize>,        // 最大层级深度
+    show_truncated: bool,            // 为被截断的目录追加占位行
+    dirs_only: bool,                 // 只保留目录
+    files_only: bool,                // 只保留文件（层级列仍显示上级目录）
+    lang: Language,                  // 占位行文本的语言
+}
+
+impl TreeFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置包含模式，如 "*.rs"：只保留匹配的文件，并保留其所在目录
+    pub fn include(mut self, patterns: &[String]) -> Result<Self> {
+        self.include = self.glob_set(patterns)?;
+        Ok(self)
+    }
+
+    /// 设置排除模式，如 "node_modules"：匹配的项目连同子项目一起排除（优先于包含模式）
+    pub fn exclude(mut self, patterns: &[String]) -> Result<Self> {
+        self.exclude = self.glob_set(patterns)?;
+        Ok(self)
+    }
+
+    /// 设置排除正则，如 "^(target|build|__pycache__)$"：匹配名称或完整路径的项目
+    /// 连同子项目一起排除，不论位于哪一层
+    pub fn exclude_regex(mut self, patterns: &[String]) -> Result<Self> {
+        if !patterns.is_empty() {
+            self.exclude_regex =
+                Some(RegexSet::new(patterns).context("无效的正则表达式（--exclude-regex）")?);
+        }
+        Ok(self)
+    }
+
+    /// 设置扩展名过滤，如 ["rs", "toml", "tar.gz"]：只保留这些扩展名的文件及其所在目录
+    pub fn extensions(mut self, extensions: &[String]) -> Self {
+        self.extensions = extensions
+            .iter()
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .collect();
+        self
+    }
+
+    /// 设置最大层级深度，show_truncated为true时在被截断的目录下追加 "…（另有 n 项）" 占位行
+    pub fn max_depth(mut self, max_depth: usize, show_truncated: bool) -> Self {
+        self.max_depth = Some(max_depth);
+        self.show_truncated = show_truncated;
+        self
+    }
+
+    /// 设置占位行文本的语言
+    pub fn lang(mut self, lang: Language) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// 只保留目录（与tree -d相同）
+    pub fn dirs_only(mut self) -> Self {
+        self.dirs_only = true;
+        self
+    }
+
+    /// 只保留文件，上级目录仍显示在层级列中
+    pub fn files_only(mut self) -> Self {
+        self.files_only = true;
+        self
+    }
+
+    /// 过滤项目列表，有项目被过滤时重新计算统计信息
+    pub fn apply(&self, items: Vec<TreeItem>) -> Vec<TreeItem> {
+        let (mut items, stats): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .partition(|item| !item.name.starts_with("📊"));
+        let original_count = items.len();
+
+        if let Some(exclude) = &self.exclude {
+            items = self.prune(items, |item| self.matches(exclude, item));
+        }
+        if let Some(exclude_regex) = &self.exclude_regex {
+            items = self.prune(items, |item| {
+                exclude_regex.is_match(&item.name) || exclude_regex.is_match(&item.full_path)
+            });
+        }
+        if let Some(include) = &self.include {
+            items =
+                self.keep_with_ancestors(items, |item| item.is_file && self.matches(include, item));
+        }
+
+        if !self.extensions.is_empty() {
+            items = self.keep_with_ancestors(items, |item| {
+                let name = item.name.to_lowercase();
+                item.is_file
+                    && self
+                        .extensions
+                        .iter()
+                        .any(|ext| name.ends_with(&format!(".{ext}")))
+            });
+        }
+        if self.dirs_only {
+            items.retain(|item| !item.is_file);
+        }
+        if self.files_only {
+            items.retain(|item| item.is_file);
+        }
+
+        let mut truncated = Vec::new();
+        if let Some(max_depth) = self.max_depth {
+            items = self.truncate(items, max_depth, &mut truncated);
+        }
+
+        // 统计信息不计入占位行
+        let changed = items.len() != original_count;
+        let file_count = items.iter().filter(|item| item.is_file).count();
+        let dir_count = items.len() - file_count;
+
+        if self.show_truncated && !truncated.is_empty() {
+            items = self.insert_placeholders(items, &truncated);
+        }
+        items.extend(stats.into_iter().map(|stats| {
+            if changed {
+                TreeItem::stats(&format!("{dir_count} directories, {file_count} files"))
+            } else {
+                stats
+            }
+        }));
+        items
+    }
+
+    /// 编译通配符模式，未指定模式时返回None
+    fn glob_set(&self, patterns: &[String]) -> Result<Option<GlobSet>> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            builder
+                .add(Glob::new(pattern).with_context(|| format!("无效的通配符模式: {pattern}"))?);
+        }
+        Ok(Some(builder.build().context("无法编译通配符模式")?))
+    }
+
+    /// 按完整路径或名称匹配（"*.rs" 与 "src/**" 都可用）
+    fn matches(&self, set: &GlobSet, item: &TreeItem) -> bool {
+        set.is_match(&item.full_path) || set.is_match(&item.name)
+    }
+
+    /// 移除满足条件的项目及其所有子项目
+    fn prune(&self, items: Vec<TreeItem>, pruned: impl Fn(&TreeItem) -> bool) -> Vec<TreeItem> {
+        let mut pruned_level: Option<usize> = None;
+        items
+            .into_iter()
+            .filter(|item| {
+                if pruned_level.is_some_and(|level| item.level > level) {
+                    return false;
+                }
+                pruned_level = None;
+                if pruned(item) {
+                    pruned_level = Some(item.level);
+                    return false;
+                }
+                true
+            })
+            .collect()
+    }
+
+    /// 去掉超过最大深度的项目，记录每个被截断的目录(在结果中的位置, 被截断的项目数)
+    fn truncate(
+        &self,
+        items: Vec<TreeItem>,
+        max_depth: usize,
+        truncated: &mut Vec<(usize, usize)>,
+    ) -> Vec<TreeItem> {
+        let mut result: Vec<TreeItem> = Vec::new();
+        for item in items {
+            if item.level <= max_depth {
+                result.push(item);
+                continue;
+            }
+            // 被截断项目之前保留的最后一项就是其位于最大深度的上级目录
+            let parent = result.len().saturating_sub(1);
+            match truncated.last_mut() {
+                Some((index, count)) if *index == parent => *count += 1,
+                _ => truncated.push((parent, 1)),
+            }
+        }
+        result
+    }
+
+    /// 在被截断的目录后插入占位行
+    fn insert_placeholders(
+        &self,
+        items: Vec<TreeItem>,
+        truncated: &[(usize, usize)],
+    ) -> Vec<TreeItem> {
+        let mut result = Vec::with_capacity(items.len() + truncated.len());
+        let mut pending = truncated.iter().peekable();
+        for (index, item) in items.into_iter().enumerate() {
+            let placeholder = pending
+                .next_if(|(parent, _)| *parent == index)
+                .map(|(_, count)| TreeItem {
+                    name: self
+                        .lang
+                        .text("…（另有 {n} 项）", "… ({n} more items)")
+                        .replace("{n}", &count.to_string()),
+                    level: item.level + 1,
+                    is_file: true,
+                    full_path: format!("{}/…", item.full_path),
+                    ..Default::default()
+                });
+            result.push(item);
+            result.extend(placeholder);
+        }
+        result
+    }
+
+    /// 只保留满足条件的项目及其上级目录，保证层级列合并时结构完整
+    fn keep_with_ancestors(
+        &self,
+        items: Vec<TreeItem>,
+        keep: impl Fn(&TreeItem) -> bool,
+    ) -> Vec<TreeItem> {
+        let mut kept = vec![false; items.len()];
+        let mut ancestors: Vec<usize> = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            ancestors.truncate(item.level.saturating_sub(1));
+            if keep(item) {
+                kept[index] = true;
+                for &ancestor in &ancestors {
+                    kept[ancestor] = true;
+                }
+            }
+            ancestors.push(index);
+        }
+
+        items
+            .into_iter()
+            .zip(kept)
+            .filter_map(|(item, kept)| kept.then_some(item))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    const INPUT: &str = "\
+.
+├── node_modules
+│   └── lodash
+│       └── index.js
+├── src
+│   ├── main.rs
+│   └── util.js
+└── Cargo.toml
+
+3 directories, 4 files
+";
+
+    fn paths(items: &[TreeItem]) -> Vec<&str> {
+        items.iter().map(|item| item.full_path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_include_exclude() {
+        let items = TreeParser::new().parse(INPUT, false).unwrap();
+
+        let filter = TreeFilter::new()
+            .exclude(&["node_modules".to_string()])
+            .unwrap();
+        assert_eq!(
+            paths(&filter.apply(items.clone())),
+            [
+                "src",
+                "src/main.rs",
+                "src/util.js",
+                "Cargo.toml",
+                "📊 统计: 1 directories, 3 files"
+            ]
+        );
+
+        // 包含模式保留上级目录，排除模式优先
+        let filter = TreeFilter::new()
+            .include(&["*.js".to_string(), "*.rs".to_string()])
+            .unwrap()
+            .exclude(&["node_modules/**".to_string()])
+            .unwrap();
+        assert_eq!(
+            paths(&filter.apply(items)),
+            [
+                "src",
+                "src/main.rs",
+                "src/util.js",
+                "📊 统计: 1 directories, 2 files"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exclude_regex() {
+        let input = ".\n├── app\n│   ├── __pycache__\n│   │   └── a.pyc\n│   └── a.py\n└── build\n    └── out.txt\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+
+        let filter = TreeFilter::new()
+            .exclude_regex(&["^(build|__pycache__)$".to_string()])
+            .unwrap();
+        assert_eq!(
+            paths(&filter.apply(items)),
+            ["app", "app/a.py", "📊 统计: 1 directories, 1 files"]
+        );
+        assert!(TreeFilter::new().exclude_regex(&["(".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_extensions() {
+        let items = TreeParser::new().parse(INPUT, false).unwrap();
+
+        let filter = TreeFilter::new().extensions(&[".RS".to_string(), "toml".to_string()]);
+        assert_eq!(
+            paths(&filter.apply(items)),
+            [
+                "src",
+                "src/main.rs",
+                "Cargo.toml",
+                "📊 统计: 1 directories, 2 files"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let items = TreeParser::new().parse(INPUT, false).unwrap();
+
+        let truncated = TreeFilter::new().max_depth(1, true).apply(items.clone());
+        let names: Vec<_> = truncated.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "node_modules",
+                "…（另有 2 项）",
+                "src",
+                "…（另有 2 项）",
+                "Cargo.toml",
+                "📊 统计: 2 directories, 1 files"
+            ]
+        );
+        assert_eq!(truncated[1].level, 2);
+
+        let english = TreeFilter::new()
+            .max_depth(1, true)
+            .lang(Language::En)
+            .apply(items.clone());
+        assert_eq!(english[1].name, "… (2 more items)");
+
+        let truncated = TreeFilter::new().max_depth(2, false).apply(items);
+        assert!(truncated.iter().all(|item| item.level <= 2));
+        assert_eq!(truncated.len(), 7);
+    }
+
+    #[test]
+    fn test_dirs_only_files_only() {
+        let items = TreeParser::new().parse(INPUT, false).unwrap();
+
+        let dirs = TreeFilter::new().dirs_only().apply(items.clone());
+        assert_eq!(
+            paths(&dirs),
+            [
+                "node_modules",
+                "node_modules/lodash",
+                "src",
+                "📊 统计: 3 directories, 0 files"
+            ]
+        );
+
+        let files = TreeFilter::new().files_only().apply(items);
+        assert_eq!(
+            paths(&files),
+            [
+                "node_modules/lodash/index.js",
+                "src/main.rs",
+                "src/util.js",
+                "Cargo.toml",
+                "📊 统计: 0 directories, 4 files"
+            ]
+        );
+        assert_eq!(files[0].level, 3);
+    }
+}
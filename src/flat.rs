@@ -0,0 +1,192 @@
+//! 扁平路径列表（压缩包目录、unzip -l、tar -tvf等）转为层级结构
+
+use chrono::NaiveDateTime;
+use std::collections::HashMap;
+
+use crate::docker::ImageLayer;
+use crate::hash::FileHash;
+use crate::parser::{EntryKind, TreeItem};
+
+/// 扁平列表中的一项
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlatEntry {
+    pub path: String, // 以"/"分隔的相对路径（目录可带"/"后缀，开头的"./"、"/"会被去掉）
+    pub is_dir: bool,
+    pub size: Option<u64>,
+    pub modified: Option<NaiveDateTime>,
+    pub permissions: Option<String>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub link_target: Option<String>,
+    pub hash: Option<FileHash>, // 列表中记录的校验和/对象ID（如git ls-tree）
+    pub error: Option<String>,  // 扫描时的读取错误（如ncdu的read_error）
+    pub storage_class: Option<String>, // 对象存储的存储类别（如S3的STANDARD、GLACIER）
+    pub layer: Option<ImageLayer>, // 最后修改该文件的镜像层（--docker-image）
+}
+
+impl FlatEntry {
+    /// 按路径创建（以"/"结尾的为目录）
+    pub fn new(path: &str) -> Self {
+        let path = path.replace('\\', "/");
+        Self {
+            is_dir: path.ends_with('/'),
+            path,
+            ..Default::default()
+        }
+    }
+}
+
+/// unix权限位转为 "drwxr-xr-x" 形式
+pub(crate) fn mode_string(type_char: char, mode: u32) -> String {
+    let mut text = String::from(type_char);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        text.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    text
+}
+
+/// 层级树中的一个节点
+#[derive(Debug, Default)]
+struct Node {
+    name: String,
+    entry: Option<FlatEntry>,      // 列表中没有单独列出的上级目录为None
+    children: Vec<usize>,          // 按首次出现的顺序
+    index: HashMap<String, usize>, // 子节点名称 -> 节点编号
+}
+
+/// 将扁平列表转为与TreeParser::parse相同结构的项目列表：
+/// 补齐没有单独列出的上级目录，同级项目保持在列表中首次出现的顺序，末尾为统计行
+pub fn build_tree(entries: Vec<FlatEntry>, include_hidden: bool) -> Vec<TreeItem> {
+    let mut nodes = vec![Node::default()];
+    for entry in entries {
+        let path = entry.path.trim_start_matches("./").trim_matches('/');
+        let segments: Vec<String> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty() && *segment != ".")
+            .map(str::to_string)
+            .collect();
+        if segments.is_empty() {
+            continue;
+        }
+        // 过滤隐藏目录/文件（任一段以.开头）
+        if !include_hidden && segments.iter().any(|segment| segment.starts_with('.')) {
+            continue;
+        }
+
+        let mut current = 0;
+        for segment in segments {
+            current = match nodes[current].index.get(&segment) {
+                Some(&child) => child,
+                None => {
+                    let child = nodes.len();
+                    nodes.push(Node {
+                        name: segment.clone(),
+                        ..Default::default()
+                    });
+                    nodes[current].index.insert(segment, child);
+                    nodes[current].children.push(child);
+                    child
+                }
+            };
+        }
+        nodes[current].entry = Some(entry);
+    }
+
+    let mut items = Vec::new();
+    collect(&nodes, 0, 0, "", &mut items);
+
+    let file_count = items.iter().filter(|item| item.is_file).count();
+    let dir_count = items.len() - file_count;
+    items.push(TreeItem::stats(&format!(
+        "{dir_count} directories, {file_count} files"
+    )));
+    items
+}
+
+/// 按深度优先顺序输出node的子节点
+fn collect(
+    nodes: &[Node],
+    node: usize,
+    level: usize,
+    parent_path: &str,
+    items: &mut Vec<TreeItem>,
+) {
+    for &child in &nodes[node].children {
+        let child_node = &nodes[child];
+        let full_path = if parent_path.is_empty() {
+            child_node.name.clone()
+        } else {
+            format!("{parent_path}/{}", child_node.name)
+        };
+        let entry = child_node.entry.clone().unwrap_or_default();
+        let is_dir = entry.is_dir || child_node.entry.is_none() || !child_node.children.is_empty();
+        let kind = if is_dir {
+            EntryKind::Directory
+        } else if entry.link_target.is_some() {
+            EntryKind::Symlink
+        } else {
+            EntryKind::File
+        };
+        items.push(TreeItem {
+            name: child_node.name.clone(),
+            level: level + 1,
+            is_file: !is_dir,
+            kind,
+            full_path: full_path.clone(),
+            size: entry.size,
+            modified: entry.modified,
+            permissions: entry.permissions,
+            owner: entry.owner,
+            group: entry.group,
+            link_target: entry.link_target,
+            hash: entry.hash,
+            error: entry.error,
+            storage_class: entry.storage_class,
+            layer: entry.layer,
+            ..Default::default()
+        });
+        collect(nodes, child, level + 1, &full_path, items);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_tree() {
+        let entries = vec![
+            FlatEntry {
+                size: Some(30),
+                ..FlatEntry::new("./src/bin/main.rs")
+            },
+            FlatEntry::new("README.md"),
+            FlatEntry::new("src/"),
+            FlatEntry::new("src/.hidden"),
+            FlatEntry {
+                size: Some(20),
+                ..FlatEntry::new("src/lib.rs")
+            },
+        ];
+        let items = build_tree(entries, false);
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.level, item.is_file))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("src", 1, false),
+                ("src/bin", 2, false),
+                ("src/bin/main.rs", 3, true),
+                ("src/lib.rs", 2, true),
+                ("README.md", 1, true),
+                ("📊 统计: 2 directories, 3 files", 0, false),
+            ]
+        );
+        assert_eq!(items[2].size, Some(30));
+    }
+}
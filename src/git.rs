@@ -0,0 +1,213 @@
+//! 目录遍历时的git信息（--git）：跟踪状态、最后提交时间及作者
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDateTime};
+use git2::{Repository, Sort, Status, StatusOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::error::Result;
+use crate::i18n::Language;
+use crate::parser::TreeItem;
+
+/// 项目在git中的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitStatus {
+    Tracked,   // 已跟踪且未修改
+    Modified,  // 已跟踪，工作区或暂存区有修改
+    Added,     // 已暂存的新文件
+    Untracked, // 未跟踪
+    Ignored,   // 被.gitignore忽略
+}
+
+impl GitStatus {
+    /// 显示文本
+    pub fn label(self, lang: Language) -> &'static str {
+        let (zh, en) = match self {
+            GitStatus::Tracked => ("已跟踪", "Tracked"),
+            GitStatus::Modified => ("已修改", "Modified"),
+            GitStatus::Added => ("已添加", "Added"),
+            GitStatus::Untracked => ("未跟踪", "Untracked"),
+            GitStatus::Ignored => ("已忽略", "Ignored"),
+        };
+        lang.text(zh, en)
+    }
+
+    fn from_status(status: Status) -> Self {
+        if status.is_ignored() {
+            GitStatus::Ignored
+        } else if status.is_wt_new() {
+            GitStatus::Untracked
+        } else if status.is_index_new() {
+            GitStatus::Added
+        } else if status.is_empty() {
+            GitStatus::Tracked
+        } else {
+            GitStatus::Modified
+        }
+    }
+}
+
+/// 项目的git信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitInfo {
+    pub status: GitStatus,
+    pub last_commit: Option<NaiveDateTime>, // 最后一次修改该项目的提交时间（提交者所在时区）
+    pub author: Option<String>,             // 该提交的作者
+}
+
+/// 为root下遍历得到的项目填写git信息（root可以是仓库中的子目录）
+pub fn annotate(root: &Path, items: &mut [TreeItem]) -> Result<()> {
+    let repo =
+        Repository::discover(root).with_context(|| format!("不在git仓库中: {}", root.display()))?;
+    let workdir = repo.workdir().context("不支持裸仓库")?.canonicalize()?;
+    let root = root.canonicalize()?;
+    let prefix = root
+        .strip_prefix(&workdir)
+        .context("遍历的目录不在仓库的工作区中")?
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    let repo_path = |full_path: &str| {
+        if prefix.is_empty() {
+            full_path.to_string()
+        } else {
+            format!("{prefix}/{full_path}")
+        }
+    };
+
+    // 有变化的路径；未跟踪及被忽略的目录只列出目录本身（如 "target/"），其下项目沿用目录的状态
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .include_ignored(true)
+        .recurse_untracked_dirs(false)
+        .recurse_ignored_dirs(false);
+    let changes: HashMap<String, Status> = repo
+        .statuses(Some(&mut options))
+        .context("无法读取git状态")?
+        .iter()
+        .filter_map(|entry| {
+            let path = entry.path()?.trim_end_matches('/').to_string();
+            Some((path, entry.status()))
+        })
+        .collect();
+    let status_of = |path: &str| {
+        let mut current = Some(path);
+        while let Some(path) = current {
+            if let Some(status) = changes.get(path) {
+                return GitStatus::from_status(*status);
+            }
+            current = path.rsplit_once('/').map(|(parent, _)| parent);
+        }
+        GitStatus::Tracked
+    };
+
+    let mut paths = Vec::with_capacity(items.len());
+    for item in items.iter_mut() {
+        let path = repo_path(&item.full_path);
+        // 统计行及.git目录本身不属于仓库内容
+        if item.name.starts_with("📊") || path == ".git" || path.starts_with(".git/") {
+            paths.push(None);
+            continue;
+        }
+        let status = status_of(&path);
+        item.git = Some(GitInfo {
+            status,
+            last_commit: None,
+            author: None,
+        });
+        let committed = !matches!(status, GitStatus::Untracked | GitStatus::Ignored);
+        paths.push(committed.then_some(path));
+    }
+
+    let last_commits = last_commits(&repo, paths.iter().flatten().cloned().collect())?;
+    for (item, path) in items.iter_mut().zip(paths) {
+        let (Some(info), Some(path)) = (item.git.as_mut(), path) else {
+            continue;
+        };
+        if let Some((time, author)) = last_commits.get(&path) {
+            info.last_commit = Some(*time);
+            info.author = Some(author.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// 从HEAD开始按时间倒序查找最后一次修改各路径（目录为其下任意文件）的提交
+fn last_commits(
+    repo: &Repository,
+    mut pending: HashSet<String>,
+) -> Result<HashMap<String, (NaiveDateTime, String)>> {
+    let mut found = HashMap::new();
+    // 还没有提交的仓库
+    if pending.is_empty() || repo.head().is_err() {
+        return Ok(found);
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TIME)?;
+    revwalk.push_head()?;
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree()?),
+            None => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+
+        let time = commit.time();
+        let seconds = time.seconds() + i64::from(time.offset_minutes()) * 60;
+        let Some(datetime) = DateTime::from_timestamp(seconds, 0) else {
+            continue;
+        };
+        let author = commit.author().name().unwrap_or_default().to_string();
+
+        for delta in diff.deltas() {
+            let Some(path) = delta.new_file().path().or(delta.old_file().path()) else {
+                continue;
+            };
+            let path = path.to_string_lossy();
+            // 修改的文件及其各级上级目录
+            let mut current = Some(path.as_ref());
+            while let Some(path) = current {
+                if pending.remove(path) {
+                    found.insert(path.to_string(), (datetime.naive_utc(), author.clone()));
+                }
+                current = path.rsplit_once('/').map(|(parent, _)| parent);
+            }
+        }
+        if pending.is_empty() {
+            break;
+        }
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_label() {
+        let status = GitStatus::from_status(Status::WT_NEW);
+        assert_eq!(status, GitStatus::Untracked);
+        assert_eq!(status.label(Language::Zh), "未跟踪");
+        assert_eq!(
+            GitStatus::from_status(Status::IGNORED).label(Language::En),
+            "Ignored"
+        );
+        assert_eq!(
+            GitStatus::from_status(Status::WT_MODIFIED),
+            GitStatus::Modified
+        );
+        assert_eq!(GitStatus::from_status(Status::INDEX_NEW), GitStatus::Added);
+        assert_eq!(GitStatus::from_status(Status::CURRENT), GitStatus::Tracked);
+    }
+}
@@ -0,0 +1,150 @@
+//! 文件校验和（--hash）
+
+use md5::Md5;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::{bail, Result};
+use crate::parser::{EntryKind, TreeItem};
+
+/// 校验和算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    Md5,
+    Sha256,
+    GitBlob, // git对象ID（与git ls-tree、git hash-object一致）
+}
+
+impl HashAlgorithm {
+    /// 按名称选择："md5"、"sha256"或"git"（不区分大小写，可写作"sha-256"）
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().replace('-', "").as_str() {
+            "md5" => Ok(HashAlgorithm::Md5),
+            "sha256" => Ok(HashAlgorithm::Sha256),
+            "git" => Ok(HashAlgorithm::GitBlob),
+            _ => bail!("不支持的校验和算法: {name}（可用: md5, sha256, git）"),
+        }
+    }
+
+    /// 显示名称，也用作表头
+    pub fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "MD5",
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::GitBlob => "Git Blob",
+        }
+    }
+
+    /// 十六进制校验和的长度
+    pub(crate) fn hex_len(self) -> usize {
+        match self {
+            HashAlgorithm::Md5 => 32,
+            HashAlgorithm::Sha256 => 64,
+            HashAlgorithm::GitBlob => 40,
+        }
+    }
+
+    /// 计算文件的校验和（小写十六进制）
+    pub fn hash_file(self, path: &Path) -> io::Result<String> {
+        if self == HashAlgorithm::GitBlob {
+            return git2::Oid::hash_file(git2::ObjectType::Blob, path)
+                .map(|oid| oid.to_string())
+                .map_err(io::Error::other);
+        }
+        let file = File::open(path)?;
+        match self {
+            HashAlgorithm::Md5 => digest_reader::<Md5>(file),
+            _ => digest_reader::<Sha256>(file),
+        }
+    }
+}
+
+/// 文件的校验和
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileHash {
+    pub algorithm: HashAlgorithm,
+    pub value: String, // 小写十六进制
+}
+
+/// 并行计算root下各普通文件的校验和；每完成一个文件调用progress(已完成数, 文件总数)，
+/// 无法读取的文件在项目上记录错误
+pub fn hash_items(
+    root: &Path,
+    items: &mut [TreeItem],
+    algorithm: HashAlgorithm,
+    progress: impl Fn(usize, usize) + Sync,
+) {
+    let mut files: Vec<&mut TreeItem> = items
+        .iter_mut()
+        .filter(|item| item.kind == EntryKind::File && !item.name.starts_with("📊"))
+        .collect();
+    let total = files.len();
+    let done = AtomicUsize::new(0);
+
+    files.par_iter_mut().for_each(|item| {
+        let path = root.join(&item.full_path);
+        // 跟随符号链接时，指向目录外的链接目标可能不是普通文件
+        if path.is_file() {
+            match algorithm.hash_file(&path) {
+                Ok(value) => item.hash = Some(FileHash { algorithm, value }),
+                Err(err) => {
+                    item.error.get_or_insert_with(|| err.to_string());
+                }
+            }
+        }
+        progress(done.fetch_add(1, Ordering::Relaxed) + 1, total);
+    });
+}
+
+/// 分块读取并计算摘要
+fn digest_reader<D: Digest>(mut reader: impl Read) -> io::Result<String> {
+    let mut hasher = D::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest() {
+        assert_eq!(
+            digest_reader::<Md5>("abc".as_bytes()).unwrap(),
+            "900150983cd24fb0d6963f7d28e17f72"
+        );
+        assert_eq!(
+            digest_reader::<Sha256>("abc".as_bytes()).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            HashAlgorithm::from_name("SHA-256").unwrap(),
+            HashAlgorithm::Sha256
+        );
+        assert!(HashAlgorithm::from_name("crc32").is_err());
+
+        let path = std::env::temp_dir().join("tree_to_excel_git_blob_test");
+        std::fs::write(&path, "hello\n").unwrap();
+        let blob = HashAlgorithm::GitBlob.hash_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // 与 echo hello | git hash-object --stdin 一致
+        assert_eq!(blob, "ce013625030ba8dba906f756967f9e9ca394464a");
+    }
+}
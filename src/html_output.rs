@@ -0,0 +1,143 @@
+//! HTML表格输出（--to html）
+
+use anyhow::Context;
+use std::io::Write;
+
+use crate::error::Result;
+use crate::excel::{convert_to_rows, level_spans};
+use crate::i18n::Language;
+use crate::output::open_output;
+use crate::parser::TreeItem;
+
+/// 与Excel一致的配色：目录浅蓝加粗、文件浅绿、路径淡黄、统计粉色
+const STYLE: &str = "table { border-collapse: collapse; font-family: sans-serif; font-size: 14px; }
+th, td { border: 1px solid #999; padding: 2px 8px; }
+th { background: #4F81BD; color: #FFFFFF; }
+td.dir { background: #E8F4FD; font-weight: bold; text-align: center; vertical-align: middle; }
+td.file { background: #F0F8E8; }
+td.path { background: #FFFEF7; }
+td.stats { background: #FFE4E1; color: #8B0000; font-weight: bold; }";
+
+/// HTML表格生成器：层级列使用rowspan呈现与Excel相同的合并单元格
+#[derive(Debug, Default)]
+pub struct HtmlGenerator {
+    lang: Language, // 表头及统计标签的语言
+}
+
+impl HtmlGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置表头及统计标签的语言
+    pub fn lang(mut self, lang: Language) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// 生成HTML文件（"-"表示标准输出）
+    pub fn generate(&self, items: Vec<TreeItem>, output_path: &str) -> Result<()> {
+        let mut writer = open_output(output_path)?;
+        writer
+            .write_all(self.render(items).as_bytes())
+            .and_then(|()| writer.flush())
+            .with_context(|| format!("无法保存HTML文件: {output_path}"))?;
+        Ok(())
+    }
+
+    /// 渲染为完整的HTML文档
+    pub fn render(&self, items: Vec<TreeItem>) -> String {
+        let rows = convert_to_rows(items);
+        let max_level = rows.first().map_or(1, |row| row.max_level);
+        let (stats_rows, data_rows): (Vec<_>, Vec<_>) =
+            rows.iter().partition(|row| row.levels[0].starts_with("📊"));
+        let spans = level_spans(&data_rows);
+
+        let mut content = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n<table>\n<tr>"
+        );
+        for level in 1..=max_level {
+            content.push_str(&format!("<th>L{level}</th>"));
+        }
+        content.push_str(&format!(
+            "<th>{}</th></tr>\n",
+            self.lang.text("完整路径", "Full Path")
+        ));
+
+        for (row, row_spans) in data_rows.iter().zip(&spans) {
+            content.push_str("<tr>");
+            let leaf_idx = row.levels.iter().rposition(|name| !name.is_empty());
+            for (level_idx, (level_name, span)) in row.levels.iter().zip(row_spans).enumerate() {
+                // 被上方rowspan覆盖的单元格不输出
+                if *span == 0 {
+                    continue;
+                }
+                let class = if Some(level_idx) == leaf_idx && row.is_file {
+                    "file"
+                } else if level_name.is_empty() {
+                    ""
+                } else {
+                    "dir"
+                };
+                let rowspan = if *span > 1 {
+                    format!(" rowspan=\"{span}\"")
+                } else {
+                    String::new()
+                };
+                let class = if class.is_empty() {
+                    String::new()
+                } else {
+                    format!(" class=\"{class}\"")
+                };
+                content.push_str(&format!(
+                    "<td{class}{rowspan}>{}</td>",
+                    self.escape(level_name)
+                ));
+            }
+            content.push_str(&format!(
+                "<td class=\"path\">{}</td></tr>\n",
+                self.escape(&row.full_path)
+            ));
+        }
+
+        for stats_row in stats_rows {
+            content.push_str(&format!(
+                "<tr><td class=\"stats\" colspan=\"{}\">{}</td></tr>\n",
+                max_level + 1,
+                self.escape(&self.lang.stats_label(&stats_row.levels[0]))
+            ));
+        }
+        content.push_str("</table>\n</body>\n</html>\n");
+        content
+    }
+
+    /// 转义HTML特殊字符
+    fn escape(&self, text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_render_html_rowspan() {
+        let input = ".\n├── src\n│   ├── a.rs\n│   └── <b>.rs\n└── README.md\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let html = HtmlGenerator::new().render(items);
+
+        assert!(html.contains(
+            "<tr><td class=\"dir\" rowspan=\"3\">src</td><td></td><td class=\"path\">src</td></tr>"
+        ));
+        assert!(
+            html.contains("<tr><td class=\"file\">a.rs</td><td class=\"path\">src/a.rs</td></tr>")
+        );
+        assert!(html.contains("&lt;b&gt;.rs"));
+        assert!(html.contains("colspan=\"3\">📊 统计: 1 directories, 3 files</td>"));
+    }
+}
@@ -0,0 +1,90 @@
+//! 输出内容（表头、统计标签等）的语言（--lang）
+
+use crate::error::{bail, Result};
+
+/// 统计行名称的前缀（解析时生成）
+const STATS_PREFIX: &str = "📊 统计: ";
+
+/// 输出语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    Zh, // 中文
+    En, // 英文
+}
+
+impl Language {
+    /// 按语言代码选择，如 "zh"、"en"、"zh_CN"、"en-US"
+    pub fn from_code(code: &str) -> Result<Self> {
+        let lower = code.to_ascii_lowercase();
+        match lower.split(['_', '-', '.']).next().unwrap_or_default() {
+            "zh" => Ok(Language::Zh),
+            "en" => Ok(Language::En),
+            _ => bail!("不支持的语言: {code}（可用: zh, en）"),
+        }
+    }
+
+    /// 按系统区域设置（LC_ALL、LC_MESSAGES、LANG）选择：中文区域或未设置时为中文，其他语言为英文
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty());
+        match locale.as_deref() {
+            None | Some("C" | "POSIX") => Language::Zh,
+            Some(locale) if locale.starts_with("C.") => Language::Zh,
+            Some(locale) => Self::from_code(locale).unwrap_or(Language::En),
+        }
+    }
+
+    /// 按语言选择文本
+    pub fn text<'a>(self, zh: &'a str, en: &'a str) -> &'a str {
+        match self {
+            Language::Zh => zh,
+            Language::En => en,
+        }
+    }
+
+    /// 统计行的显示文本（"📊 统计: ..." 在英文中为 "📊 Summary: ..."）
+    pub fn stats_label(self, name: &str) -> String {
+        match (self, name.strip_prefix(STATS_PREFIX)) {
+            (Language::En, Some(stats)) => format!("📊 Summary: {stats}"),
+            _ => name.to_string(),
+        }
+    }
+
+    /// 安全标记分类（detect_secret的返回值）的显示文本
+    pub fn secret_label(self, label: &'static str) -> &'static str {
+        let en = match label {
+            "SSH私钥" => "SSH private key",
+            "证书/私钥" => "Certificate/private key",
+            "环境变量文件" => "Environment file",
+            "凭据文件" => "Credentials file",
+            "疑似凭据" => "Possible credentials",
+            _ => label,
+        };
+        self.text(label, en)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language() {
+        assert_eq!(Language::from_code("en_US.UTF-8").unwrap(), Language::En);
+        assert_eq!(Language::from_code("zh-CN").unwrap(), Language::Zh);
+        assert!(Language::from_code("fr").is_err());
+
+        assert_eq!(
+            Language::En.stats_label("📊 统计: 1 directory, 2 files"),
+            "📊 Summary: 1 directory, 2 files"
+        );
+        assert_eq!(
+            Language::Zh.stats_label("📊 统计: 1 directory, 2 files"),
+            "📊 统计: 1 directory, 2 files"
+        );
+        assert_eq!(Language::En.secret_label("SSH私钥"), "SSH private key");
+    }
+}
@@ -0,0 +1,250 @@
+//! JSON/NDJSON结构化输出（--to json / --to ndjson）
+
+use anyhow::Context;
+use serde::Serialize;
+use std::io::Write;
+
+use crate::error::Result;
+use crate::excel::level_names;
+use crate::output::open_output;
+use crate::parser::TreeItem;
+
+/// 嵌套JSON中的一个节点，结构与tree -J输出兼容（可再用 -f json 读入）
+#[derive(Debug, Serialize)]
+struct JsonNode {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>, // 完整路径（根目录节点没有）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prot: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contents: Option<Vec<JsonNode>>, // 仅目录有
+}
+
+/// 嵌套JSON的顶层元素：根目录节点或末尾的统计节点
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum JsonEntry {
+    Node(Box<JsonNode>),
+    Report {
+        #[serde(rename = "type")]
+        kind: &'static str,
+        directories: usize,
+        files: usize,
+    },
+}
+
+/// NDJSON中每个项目的扁平记录
+#[derive(Debug, Serialize)]
+struct FlatRecord<'a> {
+    levels: Vec<String>,
+    full_path: &'a str,
+    is_file: bool,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    size: Option<u64>,
+    modified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link_target: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// JSON/NDJSON生成器
+#[derive(Debug, Default)]
+pub struct JsonGenerator;
+
+impl JsonGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 生成嵌套结构的JSON文件（"-"表示标准输出）
+    pub fn generate(&self, items: Vec<TreeItem>, output_path: &str) -> Result<()> {
+        let content = self.render(items)?;
+        let mut writer = open_output(output_path)?;
+        writer
+            .write_all(content.as_bytes())
+            .and_then(|()| writer.flush())
+            .with_context(|| format!("无法保存JSON文件: {output_path}"))?;
+        Ok(())
+    }
+
+    /// 生成每行一条记录的NDJSON文件（"-"表示标准输出）
+    pub fn generate_ndjson(&self, items: &[TreeItem], output_path: &str) -> Result<()> {
+        self.write_ndjson(items, open_output(output_path)?)
+            .with_context(|| format!("无法写入NDJSON文件: {output_path}"))?;
+        Ok(())
+    }
+
+    /// 渲染为嵌套JSON：每个根目录一个顶层节点，末尾为report节点
+    pub fn render(&self, items: Vec<TreeItem>) -> Result<String> {
+        let mut file_count = 0;
+        let mut dir_count = 0;
+        let mut roots: Vec<JsonNode> = Vec::new();
+        let mut current_root: Option<Option<String>> = None;
+        // 当前路径上尚未关闭的节点
+        let mut stack: Vec<JsonNode> = Vec::new();
+
+        for item in items {
+            if item.name.starts_with("📊") {
+                continue;
+            }
+            if item.is_file {
+                file_count += 1;
+            } else {
+                dir_count += 1;
+            }
+
+            // 进入新的根目录时关闭上一个根目录
+            if current_root.as_ref() != Some(&item.root) {
+                self.close(&mut stack, 0);
+                roots.extend(stack.pop());
+                stack.push(JsonNode::directory(
+                    item.root.clone().unwrap_or_else(|| ".".to_string()),
+                ));
+                current_root = Some(item.root.clone());
+            }
+
+            self.close(&mut stack, item.level.max(1));
+            stack.push(JsonNode::from_item(item));
+        }
+        self.close(&mut stack, 0);
+        roots.extend(stack.pop());
+
+        let mut document: Vec<JsonEntry> = roots
+            .into_iter()
+            .map(|root| JsonEntry::Node(Box::new(root)))
+            .collect();
+        document.push(JsonEntry::Report {
+            kind: "report",
+            directories: dir_count,
+            files: file_count,
+        });
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    /// 每个项目写为一行JSON记录（统计行不输出）
+    pub fn write_ndjson(&self, items: &[TreeItem], mut writer: impl Write) -> Result<()> {
+        for item in items.iter().filter(|item| !item.name.starts_with("📊")) {
+            let record = FlatRecord {
+                levels: level_names(item),
+                full_path: &item.full_path,
+                is_file: item.is_file,
+                kind: item.kind.tree_type(),
+                size: item.size,
+                modified: item.modified.map(|modified| modified.to_string()),
+                root: item.root.as_deref(),
+                link_target: item.link_target.as_deref(),
+                error: item.error.as_deref(),
+            };
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// 关闭层级不低于level的节点（栈中第一个节点是根目录，层级为0），挂到父节点下
+    fn close(&self, stack: &mut Vec<JsonNode>, level: usize) {
+        while stack.len() > level.max(1) {
+            let node = stack.pop().unwrap();
+            if let Some(contents) = stack.last_mut().and_then(|parent| parent.contents.as_mut()) {
+                contents.push(node);
+            }
+        }
+    }
+}
+
+impl JsonNode {
+    /// 只有名称的目录节点（根目录）
+    fn directory(name: String) -> Self {
+        Self {
+            kind: "directory",
+            name,
+            path: None,
+            size: None,
+            modified: None,
+            prot: None,
+            user: None,
+            group: None,
+            target: None,
+            error: None,
+            contents: Some(Vec::new()),
+        }
+    }
+
+    fn from_item(item: TreeItem) -> Self {
+        Self {
+            kind: item.kind.tree_type(),
+            name: item.name,
+            path: Some(item.full_path),
+            size: item.size,
+            modified: item.modified.map(|modified| modified.to_string()),
+            prot: item.permissions,
+            user: item.owner,
+            group: item.group,
+            target: item.link_target,
+            error: item.error,
+            contents: (!item.is_file).then(Vec::new),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json_parser::JsonTreeParser;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_render_json_round_trip() {
+        let input = ".\n├── src\n│   ├── bin\n│   │   └── main.rs\n│   └── lib.rs\n└── README.md\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let json = JsonGenerator::new().render(items.clone()).unwrap();
+
+        // 输出与tree -J兼容，可以再解析回相同的项目
+        let parsed = JsonTreeParser::new().parse(&json, false).unwrap();
+        let paths = |items: &[TreeItem]| -> Vec<String> {
+            items.iter().map(|item| item.full_path.clone()).collect()
+        };
+        assert_eq!(paths(&parsed), paths(&items));
+    }
+
+    #[test]
+    fn test_write_ndjson() {
+        let input = ".\n└── src\n    └── lib.rs\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let mut output = Vec::new();
+        JsonGenerator::new()
+            .write_ndjson(&items, &mut output)
+            .unwrap();
+
+        let lines: Vec<_> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            r#"{"levels":["src","lib.rs"],"full_path":"src/lib.rs","is_file":true,"type":"file","size":null,"modified":null,"root":"."}"#
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! tree -J（JSON）输出解析
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::error::Result;
+use crate::parser::{EntryKind, TreeItem};
+
+/// tree -J输出中的一个节点
+#[derive(Debug, Deserialize)]
+struct JsonEntry {
+    #[serde(rename = "type", default)]
+    kind: String, // directory / file / link / report ...（错误节点没有type）
+    name: Option<String>,
+    size: Option<u64>,
+    prot: Option<String>, // tree -p的权限字符串
+    user: Option<String>,
+    group: Option<String>,
+    target: Option<String>, // 符号链接目标
+    error: Option<String>,  // 读取错误，如 {"error": "opening dir"}
+    contents: Option<Vec<JsonEntry>>,
+    directories: Option<u64>,
+    files: Option<u64>,
+}
+
+/// tree -J输出解析器
+#[derive(Debug, Default)]
+pub struct JsonTreeParser;
+
+impl JsonTreeParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 解析tree -J输出，返回与TreeParser::parse相同结构的项目列表
+    pub fn parse(&self, input: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let entries: Vec<JsonEntry> =
+            serde_json::from_str(input).context("无法解析tree -J输出的JSON")?;
+
+        let mut items = Vec::new();
+        let mut stats_line = None;
+
+        for entry in &entries {
+            if entry.kind == "report" {
+                stats_line = Some(format!(
+                    "{} directories, {} files",
+                    entry.directories.unwrap_or(0),
+                    entry.files.unwrap_or(0)
+                ));
+                continue;
+            }
+
+            // 顶层节点是根目录（如"."，tree -J dirA dirB时有多个），
+            // 与文本模式一样不作为层级展示，只记录在项目的root中
+            if let Some(contents) = &entry.contents {
+                let start = items.len();
+                self.collect(contents, 1, "", include_hidden, &mut items);
+                for item in &mut items[start..] {
+                    item.root = entry.name.clone();
+                }
+            }
+        }
+
+        let file_count = items.iter().filter(|item| item.is_file).count();
+        let dir_count = items.len() - file_count;
+
+        let stats_text = match stats_line {
+            Some(stats_line) if include_hidden => stats_line,
+            _ => format!("{dir_count} directories, {file_count} files"),
+        };
+        items.push(TreeItem::stats(&stats_text));
+
+        Ok(items)
+    }
+
+    /// 递归收集子节点
+    fn collect(
+        &self,
+        entries: &[JsonEntry],
+        level: usize,
+        parent_path: &str,
+        include_hidden: bool,
+        items: &mut Vec<TreeItem>,
+    ) {
+        for entry in entries {
+            let Some(name) = &entry.name else {
+                continue;
+            };
+
+            // 过滤隐藏目录/文件及其子项目
+            if !include_hidden && name.starts_with('.') {
+                continue;
+            }
+
+            let full_path = if parent_path.is_empty() {
+                name.clone()
+            } else {
+                format!("{parent_path}/{name}")
+            };
+
+            // 无法打开的目录在contents中包含一个错误节点
+            let error = entry
+                .contents
+                .iter()
+                .flatten()
+                .find_map(|child| child.error.clone());
+
+            let kind = EntryKind::from_tree_type(&entry.kind);
+            items.push(TreeItem {
+                name: name.clone(),
+                level,
+                is_file: kind != EntryKind::Directory,
+                kind,
+                full_path: full_path.clone(),
+                size: entry.size,
+                permissions: entry.prot.clone(),
+                owner: entry.user.clone(),
+                group: entry.group.clone(),
+                link_target: entry.target.clone(),
+                error,
+                ..Default::default()
+            });
+
+            if let Some(contents) = &entry.contents {
+                self.collect(contents, level + 1, &full_path, include_hidden, items);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json() {
+        let input = r#"[
+  {"type":"directory","name":".","contents":[
+    {"type":"directory","name":"src","contents":[
+      {"type":"file","name":"main.rs","size":120}
+    ]},
+    {"type":"file","name":".env","size":8},
+    {"type":"file","name":"Makefile","size":42}
+  ]}
+,
+  {"type":"report","directories":1,"files":3}
+]"#;
+        let items = JsonTreeParser::new().parse(input, false).unwrap();
+
+        let paths: Vec<_> = items.iter().map(|item| item.full_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "src",
+                "src/main.rs",
+                "Makefile",
+                "📊 统计: 1 directories, 2 files"
+            ]
+        );
+        assert_eq!(items[1].level, 2);
+        assert_eq!(items[1].size, Some(120));
+        assert!(!items[0].is_file);
+    }
+}
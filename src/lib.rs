@@ -0,0 +1,111 @@
+//! 将`tree`命令输出转换为带有合并单元格的Excel表格。
+//!
+//! ```no_run
+//! use tree_to_excel::{ExcelGenerator, TreeParser};
+//!
+//! # fn main() -> tree_to_excel::Result<()> {
+//! let input = std::fs::read_to_string("tree.txt")?;
+//! let items = TreeParser::new().parse(&input, false)?;
+//! ExcelGenerator::new(false).generate(items, "tree_output.xlsx")?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod archive;
+mod column;
+mod config;
+mod csv_output;
+mod dialect;
+mod diff;
+mod docker;
+mod duplicates;
+mod encoding;
+mod error;
+mod excel;
+mod file_kind;
+mod filter;
+mod flat;
+mod git;
+mod hash;
+mod html_output;
+mod i18n;
+mod json_output;
+mod json_parser;
+mod listing;
+mod loc;
+mod ls_parser;
+mod markdown_output;
+mod ncdu_parser;
+mod ods_output;
+mod output;
+mod parser;
+mod powershell_parser;
+#[cfg(feature = "s3")]
+mod s3;
+mod scaffold;
+#[cfg(feature = "ssh")]
+mod sftp;
+mod sort;
+mod stats;
+mod summary;
+mod theme;
+mod tree_output;
+mod walker;
+#[cfg(feature = "webdav")]
+mod webdav;
+mod windows_parser;
+mod xlsx_parser;
+mod xml_parser;
+
+pub use archive::ArchiveReader;
+pub use column::{ColumnProvider, ModifiedColumn, NotesColumn, PathColumn, SizeColumn};
+pub use config::{Config, ConfigValue};
+pub use csv_output::CsvGenerator;
+pub use dialect::{
+    detect_dialect, detect_format, input_dialect, parse_with, BufferedDialect, InputDialect,
+    TreeDialect,
+};
+pub use diff::{diff_trees, Change, DiffSummary, VerifyReport};
+pub use docker::{DockerImageReader, ImageLayer};
+pub use duplicates::{find_duplicates, DuplicateGroup};
+pub use encoding::InputEncoding;
+pub use error::{Error, Result};
+pub use excel::{
+    detect_secret, ChartKind, ExcelGenerator, ExcelGeneratorBuilder, Layout, SizeFormat,
+};
+pub use file_kind::{describe as describe_file_kind, sniff_items};
+pub use filter::TreeFilter;
+pub use flat::{build_tree, FlatEntry};
+pub use git::{GitInfo, GitStatus};
+pub use hash::{hash_items, FileHash, HashAlgorithm};
+pub use html_output::HtmlGenerator;
+pub use i18n::Language;
+pub use json_output::JsonGenerator;
+pub use json_parser::JsonTreeParser;
+pub use listing::{ListingFormat, ListingParser};
+pub use loc::{count_items as count_lines, language};
+pub use ls_parser::LsParser;
+pub use markdown_output::MarkdownGenerator;
+pub use ncdu_parser::NcduParser;
+pub use ods_output::OdsWriter;
+pub use output::{write_table, Cell, CellKind, CellValue, OutputWriter};
+pub use parser::{
+    include_root, split_by_top, EntryKind, SkippedLine, TreeItem, TreeItemStream, TreeParser,
+};
+pub use powershell_parser::PowerShellParser;
+#[cfg(feature = "s3")]
+pub use s3::{parse_s3_url, S3Lister};
+pub use scaffold::{create_structure, render_script, write_script, Shell};
+#[cfg(feature = "ssh")]
+pub use sftp::{SftpLister, SshTarget};
+pub use sort::{SortKey, TreeSorter};
+pub use stats::{DirectoryStats, ExtensionStats, TopItems, TreeStats, MAX_PATH_LEN};
+pub use summary::SummaryGenerator;
+pub use theme::{Border, Category, CellStyle, Theme};
+pub use tree_output::TreeTextGenerator;
+pub use walker::DirWalker;
+#[cfg(feature = "webdav")]
+pub use webdav::WebDavLister;
+pub use windows_parser::WindowsTreeParser;
+pub use xlsx_parser::XlsxTreeParser;
+pub use xml_parser::XmlTreeParser;
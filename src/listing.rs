@@ -0,0 +1,678 @@
+//! 扁平列表输出解析（-f 7z / rar / unzip / tar / git-ls-tree / paths / du / s3-ls）：7z l、unrar l、
+//! unzip -l、tar -tvf、git ls-tree、du、aws s3 ls的文本输出，以及find、fd、git ls-files等输出的每行一个路径的列表
+
+use chrono::NaiveDateTime;
+
+use crate::error::{Error, Result};
+use crate::flat::{build_tree, mode_string, FlatEntry};
+use crate::hash::{FileHash, HashAlgorithm};
+use crate::parser::{TreeItem, TreeParser};
+
+/// 列表输出的来源工具
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingFormat {
+    SevenZip,  // 7z l（或7z l -slt）
+    Unrar,     // unrar l / unrar v
+    Unzip,     // unzip -l / unzip -v
+    Tar,       // tar -tvf（GNU tar或bsdtar）
+    GitLsTree, // git ls-tree（-r、--long可选）
+    Paths,     // 每行一个路径（find、fd、git ls-files）
+    Du,        // du -a（-b或-h），最末一级为文件
+    DuDirs,    // 不带-a的du（如du -h --max-depth=2），全部为目录
+    S3Ls,      // aws s3 ls（--recursive、--human-readable可选）
+}
+
+impl ListingFormat {
+    /// 按--format的名称选择
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "7z" => Some(ListingFormat::SevenZip),
+            "rar" => Some(ListingFormat::Unrar),
+            "unzip" => Some(ListingFormat::Unzip),
+            "tar" => Some(ListingFormat::Tar),
+            "git-ls-tree" => Some(ListingFormat::GitLsTree),
+            "paths" => Some(ListingFormat::Paths),
+            "du" => Some(ListingFormat::Du),
+            "du-dirs" => Some(ListingFormat::DuDirs),
+            "s3-ls" => Some(ListingFormat::S3Ls),
+            _ => None,
+        }
+    }
+}
+
+/// 扁平列表输出解析器
+#[derive(Debug)]
+pub struct ListingParser {
+    format: ListingFormat,
+}
+
+impl ListingParser {
+    pub fn new(format: ListingFormat) -> Self {
+        Self { format }
+    }
+
+    /// 解析列表输出，返回与TreeParser::parse相同结构的项目列表
+    pub fn parse(&self, input: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let entries = match self.format {
+            // -slt输出以单独的 "----------" 行开始条目列表
+            ListingFormat::SevenZip
+                if input.lines().any(|line| line.trim_end() == "----------") =>
+            {
+                self.parse_7z_technical(input)
+            }
+            ListingFormat::SevenZip => self.parse_7z(input),
+            ListingFormat::Unrar => self.parse_table(input, "Attributes"),
+            ListingFormat::Unzip => self.parse_table(input, "Length"),
+            ListingFormat::Tar => self.parse_tar(input),
+            ListingFormat::GitLsTree => self.parse_git_ls_tree(input),
+            ListingFormat::Paths => self.parse_paths(input),
+            ListingFormat::Du => self.parse_du(input, false),
+            ListingFormat::DuDirs => self.parse_du(input, true),
+            ListingFormat::S3Ls => self.parse_s3_ls(input),
+        };
+        if entries.is_empty() {
+            return Err(Error::parse(
+                None,
+                "没有找到条目列表，请确认输入为对应工具的列表输出",
+            ));
+        }
+        Ok(build_tree(entries, include_hidden))
+    }
+
+    /// 7z l：两条 "-------------------" 分隔线之间为条目，名称从表头的Name列开始
+    fn parse_7z(&self, input: &str) -> Vec<FlatEntry> {
+        let lines: Vec<&str> = input.lines().collect();
+        let separators: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.starts_with("-------------------"))
+            .map(|(index, _)| index)
+            .collect();
+        let (Some(&start), Some(&end)) = (separators.first(), separators.get(1)) else {
+            return Vec::new();
+        };
+        // 分隔线的最后一段对齐名称列
+        let name_col = lines[start].rfind(' ').map_or(0, |col| col + 1);
+
+        let mut entries = Vec::new();
+        for line in &lines[start + 1..end] {
+            let (Some(meta), Some(name)) = (line.get(..name_col), line.get(name_col..)) else {
+                continue;
+            };
+            let attr = meta.get(20..25).unwrap_or_default();
+            let is_dir = attr.starts_with('D');
+            let size = meta
+                .get(25..)
+                .and_then(|rest| rest.split_whitespace().next())
+                .and_then(|size| size.parse().ok());
+            entries.push(FlatEntry {
+                is_dir,
+                size: size.filter(|_| !is_dir),
+                modified: meta.get(..19).and_then(|date| parse_datetime(date.trim())),
+                ..FlatEntry::new(name.trim_end())
+            });
+        }
+        entries
+    }
+
+    /// 7z l -slt：第一条 "----------" 之后每个空行分隔的段落为一个条目
+    fn parse_7z_technical(&self, input: &str) -> Vec<FlatEntry> {
+        let Some((_, body)) = input.split_once("\n----------") else {
+            return Vec::new();
+        };
+        let mut entries = Vec::new();
+        for block in body.split("\n\n") {
+            let field = |key: &str| {
+                block.lines().find_map(|line| {
+                    line.strip_prefix(key)
+                        .and_then(|rest| rest.strip_prefix(" = "))
+                })
+            };
+            let Some(path) = field("Path") else {
+                continue;
+            };
+            let is_dir = field("Folder") == Some("+")
+                || field("Attributes").is_some_and(|attr| attr.starts_with('D'));
+            entries.push(FlatEntry {
+                is_dir,
+                size: field("Size")
+                    .and_then(|size| size.parse().ok())
+                    .filter(|_| !is_dir),
+                modified: field("Modified").and_then(|date| parse_datetime(date.get(..19)?)),
+                ..FlatEntry::new(path)
+            });
+        }
+        entries
+    }
+
+    /// unrar l/v、unzip -l/-v：按表头（以first_column开头、Name结尾）的列名取各列，
+    /// 名称为最后一列（可含空格）
+    fn parse_table(&self, input: &str, first_column: &str) -> Vec<FlatEntry> {
+        let lines: Vec<&str> = input.lines().collect();
+        let Some(header) = lines.iter().position(|line| {
+            let line = line.trim_start();
+            line.starts_with(first_column) && line.trim_end().ends_with("Name")
+        }) else {
+            return Vec::new();
+        };
+        let columns: Vec<&str> = lines[header].split_whitespace().collect();
+        let column = |name: &str| columns.iter().position(|column| *column == name);
+        // unzip -v的Size为压缩后的大小，原始大小在Length列
+        let size_col = column("Length").or(column("Size"));
+        let (attr_col, date_col, time_col) = (column("Attributes"), column("Date"), column("Time"));
+        let fields = columns.len() - 1;
+
+        let mut entries = Vec::new();
+        // 表头下方是分隔线，条目直到下一条分隔线为止
+        for line in lines[header + 2..]
+            .iter()
+            .take_while(|line| !line.trim_start().starts_with("---"))
+        {
+            let Some((values, name)) = split_fields(line, fields) else {
+                continue;
+            };
+            let value = |col: Option<usize>| col.and_then(|col| values.get(col).copied());
+            let attr = value(attr_col).unwrap_or_default();
+            let is_dir = attr.starts_with('d') || attr.contains('D') || name.ends_with('/');
+            let modified = match (value(date_col), value(time_col)) {
+                (Some(date), Some(time)) => parse_datetime(&format!("{date} {time}")),
+                _ => None,
+            };
+            entries.push(FlatEntry {
+                is_dir,
+                size: value(size_col)
+                    .and_then(|size| size.parse().ok())
+                    .filter(|_| !is_dir),
+                modified,
+                permissions: attr.starts_with(['-', 'd', 'l']).then(|| attr.to_string()),
+                ..FlatEntry::new(name)
+            });
+        }
+        entries
+    }
+
+    /// git ls-tree：每行为 "模式 类型 对象ID[ 大小]\t路径"（--long时有大小），
+    /// 对象ID写入校验和列；含特殊字符的路径由git加引号转义
+    fn parse_git_ls_tree(&self, input: &str) -> Vec<FlatEntry> {
+        let mut entries = Vec::new();
+        for line in input.lines() {
+            let Some((meta, path)) = line.split_once('\t') else {
+                continue;
+            };
+            let fields: Vec<&str> = meta.split_whitespace().collect();
+            let (Some(mode), Some(kind), Some(object)) =
+                (fields.first(), fields.get(1), fields.get(2))
+            else {
+                continue;
+            };
+            let Ok(mode) = u32::from_str_radix(mode, 8) else {
+                continue;
+            };
+            // tree为目录，commit为子模块
+            let is_dir = matches!(*kind, "tree" | "commit");
+            let type_char = match mode & 0o170000 {
+                0o040000 | 0o160000 => 'd',
+                0o120000 => 'l',
+                _ => '-',
+            };
+            entries.push(FlatEntry {
+                is_dir,
+                size: fields.get(3).and_then(|size| size.parse().ok()),
+                permissions: Some(mode_string(type_char, mode)),
+                hash: (*kind == "blob").then(|| FileHash {
+                    algorithm: HashAlgorithm::GitBlob,
+                    value: object.to_string(),
+                }),
+                ..FlatEntry::new(&unquote_git_path(path))
+            });
+        }
+        entries
+    }
+
+    /// 每行一个路径，"/"和"\\"均作为分隔符，以分隔符结尾的为目录（如fd的输出）
+    fn parse_paths(&self, input: &str) -> Vec<FlatEntry> {
+        input
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(FlatEntry::new)
+            .collect()
+    }
+
+    /// du：每行为 "大小<Tab>路径"（--time时中间多一列时间），目录的大小为其总占用；
+    /// 大小为字节数（-b）或可读单位（-h），输出中没有子项目的行按dirs_only决定是否为目录
+    fn parse_du(&self, input: &str, dirs_only: bool) -> Vec<FlatEntry> {
+        let parser = TreeParser::new();
+        let mut entries = Vec::new();
+        for line in input.lines() {
+            let fields: Vec<&str> = line.splitn(3, '\t').collect();
+            let (size, modified, path) = match fields[..] {
+                [size, path] => (size, None, path),
+                [size, time, path] => (size, parse_datetime(time), path),
+                _ => continue,
+            };
+            let Some(size) = parser.parse_size(size.trim()) else {
+                continue;
+            };
+            entries.push(FlatEntry {
+                is_dir: dirs_only || path.ends_with('/'),
+                size: Some(size),
+                modified,
+                ..FlatEntry::new(path)
+            });
+        }
+        entries
+    }
+
+    /// aws s3 ls：对象为 "日期 时间 大小 键"（--human-readable时大小为 "1.2 KiB"），
+    /// 未加--recursive时的公共前缀为 "PRE 目录/"；--summarize的汇总行跳过
+    fn parse_s3_ls(&self, input: &str) -> Vec<FlatEntry> {
+        const UNITS: [&str; 6] = ["Bytes", "KiB", "MiB", "GiB", "TiB", "PiB"];
+        let mut entries = Vec::new();
+        for line in input.lines() {
+            if let Some(prefix) = line.trim_start().strip_prefix("PRE ") {
+                entries.push(FlatEntry::new(prefix));
+                continue;
+            }
+            let Some((fields, rest)) = split_fields(line, 3) else {
+                continue;
+            };
+            let Some(modified) = parse_datetime(&format!("{} {}", fields[0], fields[1])) else {
+                continue;
+            };
+            let Ok(number) = fields[2].parse::<f64>() else {
+                continue;
+            };
+            let (size, key) = match split_fields(rest, 1) {
+                Some((unit, key)) if UNITS.contains(&unit[0]) => {
+                    let exponent = UNITS.iter().position(|u| *u == unit[0]).unwrap_or(0);
+                    (number * 1024f64.powi(exponent as i32), key)
+                }
+                _ => (number, rest),
+            };
+            let entry = FlatEntry::new(key);
+            entries.push(FlatEntry {
+                size: (!entry.is_dir).then_some(size.round() as u64),
+                modified: Some(modified),
+                ..entry
+            });
+        }
+        entries
+    }
+
+    /// tar -tvf：GNU tar为 "权限 所有者/组 大小 日期 时间 名称"，
+    /// bsdtar为ls风格的 "权限 链接数 所有者 组 大小 月 日 时间/年份 名称"
+    fn parse_tar(&self, input: &str) -> Vec<FlatEntry> {
+        let parser = TreeParser::new();
+        let mut entries = Vec::new();
+        for line in input.lines() {
+            let mut tokens = line.split_whitespace();
+            let Some(permissions) = tokens.next() else {
+                continue;
+            };
+            // 硬链接的类型字符为h，其余与ls相同；不是权限开头的行（如tar的提示信息）跳过
+            if !parser.is_permissions(&permissions.replacen('h', "-", 1)) {
+                continue;
+            }
+            let gnu = tokens.next().is_some_and(|token| token.contains('/'));
+            let Some((fields, name)) = split_fields(line, if gnu { 5 } else { 8 }) else {
+                continue;
+            };
+            let (owner, group, size, mut date) = if gnu {
+                let (owner, group) = fields[1].split_once('/').unwrap_or((fields[1], ""));
+                (owner, group, fields[2], fields[3..].to_vec())
+            } else {
+                (fields[2], fields[3], fields[4], fields[5..].to_vec())
+            };
+
+            let (name, link_target) = match permissions.chars().next() {
+                Some('l') => match name.split_once(" -> ") {
+                    Some((name, target)) => (name, Some(target.to_string())),
+                    None => (name, None),
+                },
+                Some('h') => (name.split(" link to ").next().unwrap_or(name), None),
+                _ => (name, None),
+            };
+            let is_dir = permissions.starts_with('d');
+            entries.push(FlatEntry {
+                is_dir,
+                size: size.parse().ok().filter(|_| !is_dir),
+                modified: parser.take_date(&mut date),
+                permissions: Some(permissions.to_string()),
+                owner: Some(owner.to_string()),
+                group: Some(group.to_string()).filter(|group| !group.is_empty()),
+                link_target,
+                ..FlatEntry::new(name)
+            });
+        }
+        entries
+    }
+}
+
+/// 取行首的count个空白分隔的字段，其余部分（去掉前导空白）作为名称
+pub(crate) fn split_fields(line: &str, count: usize) -> Option<(Vec<&str>, &str)> {
+    let mut fields = Vec::with_capacity(count);
+    let mut rest = line.trim_start();
+    for _ in 0..count {
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    (!rest.is_empty()).then_some((fields, rest))
+}
+
+/// 去掉git为含特殊字符的路径加的引号及C风格转义（如 "\344\270\255.txt"）
+fn unquote_git_path(path: &str) -> String {
+    let Some(quoted) = path
+        .strip_prefix('"')
+        .and_then(|path| path.strip_suffix('"'))
+    else {
+        return path.to_string();
+    };
+    let mut bytes = Vec::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('t') => bytes.push(b'\t'),
+            Some('n') => bytes.push(b'\n'),
+            Some(digit @ '0'..='7') => {
+                // 三位八进制表示一个字节
+                let octal: String = std::iter::once(digit)
+                    .chain(chars.by_ref().take(2))
+                    .collect();
+                bytes.push(u8::from_str_radix(&octal, 8).unwrap_or(b'?'));
+            }
+            Some(other) => bytes.push(other as u8),
+            None => {}
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// 解析列表中常见的日期时间格式
+pub(crate) fn parse_datetime(text: &str) -> Option<NaiveDateTime> {
+    const FORMATS: [&str; 4] = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%d-%m-%y %H:%M",
+        "%m-%d-%Y %H:%M",
+    ];
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_7zip() {
+        let seven_zip = "\
+7-Zip [64] 16.02 : Copyright (c) 1999-2016 Igor Pavlov : 2016-05-21
+
+Listing archive: release.7z
+
+--
+Path = release.7z
+Type = 7z
+
+   Date      Time    Attr         Size   Compressed  Name
+------------------- ----- ------------ ------------  ------------------------
+2024-01-15 12:34:56 D....            0            0  src
+2024-01-15 12:34:56 ....A         1234          567  src\\main file.rs
+2024-01-15 12:30:00 ....A          100               README.md
+------------------- ----- ------------ ------------  ------------------------
+2024-01-15 12:34:56               1334          567  2 files, 1 folders
+";
+        let items = ListingParser::new(ListingFormat::SevenZip)
+            .parse(seven_zip, false)
+            .unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file, item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("src", false, None),
+                ("src/main file.rs", true, Some(1234)),
+                ("README.md", true, Some(100)),
+                ("📊 统计: 1 directories, 2 files", false, None),
+            ]
+        );
+        assert_eq!(
+            items[2].modified.unwrap().to_string(),
+            "2024-01-15 12:30:00"
+        );
+
+        let technical = "Listing archive: a.7z\n\n--\nPath = a.7z\nType = 7z\n\n----------\nPath = docs\nFolder = +\nSize = 0\nModified = 2024-01-15 12:34:56.1234567\n\nPath = docs/a.md\nFolder = -\nSize = 42\nModified = 2024-01-15 12:34:56.1234567\n";
+        let items = ListingParser::new(ListingFormat::SevenZip)
+            .parse(technical, false)
+            .unwrap();
+        assert_eq!(items[1].full_path, "docs/a.md");
+        assert_eq!(items[1].size, Some(42));
+    }
+
+    #[test]
+    fn test_parse_unrar() {
+        let unrar = "\
+UNRAR 6.24 freeware      Copyright (c) 1993-2023 Alexander Roshal
+
+Archive: release.rar
+Details: RAR 5
+
+ Attributes      Size     Date    Time   Name
+----------- ---------  ---------- -----  ----
+ -rw-r--r--      1234  2024-01-15 12:34  src/main file.rs
+ drwxr-xr-x         0  2024-01-15 12:34  src
+    ..A....       100  2024-01-15 12:30  README.md
+----------- ---------  ---------- -----  ----
+                 1334                    3
+";
+        let items = ListingParser::new(ListingFormat::Unrar)
+            .parse(unrar, false)
+            .unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file, item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("src", false, None),
+                ("src/main file.rs", true, Some(1234)),
+                ("README.md", true, Some(100)),
+                ("📊 统计: 1 directories, 2 files", false, None),
+            ]
+        );
+        assert_eq!(items[1].permissions.as_deref(), Some("-rw-r--r--"));
+    }
+
+    #[test]
+    fn test_parse_unzip() {
+        let unzip = "\
+Archive:  release.zip
+  Length      Date    Time    Name
+---------  ---------- -----   ----
+        0  2024-01-15 12:34   src/
+     1234  01-15-2024 12:34   src/main.rs
+---------                     -------
+     1234                     2 files
+";
+        let items = ListingParser::new(ListingFormat::Unzip)
+            .parse(unzip, false)
+            .unwrap();
+        assert_eq!(items[0].full_path, "src");
+        assert!(!items[0].is_file);
+        assert_eq!(items[1].size, Some(1234));
+        assert_eq!(
+            items[1].modified.unwrap().to_string(),
+            "2024-01-15 12:34:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_tar() {
+        let tar = "\
+drwxr-xr-x alice/staff       0 2024-01-15 12:34 src/
+-rw-r--r-- alice/staff    1234 2024-01-15 12:34 src/main file.rs
+lrwxrwxrwx alice/staff       0 2024-01-15 12:34 src/latest -> main file.rs
+hrw-r--r-- alice/staff       0 2024-01-15 12:34 src/copy.rs link to src/main file.rs
+-rw-r--r--  0 bob    wheel     100 Jan 15  2023 README.md
+";
+        let items = ListingParser::new(ListingFormat::Tar)
+            .parse(tar, false)
+            .unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.size, item.owner.as_deref()))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("src", None, Some("alice")),
+                ("src/main file.rs", Some(1234), Some("alice")),
+                ("src/latest", Some(0), Some("alice")),
+                ("src/copy.rs", Some(0), Some("alice")),
+                ("README.md", Some(100), Some("bob")),
+                ("📊 统计: 1 directories, 4 files", None, None),
+            ]
+        );
+        assert_eq!(items[2].link_target.as_deref(), Some("main file.rs"));
+        assert_eq!(
+            items[4].modified.unwrap().to_string(),
+            "2023-01-15 00:00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_git_ls_tree() {
+        let ls_tree = "\
+100644 blob ce013625030ba8dba906f756967f9e9ca394464a       6\tREADME.md
+100755 blob 2e65efe2a145dda7ee51d1741299f848e5bf752e      12\tbin/run.sh
+160000 commit 4b825dc642cb6eb9a060e54bf8d69288fbee4904       -\tvendor/lib
+100644 blob e69de29bb2d1d6434b8b29ae775ad8c2e48c5391       0\t\"docs/\\344\\270\\255\\346\\226\\207.md\"
+";
+        let items = ListingParser::new(ListingFormat::GitLsTree)
+            .parse(ls_tree, false)
+            .unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file, item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("README.md", true, Some(6)),
+                ("bin", false, None),
+                ("bin/run.sh", true, Some(12)),
+                ("vendor", false, None),
+                ("vendor/lib", false, None),
+                ("docs", false, None),
+                ("docs/中文.md", true, Some(0)),
+                ("📊 统计: 4 directories, 3 files", false, None),
+            ]
+        );
+        assert_eq!(
+            items[0].hash.as_ref().unwrap().value,
+            "ce013625030ba8dba906f756967f9e9ca394464a"
+        );
+        assert_eq!(items[2].permissions.as_deref(), Some("-rwxr-xr-x"));
+    }
+
+    #[test]
+    fn test_parse_paths() {
+        let paths = ".\n./src\n./src/main.rs\r\ndocs\\guide.md\n./.git/config\n";
+        let items = ListingParser::new(ListingFormat::Paths)
+            .parse(paths, false)
+            .unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("src", false),
+                ("src/main.rs", true),
+                ("docs", false),
+                ("docs/guide.md", true),
+                ("📊 统计: 2 directories, 2 files", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_du() {
+        let du = "4096\t./src/main.rs\n8192\t./src\n120\t./README.md\nsize\tjunk\n12408\t.\n";
+        let items = ListingParser::new(ListingFormat::Du)
+            .parse(du, false)
+            .unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file, item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("src", false, Some(8192)),
+                ("src/main.rs", true, Some(4096)),
+                ("README.md", true, Some(120)),
+                ("📊 统计: 1 directories, 2 files", false, None),
+            ]
+        );
+
+        let du_dirs = "1.5M\t2024-01-15 12:34\t/var/log/nginx\n4.0K\t2024-01-15 12:30\t/var/log\n";
+        let items = ListingParser::new(ListingFormat::DuDirs)
+            .parse(du_dirs, false)
+            .unwrap();
+        assert_eq!(items[2].full_path, "var/log/nginx");
+        assert!(!items[2].is_file);
+        assert_eq!(items[2].size, Some(1572864));
+        assert_eq!(
+            items[1].modified.unwrap().to_string(),
+            "2024-01-15 12:30:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_s3_ls() {
+        let s3_ls = "\
+                           PRE archive/
+2024-01-15 12:34:56          0 logs/
+2024-01-15 12:34:56       1234 logs/2024/app 1.log
+2024-01-16 08:00:00    1.5 KiB logs/2024/b.log
+
+Total Objects: 3
+   Total Size: 2770
+";
+        let items = ListingParser::new(ListingFormat::S3Ls)
+            .parse(s3_ls, false)
+            .unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file, item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("archive", false, None),
+                ("logs", false, None),
+                ("logs/2024", false, None),
+                ("logs/2024/app 1.log", true, Some(1234)),
+                ("logs/2024/b.log", true, Some(1536)),
+                ("📊 统计: 3 directories, 2 files", false, None),
+            ]
+        );
+        assert_eq!(
+            items[3].modified.unwrap().to_string(),
+            "2024-01-15 12:34:56"
+        );
+    }
+}
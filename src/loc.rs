@@ -0,0 +1,112 @@
+//! 源文件行数统计及语言识别（--loc）
+
+use rayon::prelude::*;
+use std::fs;
+use std::path::Path;
+
+use crate::parser::{EntryKind, TreeItem};
+
+/// 判断二进制文件时检查的开头字节数
+const BINARY_CHECK_LEN: usize = 8 * 1024;
+
+/// 扩展名（或小写的完整文件名）及对应的编程语言
+const LANGUAGES: &[(&[&str], &str)] = &[
+    (&["rs"], "Rust"),
+    (&["py", "pyw"], "Python"),
+    (&["js", "mjs", "cjs", "jsx"], "JavaScript"),
+    (&["ts", "mts", "tsx"], "TypeScript"),
+    (&["go"], "Go"),
+    (&["java"], "Java"),
+    (&["kt", "kts"], "Kotlin"),
+    (&["scala"], "Scala"),
+    (&["c", "h"], "C"),
+    (&["cpp", "cc", "cxx", "hpp", "hh", "hxx"], "C++"),
+    (&["cs"], "C#"),
+    (&["m", "mm"], "Objective-C"),
+    (&["swift"], "Swift"),
+    (&["rb"], "Ruby"),
+    (&["php"], "PHP"),
+    (&["pl", "pm"], "Perl"),
+    (&["lua"], "Lua"),
+    (&["r"], "R"),
+    (&["dart"], "Dart"),
+    (&["zig"], "Zig"),
+    (&["hs"], "Haskell"),
+    (&["ex", "exs"], "Elixir"),
+    (&["erl"], "Erlang"),
+    (&["clj", "cljs"], "Clojure"),
+    (&["sh", "bash", "zsh"], "Shell"),
+    (&["ps1"], "PowerShell"),
+    (&["bat", "cmd"], "Batch"),
+    (&["sql"], "SQL"),
+    (&["html", "htm"], "HTML"),
+    (&["css", "scss", "less"], "CSS"),
+    (&["vue"], "Vue"),
+    (&["svelte"], "Svelte"),
+    (&["md", "markdown"], "Markdown"),
+    (&["json"], "JSON"),
+    (&["yaml", "yml"], "YAML"),
+    (&["toml"], "TOML"),
+    (&["xml"], "XML"),
+    (&["proto"], "Protocol Buffers"),
+    (&["makefile"], "Makefile"),
+    (&["dockerfile"], "Dockerfile"),
+    (&["cmakelists.txt", "cmake"], "CMake"),
+];
+
+/// 文件的编程语言（按扩展名，无扩展名时按完整文件名，如Makefile）
+pub fn language(name: &str) -> Option<&'static str> {
+    let lower = name.to_lowercase();
+    let extension = match lower.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => Some(ext),
+        _ => None,
+    };
+    let keys = [extension, Some(lower.as_str())];
+    let language = keys.into_iter().flatten().find_map(|key| {
+        LANGUAGES
+            .iter()
+            .find(|(names, _)| names.contains(&key))
+            .map(|(_, language)| *language)
+    });
+    language
+}
+
+/// 文本内容的行数（最后一行没有换行符时也计入），开头含NUL字节的二进制内容返回None
+pub fn count_lines(content: &[u8]) -> Option<usize> {
+    if content[..content.len().min(BINARY_CHECK_LEN)].contains(&0) {
+        return None;
+    }
+    let newlines = content.iter().filter(|&&byte| byte == b'\n').count();
+    let unterminated = content.last().is_some_and(|&byte| byte != b'\n');
+    Some(newlines + usize::from(unterminated))
+}
+
+/// 并行统计root下各文本文件的行数，写入item.lines（二进制及无法读取的文件跳过）
+pub fn count_items(root: &Path, items: &mut [TreeItem]) {
+    items
+        .par_iter_mut()
+        .filter(|item| item.kind == EntryKind::File && !item.name.starts_with("📊"))
+        .for_each(|item| {
+            item.lines = fs::read(root.join(&item.full_path))
+                .ok()
+                .and_then(|content| count_lines(&content));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_lines() {
+        assert_eq!(count_lines(b""), Some(0));
+        assert_eq!(count_lines(b"fn main() {}\n"), Some(1));
+        assert_eq!(count_lines(b"a\nb\n\nc"), Some(4));
+        assert_eq!(count_lines(b"\x89PNG\x00\x00"), None);
+
+        assert_eq!(language("src/MAIN.RS"), Some("Rust"));
+        assert_eq!(language("Makefile"), Some("Makefile"));
+        assert_eq!(language("CMakeLists.txt"), Some("CMake"));
+        assert_eq!(language("notes.txt"), None);
+    }
+}
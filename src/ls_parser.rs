@@ -0,0 +1,168 @@
+//! `ls -lR` 递归长格式输出解析
+
+use crate::error::{Error, Result};
+use crate::flat::{build_tree, FlatEntry};
+use crate::listing::split_fields;
+use crate::parser::{TreeItem, TreeParser};
+
+/// ls -lR输出解析器
+///
+/// 输出由若干段组成，每段以 "目录:" 开头、"total N" 之后为该目录下的长格式条目，
+/// 第一段的目录作为根目录，其余目录按相对路径还原层级。
+#[derive(Debug, Default)]
+pub struct LsParser;
+
+impl LsParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 判断输入是否为ls -lR输出（"目录:" 的下一行为 "total N"）
+    pub fn detect(input: &str) -> bool {
+        let lines: Vec<&str> = input.lines().take(50).collect();
+        lines.windows(2).any(|pair| {
+            pair[0].trim_end().ends_with(':') && pair[1].trim_start().starts_with("total ")
+        })
+    }
+
+    /// 解析ls -lR输出，返回与TreeParser::parse相同结构的项目列表
+    pub fn parse(&self, input: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let parser = TreeParser::new();
+        let mut root: Option<String> = None;
+        let mut directory = String::new();
+        let mut entries = Vec::new();
+
+        for line in input.lines() {
+            let line = line.trim_end();
+            let Some(permissions) = line.split_whitespace().next() else {
+                continue;
+            };
+            if !parser.is_permissions(permissions) {
+                // 目录标题行：第一个为根目录，之后的相对于根目录
+                if let Some(header) = line.strip_suffix(':') {
+                    let header = header.trim_end_matches('/');
+                    directory = match &root {
+                        Some(root) => header
+                            .strip_prefix(root.as_str())
+                            .unwrap_or(header)
+                            .trim_start_matches('/')
+                            .to_string(),
+                        None => {
+                            root = Some(header.to_string());
+                            String::new()
+                        }
+                    };
+                }
+                continue;
+            }
+            if let Some(entry) = self.parse_entry(&parser, line, &directory) {
+                entries.push(entry);
+            }
+        }
+        if entries.is_empty() {
+            return Err(Error::parse(None, "没有找到ls -lR的长格式条目"));
+        }
+
+        let mut items = build_tree(entries, include_hidden);
+        if let Some(root) = root.filter(|root| !root.is_empty() && root != ".") {
+            for item in items.iter_mut().filter(|item| !item.name.starts_with("📊")) {
+                item.root = Some(root.clone());
+            }
+        }
+        Ok(items)
+    }
+
+    /// 解析一行长格式条目："权限 链接数 所有者 组 大小 日期 名称"，
+    /// 日期为 "Jan 15 12:34"/"Jan 15  2023"，或--time-style=long-iso/full-iso的ISO格式
+    fn parse_entry(&self, parser: &TreeParser, line: &str, directory: &str) -> Option<FlatEntry> {
+        // 设备文件的大小列为 "主设备号, 次设备号"
+        let device = split_fields(line, 5)?.0[4].ends_with(',');
+        let (fields, rest) = split_fields(line, if device { 6 } else { 5 })?;
+        let date_tokens = match rest.split_whitespace().collect::<Vec<_>>()[..] {
+            [date, _, zone, ..]
+                if date.contains('-') && zone.starts_with(['+', '-']) && zone.len() == 5 =>
+            {
+                3
+            }
+            [date, ..] if date.contains('-') => 2,
+            _ => 3,
+        };
+        let (date, name) = split_fields(rest, date_tokens)?;
+        // full-iso的时间带有纳秒部分
+        let mut date: Vec<&str> = date
+            .into_iter()
+            .map(|token| token.split('.').next().unwrap_or(token))
+            .collect();
+
+        let (name, link_target) = match name.split_once(" -> ") {
+            Some((name, target)) if fields[0].starts_with('l') => (name, Some(target.to_string())),
+            _ => (name, None),
+        };
+        if name == "." || name == ".." {
+            return None;
+        }
+        let path = if directory.is_empty() {
+            name.to_string()
+        } else {
+            format!("{directory}/{name}")
+        };
+        let is_dir = fields[0].starts_with('d');
+        Some(FlatEntry {
+            is_dir,
+            size: fields[4].parse().ok().filter(|_| !is_dir && !device),
+            modified: parser.take_date(&mut date),
+            permissions: Some(fields[0].to_string()),
+            owner: Some(fields[2].to_string()),
+            group: Some(fields[3].to_string()),
+            link_target,
+            ..FlatEntry::new(&path)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ls_lr() {
+        let input = "\
+/srv/app:
+total 12
+drwxr-xr-x 2 alice staff 4096 Jan 15  2023 conf
+-rw-r--r-- 1 alice staff  120 Jan 15  2023 README.md
+lrwxrwxrwx 1 alice staff    9 Jan 15  2023 current -> README.md
+
+/srv/app/conf:
+total 4
+drwxr-xr-x 2 alice staff 4096 2024-01-15 12:34 .
+-rw-r----- 1 root  adm   1234 2024-01-15 12:34:56.123456789 +0800 app.conf
+crw-rw---- 1 root  tty  4, 0 2024-01-15 12:34 tty0
+";
+        assert!(LsParser::detect(input));
+        let items = LsParser::new().parse(input, false).unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file, item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("conf", false, None),
+                ("conf/app.conf", true, Some(1234)),
+                ("conf/tty0", true, None),
+                ("README.md", true, Some(120)),
+                ("current", true, Some(9)),
+                ("📊 统计: 1 directories, 4 files", false, None),
+            ]
+        );
+        assert_eq!(items[0].root.as_deref(), Some("/srv/app"));
+        assert_eq!(items[1].owner.as_deref(), Some("root"));
+        assert_eq!(
+            items[1].modified.unwrap().to_string(),
+            "2024-01-15 12:34:56"
+        );
+        assert_eq!(items[4].link_target.as_deref(), Some("README.md"));
+        assert!(!LsParser::detect(".\n└── a.txt\n\n0 directories, 1 file\n"));
+    }
+}
@@ -1,662 +1,1858 @@
 use anyhow::{Context, Result};
-use clap::{Arg, Command};
-use rust_xlsxwriter::{Format, Workbook, Worksheet};
+use clap::parser::ValueSource;
+use clap::{Arg, ArgGroup, ArgMatches, Command};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::ffi::OsString;
+use std::fmt;
 use std::fs;
-use std::io::{self, Read};
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+use tree_to_excel::{
+    count_lines, create_structure, detect_dialect, detect_format, detect_secret, diff_trees,
+    find_duplicates, hash_items, include_root, input_dialect, sniff_items, split_by_top,
+    write_script, write_table, ArchiveReader, ChartKind, Config, ConfigValue, CsvGenerator,
+    DirWalker, DockerImageReader, DuplicateGroup, ExcelGenerator, HashAlgorithm, HtmlGenerator,
+    InputEncoding, JsonGenerator, JsonTreeParser, Language, Layout, MarkdownGenerator, OdsWriter,
+    Shell, SizeFormat, SkippedLine, SortKey, SummaryGenerator, Theme, TreeFilter, TreeItem,
+    TreeParser, TreeSorter, TreeTextGenerator, VerifyReport, XlsxTreeParser,
+};
+
+/// 进度输出间隔（行数）
+const PROGRESS_INTERVAL: usize = 100_000;
+
+/// --watch合并变化事件的时间窗口
+const WATCH_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// 输出写到标准输出（-o -）时，提示信息改为写到标准错误
+static STDOUT_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// -q：只输出错误
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// --report json：结束时输出运行摘要
+static REPORT_JSON: AtomicBool = AtomicBool::new(false);
+
+/// --strict时输入中有无法解析的行的退出状态码（其他错误为1，参数错误为2）
+const EXIT_STRICT: i32 = 3;
+
+/// --strict时最多列出的无法解析的行数
+const STRICT_SHOWN_LINES: usize = 20;
+
+/// 本次运行的摘要
+static REPORT: Mutex<RunReport> = Mutex::new(RunReport {
+    success: false,
+    error: None,
+    output: None,
+    items: 0,
+    rows_written: 0,
+    skipped_lines: 0,
+    errors: 0,
+    warnings: Vec::new(),
+});
+
+static LOGGER: Logger = Logger;
+
+/// 输出提示信息（-q时不输出）
+macro_rules! status {
+    ($($arg:tt)*) => {
+        log::info!($($arg)*)
+    };
+}
 
-/// 文件/目录项
-#[derive(Debug, Clone)]
-struct TreeItem {
-    name: String,
-    level: usize,
-    is_file: bool,
-    full_path: String,
+/// 输出进度（-q时不输出）
+macro_rules! progress {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            eprint!($($arg)*);
+        }
+    };
 }
 
-/// Excel行数据  
+/// --strict时输入中有无法解析的行
 #[derive(Debug)]
-struct ExcelRow {
-    levels: Vec<String>, // 每个层级的名称，如["src", "bin", "file.rs"]
-    full_path: String,   // 完整路径
-    max_level: usize,    // 最大层级深度
-    is_file: bool,
+struct StrictError(usize);
+
+impl fmt::Display for StrictError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} 行无法按tree格式解析（--strict）", self.0)
+    }
 }
 
-/// Tree输出解析器
-struct TreeParser;
+impl std::error::Error for StrictError {}
 
-impl TreeParser {
-    fn new() -> Self {
-        Self
+/// verify发现清单与目录不一致：(缺失数, 未登记数, 大小不符数)
+#[derive(Debug)]
+struct VerifyError(usize, usize, usize);
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "校验失败：缺失 {}，未登记 {}，大小不符 {}",
+            self.0, self.1, self.2
+        )
     }
+}
 
-    /// 解析tree输出，返回扁平化的项目列表
-    fn parse(&self, input: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
-        let lines: Vec<&str> = input.lines().collect();
-        let mut items = Vec::new();
-        let mut path_stack: Vec<String> = Vec::new();
-        let mut stats_line = None;
-        let mut hidden_levels: Vec<usize> = Vec::new(); // 记录被过滤的隐藏目录的层级
+impl std::error::Error for VerifyError {}
+
+/// 运行摘要（--report json），供CI读取
+#[derive(Debug, Serialize)]
+struct RunReport {
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    items: usize,          // 文件及目录数（不含统计行）
+    rows_written: usize,   // 写入输出的行数（含统计行，不含表头）
+    skipped_lines: usize,  // 无法解析而跳过的输入行数
+    errors: usize,         // 带有错误（如无法读取的目录）的项目数
+    warnings: Vec<String>, // 运行中的警告（-q时也会记录）
+}
 
-        for line in lines {
-            if line.trim().is_empty() {
-                continue;
-            }
+/// 命令行的日志输出：info为普通提示（写到标准输出，-o -或--report时写到标准错误，
+/// 使标准输出只有输出文件或运行摘要），其余级别写到标准错误；只输出本程序的日志，警告同时记入运行摘要
+struct Logger;
 
-            // 检查统计行
-            if line.contains("directories") && line.contains("files") {
-                stats_line = Some(line.trim().to_string());
-                continue;
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target().starts_with("tree_to_excel")
+            && (metadata.level() == log::Level::Error || !QUIET.load(Ordering::Relaxed))
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !record.target().starts_with("tree_to_excel") {
+            return;
+        }
+        if record.level() == log::Level::Warn {
+            if let Ok(mut report) = REPORT.lock() {
+                report.warnings.push(record.args().to_string());
             }
+        }
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        match record.level() {
+            log::Level::Info
+                if !STDOUT_OUTPUT.load(Ordering::Relaxed)
+                    && !REPORT_JSON.load(Ordering::Relaxed) =>
+            {
+                println!("{}", record.args())
+            }
+            log::Level::Info => eprintln!("{}", record.args()),
+            log::Level::Warn => eprintln!("⚠️ {}", record.args()),
+            log::Level::Error => eprintln!("❌ {}", record.args()),
+            log::Level::Debug | log::Level::Trace => eprintln!("🔍 {}", record.args()),
+        }
+    }
 
-            // 解析层级和名称
-            if let Some((level, name)) = self.parse_line(line) {
-                // 清理过期的隐藏层级记录（当前层级小于等于隐藏层级时）
-                hidden_levels.retain(|&hidden_level| hidden_level < level);
-
-                // 检查是否在隐藏目录内
-                let in_hidden_dir = !hidden_levels.is_empty();
-
-                // 过滤隐藏目录/文件（以.开头的项目，如.git）
-                if !include_hidden && (name.starts_with('.') || in_hidden_dir) {
-                    if name.starts_with('.') {
-                        // 记录这个隐藏目录的层级，用于过滤其子项目
-                        hidden_levels.push(level);
-                    }
-                    continue;
-                }
+    fn flush(&self) {}
+}
 
-                // 调整路径栈到当前层级
-                path_stack.truncate(level.saturating_sub(1));
+/// 按-q/-v设置日志级别（-q时仍记录警告以写入运行摘要）
+fn set_verbosity(matches: &ArgMatches) {
+    let quiet = matches.get_flag("quiet");
+    QUIET.store(quiet, Ordering::Relaxed);
+    log::set_max_level(match matches.get_count("verbose") {
+        _ if quiet => log::LevelFilter::Warn,
+        0 => log::LevelFilter::Info,
+        1 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    });
+}
 
-                // 构建完整路径
-                let full_path = if path_stack.is_empty() {
-                    name.clone()
-                } else {
-                    format!("{}/{}", path_stack.join("/"), name)
-                };
+/// 输出运行摘要：写到标准输出，-o -时写到标准错误
+fn print_report(result: &Result<()>) {
+    let Ok(mut report) = REPORT.lock() else {
+        return;
+    };
+    report.success = result.is_ok();
+    report.error = result.as_ref().err().map(|err| format!("{err:#}"));
+    let json = serde_json::to_string_pretty(&*report).unwrap_or_default();
+    if STDOUT_OUTPUT.load(Ordering::Relaxed) {
+        eprintln!("{json}");
+    } else {
+        println!("{json}");
+    }
+}
 
-                // 添加到路径栈
-                path_stack.push(name.clone());
+/// 文本进度条，如 "██████░░░░"
+fn progress_bar(done: usize, total: usize) -> String {
+    const WIDTH: usize = 30;
+    let filled = (done * WIDTH).checked_div(total).unwrap_or(WIDTH);
+    format!("{}{}", "█".repeat(filled), "░".repeat(WIDTH - filled))
+}
 
-                // 判断是否为文件
-                let is_file = self.is_file(&name);
+fn main() -> Result<()> {
+    log::set_logger(&LOGGER).context("无法初始化日志")?;
+    log::set_max_level(log::LevelFilter::Info);
+    let result = run();
+    if REPORT_JSON.load(Ordering::Relaxed) {
+        print_report(&result);
+    }
+    // --strict的解析错误以单独的状态码退出，便于与其他错误区分
+    if let Some(err) = result
+        .as_ref()
+        .err()
+        .filter(|err| err.downcast_ref::<StrictError>().is_some())
+    {
+        log::error!("{err:#}");
+        std::process::exit(EXIT_STRICT);
+    }
+    // verify不一致时已列出差异，只输出汇总并以状态码1退出
+    if let Some(err) = result
+        .as_ref()
+        .err()
+        .filter(|err| err.downcast_ref::<VerifyError>().is_some())
+    {
+        log::error!("{err:#}");
+        std::process::exit(1);
+    }
+    result
+}
 
-                items.push(TreeItem {
-                    name: name.clone(),
-                    level,
-                    is_file,
-                    full_path,
-                });
-            }
+fn run() -> Result<()> {
+    let command = Command::new("tree-to-excel")
+        .about("将tree命令输出转换为Excel表格，支持合并单元格层级展示")
+        .version("1.0")
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .action(clap::ArgAction::Append)
+                .num_args(1..)
+                .help("输入文件路径（tree命令输出），可指定多个，每个文件生成一个工作表"),
+        )
+        .arg(
+            Arg::new("path")
+                .short('p')
+                .long("path")
+                .value_name("DIR")
+                .conflicts_with("input")
+                .help("直接遍历目录生成表格（无需tree命令）"),
+        )
+        .arg(
+            Arg::new("archive")
+                .long("archive")
+                .value_name("FILE")
+                .conflicts_with_all(["input", "path"])
+                .help("读取压缩包（.zip、.tar、.tar.gz/.tgz）的条目列表生成表格，无需解压，大小、修改时间及权限来自压缩包元数据"),
+        )
+        .arg(
+            Arg::new("s3")
+                .long("s3")
+                .value_name("URL")
+                .conflicts_with_all(["input", "path", "archive"])
+                .help("列出S3存储桶（s3://bucket/prefix）中的对象生成表格，键前缀作为目录，含大小、修改时间及存储类别（需启用s3特性编译）"),
+        )
+        .arg(
+            Arg::new("ssh")
+                .long("ssh")
+                .value_name("USER@HOST:PATH")
+                .conflicts_with_all(["input", "path", "archive", "s3"])
+                .help("通过SFTP递归列出远程目录生成表格，远程主机无需安装tree（需启用ssh特性编译）；主机密钥按~/.ssh/known_hosts校验，认证依次尝试ssh-agent、默认密钥及SSHPASS环境变量"),
+        )
+        .arg(
+            Arg::new("docker_image")
+                .long("docker-image")
+                .value_name("IMAGE")
+                .conflicts_with_all(["input", "path", "archive", "s3", "ssh"])
+                .help("列出Docker镜像的文件系统（如ubuntu:22.04，本地没有时先拉取；也可为docker save导出的tar文件），逐层叠加并标出每个文件来自哪一层及生成该层的指令"),
+        )
+        .arg(
+            Arg::new("ssh_port")
+                .long("ssh-port")
+                .value_name("PORT")
+                .value_parser(clap::value_parser!(u16))
+                .default_value("22")
+                .help("--ssh使用的端口"),
+        )
+        .arg(
+            Arg::new("webdav")
+                .long("webdav")
+                .value_name("URL")
+                .conflicts_with_all(["input", "path", "archive", "s3", "ssh", "docker_image"])
+                .help("通过PROPFIND递归列出WebDAV目录（如Nextcloud、群晖的共享）生成表格，含大小及修改时间（需启用webdav特性编译）；凭据取自地址中的user:password@或WEBDAV_USER/WEBDAV_PASSWORD环境变量"),
+        )
+        .arg(
+            Arg::new("use_gitignore")
+                .long("use-gitignore")
+                .action(clap::ArgAction::SetTrue)
+                .requires("path")
+                .help("遍历目录时跳过.gitignore、.git/info/exclude及全局忽略文件匹配的项目（如target/、node_modules/、*.o）"),
+        )
+        .arg(
+            Arg::new("follow_symlinks")
+                .long("follow-symlinks")
+                .action(clap::ArgAction::SetTrue)
+                .requires("path")
+                .help("遍历目录时进入符号链接指向的目录，指向上级目录的循环链接在备注中标出"),
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .action(clap::ArgAction::SetTrue)
+                .requires("path")
+                .help("生成后继续监视目录，有变化时（合并1秒内的事件）重新生成输出文件，Ctrl+C结束；适合整理目录时让共享盘上的表格保持最新"),
+        )
+        .arg(
+            Arg::new("hash")
+                .long("hash")
+                .value_name("ALGORITHM")
+                .value_parser(["md5", "sha256", "git"])
+                .requires("path")
+                .help("遍历目录时并行计算每个文件的校验和（md5、sha256或git对象ID），写入校验和列，可用作传输清单"),
+        )
+        .arg(
+            Arg::new("detect_type")
+                .long("detect-type")
+                .action(clap::ArgAction::SetTrue)
+                .help("增加\"文件类型\"列，如Rust源文件、PNG图片、ZIP压缩包（遍历目录时按文件内容识别，否则按扩展名）"),
+        )
+        .arg(
+            Arg::new("loc")
+                .long("loc")
+                .action(clap::ArgAction::SetTrue)
+                .requires("path")
+                .help("遍历目录时统计每个文本文件的行数，并按扩展名识别编程语言，写入\"行数\"和\"语言\"列"),
+        )
+        .arg(
+            Arg::new("find_duplicates")
+                .long("find-duplicates")
+                .action(clap::ArgAction::SetTrue)
+                .requires("path")
+                .help("遍历目录时按大小和校验和查找内容相同的文件，标记重复组并增加\"重复文件\"工作表"),
+        )
+        .arg(
+            Arg::new("git")
+                .long("git")
+                .action(clap::ArgAction::SetTrue)
+                .requires("path")
+                .help("遍历git仓库时增加Git状态（已跟踪、已修改、未跟踪、已忽略）、最后提交时间及作者列"),
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .global(true)
+                .value_name("FORMAT")
+                .value_parser([
+                    "auto",
+                    "tree",
+                    "json",
+                    "xml",
+                    "7z",
+                    "rar",
+                    "unzip",
+                    "tar",
+                    "git-ls-tree",
+                    "paths",
+                    "du",
+                    "du-dirs",
+                    "s3-ls",
+                    "ncdu",
+                    "powershell",
+                ])
+                .default_value("auto")
+                .help("输入格式，默认按开头的内容自动识别tree文本、json、xml、ncdu或paths：tree（文本输出）、json（tree -J输出）、xml（tree -X输出）、7z（7z l或7z l -slt的列表输出）、rar（unrar l/v的列表输出）、unzip（unzip -l/-v的列表输出）、tar（tar -tvf的列表输出）、git-ls-tree（git ls-tree -r --long的输出，含对象ID）、paths（每行一个路径，如find、fd、git ls-files的输出）、du（du -ab或du -ah的输出，大小附在目录行上）、du-dirs（不带-a的du输出，如du -h --max-depth=2，全部按目录处理）、s3-ls（aws s3 ls --recursive的输出，按键还原层级）、ncdu（ncdu -o导出的JSON扫描结果）或powershell（Get-ChildItem -Recurse的表格、Format-List或Export-Csv输出）"),
+        )
+        .arg(
+            Arg::new("dialect")
+                .long("dialect")
+                .global(true)
+                .value_name("DIALECT")
+                .value_parser(["auto", "unix", "ascii", "windows", "eza", "lsd", "ls"])
+                .default_value("auto")
+                .help("tree文本的方言：unix（tree命令）、ascii（tree --charset=ascii）、windows（tree /F）、eza/lsd（--tree输出）或ls（ls -lR递归长格式输出），默认自动识别"),
+        )
+        .arg(
+            Arg::new("encoding")
+                .long("encoding")
+                .global(true)
+                .value_name("ENCODING")
+                .default_value("auto")
+                .help("输入文本的编码：auto（按BOM及内容识别UTF-8、UTF-16、GBK、CP437或Latin-1）、utf-8、gbk、cp437、latin1、utf-16le等，用于重定向到文件的Windows tree输出"),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .global(true)
+                .value_name("FILE")
+                .help("输出文件路径，默认为tree_output.<输出格式扩展名>；\"-\"表示写到标准输出")
+                .default_value("tree_output.xlsx"),
+        )
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_name("FORMAT")
+                .value_parser(["xlsx", "ods", "csv", "markdown", "html", "json", "ndjson"])
+                .default_value("xlsx")
+                .help("输出格式：xlsx（Excel）、ods（OpenDocument电子表格，保留合并单元格）、csv（层级列+完整路径）、markdown（Markdown表格）、html（rowspan合并的HTML表格）、json（与tree -J兼容的嵌套结构）或ndjson（每行一条记录）"),
+        )
+        .arg(
+            Arg::new("lang")
+                .long("lang")
+                .global(true)
+                .value_name("LANG")
+                .value_parser(["zh", "en"])
+                .help("表头、统计标签等输出内容的语言：zh（中文）或en（英文），默认按系统区域设置"),
+        )
+        .arg(
+            Arg::new("delimiter")
+                .long("delimiter")
+                .value_name("CHAR")
+                .value_parser(clap::value_parser!(char))
+                .default_value(",")
+                .help("CSV字段分隔符，如 ';' 或制表符"),
+        )
+        .arg(
+            Arg::new("hyperlink")
+                .long("hyperlink")
+                .action(clap::ArgAction::SetTrue)
+                .help("将完整路径列写为file://超链接，点击即可打开文件（需要--path或--base-dir）"),
+        )
+        .arg(
+            Arg::new("base_dir")
+                .long("base-dir")
+                .value_name("DIR")
+                .requires("hyperlink")
+                .help("超链接的基准目录（tree输出所在的目录），--path模式下默认为遍历的目录"),
+        )
+        .arg(
+            Arg::new("link_prefix")
+                .long("link-prefix")
+                .value_name("URL")
+                .help("将文件的完整路径写为网址超链接，如 https://github.com/org/repo/blob/main/"),
+        )
+        .group(ArgGroup::new("links").args(["hyperlink", "link_prefix"]))
+        .arg(
+            Arg::new("link_names")
+                .long("link-names")
+                .action(clap::ArgAction::SetTrue)
+                .requires("links")
+                .help("文件名单元格也写为超链接（与--hyperlink或--link-prefix一起使用）"),
+        )
+        .arg(
+            Arg::new("include_hidden")
+                .short('a')
+                .long("include-hidden")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .help("包含隐藏目录/文件（以.开头的项目，如.git）"),
+        )
+        .arg(
+            Arg::new("include")
+                .long("include")
+                .value_name("PATTERN")
+                .action(clap::ArgAction::Append)
+                .help("只保留匹配的文件（及其所在目录），按完整路径或名称匹配，如 '*.rs'，可多次指定"),
+        )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .value_name("PATTERN")
+                .action(clap::ArgAction::Append)
+                .help("排除匹配的项目及其子项目，如 'node_modules'，可多次指定，优先于--include"),
+        )
+        .arg(
+            Arg::new("exclude_regex")
+                .long("exclude-regex")
+                .value_name("REGEX")
+                .action(clap::ArgAction::Append)
+                .help("排除名称或路径匹配正则的项目及其子项目，如 '^(target|build|__pycache__)$'"),
+        )
+        .arg(
+            Arg::new("ext")
+                .long("ext")
+                .value_name("EXTS")
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append)
+                .help("只保留指定扩展名的文件（及其所在目录），如 rs,toml,md"),
+        )
+        .arg(
+            Arg::new("max_depth")
+                .short('L')
+                .long("max-depth")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("只保留前N层，更深的项目被截断"),
+        )
+        .arg(
+            Arg::new("show_truncated")
+                .long("show-truncated")
+                .action(clap::ArgAction::SetTrue)
+                .requires("max_depth")
+                .help("为被截断的目录追加 \"…（另有 n 项）\" 占位行"),
+        )
+        .arg(
+            Arg::new("dirs_only")
+                .short('d')
+                .long("dirs-only")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("files_only")
+                .help("只输出目录（与tree -d相同）"),
+        )
+        .arg(
+            Arg::new("files_only")
+                .long("files-only")
+                .action(clap::ArgAction::SetTrue)
+                .help("只输出文件，层级列中仍显示其上级目录"),
+        )
+        .arg(
+            Arg::new("sort")
+                .long("sort")
+                .value_name("KEY")
+                .value_parser(["name", "ext", "size", "type"])
+                .help("同级项目排序方式：name（名称）、ext（扩展名）、size（大小，从大到小）或type（类型），默认保持输入顺序"),
+        )
+        .arg(
+            Arg::new("dirs_first")
+                .long("dirs-first")
+                .action(clap::ArgAction::SetTrue)
+                .help("同级项目中目录排在文件之前"),
+        )
+        .arg(
+            Arg::new("include_root")
+                .long("include-root")
+                .action(clap::ArgAction::SetTrue)
+                .help("保留根目录作为第一层（L1），完整路径以根目录开头"),
+        )
+        .arg(
+            Arg::new("split_by_top")
+                .long("split-by-top")
+                .action(clap::ArgAction::SetTrue)
+                .help("每个第一层目录（src、tests、docs…）输出到单独的工作表，并增加带超链接的\"目录\"索引工作表"),
+        )
+        .arg(
+            Arg::new("stats_sheet")
+                .long("stats-sheet")
+                .action(clap::ArgAction::SetTrue)
+                .help("增加\"统计\"工作表：扩展名分布、各目录文件数、总大小及最大深度"),
+        )
+        .arg(
+            Arg::new("top")
+                .long("top")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("增加\"最大文件\"和\"最长路径\"工作表，各列出前N项，超过260个字符的路径标出警告"),
+        )
+        .arg(
+            Arg::new("baseline")
+                .long("baseline")
+                .value_name("FILE")
+                .help("与上次导出的结果（.xlsx、--snapshot保存的.json或tree输出）对比，在\"变更\"列中标出新增、删除及大小变化的项目"),
+        )
+        .arg(
+            Arg::new("snapshot")
+                .long("snapshot")
+                .value_name("FILE")
+                .help("同时将本次的目录结构保存为JSON快照，供下次--baseline使用（可与--baseline为同一文件）"),
+        )
+        .arg(
+            Arg::new("chart")
+                .long("chart")
+                .value_name("TYPE")
+                .value_parser(["pie", "bar"])
+                .help("在\"统计\"工作表中嵌入扩展名分布图表：pie（饼图）或bar（条形图），自动启用--stats-sheet"),
+        )
+        .arg(
+            Arg::new("chart_by")
+                .long("chart-by")
+                .value_name("VALUE")
+                .value_parser(["count", "size"])
+                .default_value("count")
+                .requires("chart")
+                .help("图表按文件数（count）或大小（size）统计，输入没有大小时按文件数"),
+        )
+        .arg(
+            Arg::new("layout")
+                .long("layout")
+                .value_name("LAYOUT")
+                .value_parser(["merged", "outline", "flat", "indent"])
+                .default_value("merged")
+                .help("层级的呈现方式：merged（合并单元格）、outline（分组，可用+/-折叠目录）、flat（完整路径、名称、深度、类型、上级目录各一列，没有合并单元格，便于数据透视表和VLOOKUP）或indent（单个按层级缩进的名称列）"),
+        )
+        .arg(
+            Arg::new("max_level_cols")
+                .long("max-level-cols")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("层级列最多N列，更深的层级合并到最后一个层级列（如 c/d/e.txt），避免很深的目录产生过多的列"),
+        )
+        .arg(
+            Arg::new("size_format")
+                .long("size-format")
+                .value_name("FORMAT")
+                .value_parser(["bytes", "human", "both"])
+                .default_value("both")
+                .help("输入有大小时的大小列：bytes（字节数，可排序求和）、human（易读，如1.4 MiB）或both（两列都输出）"),
+        )
+        .arg(
+            Arg::new("extra_col")
+                .long("extra-col")
+                .value_name("NAME")
+                .action(clap::ArgAction::Append)
+                .help("在备注列之后追加空白列（可多次指定），如 --extra-col 负责人 --extra-col 审核状态"),
+        )
+        .arg(
+            Arg::new("status_values")
+                .long("status-values")
+                .value_name("LIST")
+                .help("在备注列之前增加带下拉列表的\"状态\"列，选项以逗号分隔，如 \"保留,删除,归档\""),
+        )
+        .arg(
+            Arg::new("autofit")
+                .long("autofit")
+                .action(clap::ArgAction::SetTrue)
+                .help("按各列最长的内容自动设置列宽"),
+        )
+        .arg(
+            Arg::new("col_width")
+                .long("col-width")
+                .value_name("SPEC")
+                .help("手动指定列宽，如 L1=30,L2=25,path=80（列名: L1…、path、name、size、notes或--extra-col的列名等）"),
+        )
+        .arg(
+            Arg::new("theme")
+                .long("theme")
+                .global(true)
+                .value_name("THEME")
+                .help("配色主题：内置的light、dark、mono、corporate，或TOML主题文件的路径"),
+        )
+        .arg(
+            Arg::new("color_by_type")
+                .long("color-by-type")
+                .action(clap::ArgAction::SetTrue)
+                .help("按文件类别（代码、文档、图片、二进制、配置）为文件名着色，类别及颜色可在主题文件中配置"),
+        )
+        .arg(
+            Arg::new("no_stats")
+                .long("no-stats")
+                .action(clap::ArgAction::SetTrue)
+                .help("不输出末尾的统计行"),
+        )
+        .arg(
+            Arg::new("summary")
+                .long("summary")
+                .value_name("FILE")
+                .help("同时导出mdBook风格的SUMMARY.md导航文件"),
+        )
+        .arg(
+            Arg::new("summary_md_only")
+                .long("summary-md-only")
+                .action(clap::ArgAction::SetTrue)
+                .requires("summary")
+                .help("SUMMARY.md中只包含.md文件（及其所在目录）"),
+        )
+        .arg(
+            Arg::new("scan_secrets")
+                .long("scan-secrets")
+                .action(clap::ArgAction::SetTrue)
+                .help("按文件名标记疑似密钥/凭据文件（id_rsa、*.pem、.env等）"),
+        )
+        .arg(
+            Arg::new("low_memory")
+                .long("low-memory")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all([
+                    "path",
+                    "include",
+                    "exclude",
+                    "exclude_regex",
+                    "ext",
+                    "max_depth",
+                    "dirs_only",
+                    "files_only",
+                    "sort",
+                    "dirs_first",
+                    "include_root",
+                    "summary",
+                    "split_by_top",
+                    "baseline",
+                    "snapshot",
+                    "archive",
+                    "s3",
+                    "ssh",
+                    "docker_image",
+                    "webdav",
+                ])
+                .help("边解析边写入Excel，内存占用恒定，适合百万行级的tree文本（不支持过滤和排序）"),
+        )
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .global(true)
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("verbose")
+                .help("不输出提示信息、警告和进度，只输出错误"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .global(true)
+                .action(clap::ArgAction::Count)
+                .help("输出诊断信息：-v显示检测到的格式及跳过的无法解析的行，-vv逐行显示解析结果"),
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .action(clap::ArgAction::SetTrue)
+                .help("tree文本中有无法解析的行时列出其行号及内容，并以状态码3退出（默认跳过这些行并给出警告）"),
+        )
+        .arg(
+            Arg::new("report")
+                .long("report")
+                .global(true)
+                .value_name("FORMAT")
+                .value_parser(["json"])
+                .help("结束时输出运行摘要（是否成功、项目数、写入行数、跳过的行数、警告），写到标准输出（-o -时写到标准错误），此时提示信息改写到标准错误，供CI读取"),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .value_name("FILE")
+                .help("配置文件（TOML或YAML），以选项的长名称为键保存默认值，如 exclude = [\"target\"]、theme = \"dark\"；命令行指定的选项优先。未指定时依次查找当前目录的tree2excel.toml/.yaml/.yml及用户配置目录下的tree-to-excel/config.toml"),
+        )
+        .arg(
+            Arg::new("no_config")
+                .long("no-config")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("config")
+                .help("不读取任何配置文件"),
+        )
+        .subcommand(
+            Command::new("diff")
+                .about("对比两份tree输出（如两个版本的打包内容）：新增的行为绿色，删除的为红色，大小变化的为黄色，末尾为各类变化数")
+                .arg(
+                    Arg::new("old")
+                        .value_name("OLD")
+                        .required(true)
+                        .help("旧的tree输出文件"),
+                )
+                .arg(
+                    Arg::new("new")
+                        .value_name("NEW")
+                        .required(true)
+                        .help("新的tree输出文件"),
+                ),
+        )
+        .subcommand(
+            Command::new("reverse")
+                .about("将Excel（本工具生成后编辑过的，如删除行、重命名）还原为经典tree文本，默认输出到标准输出")
+                .arg(
+                    Arg::new("xlsx")
+                        .value_name("INPUT")
+                        .required(true)
+                        .help("xlsx文件，按层级列（L1、L2…）或完整路径列读取第一个工作表"),
+                ),
+        )
+        .subcommand(
+            Command::new("scaffold")
+                .about("按Excel中设计的目录结构生成创建脚本（默认输出到标准输出），或用--create直接创建")
+                .arg(
+                    Arg::new("xlsx")
+                        .value_name("INPUT")
+                        .required(true)
+                        .help("xlsx文件，按层级列（L1、L2…）或完整路径列读取第一个工作表"),
+                )
+                .arg(
+                    Arg::new("shell")
+                        .long("shell")
+                        .value_name("SHELL")
+                        .value_parser(["bash", "powershell"])
+                        .default_value("bash")
+                        .help("脚本类型：bash（mkdir -p/touch）或powershell（New-Item）"),
+                )
+                .arg(
+                    Arg::new("create")
+                        .long("create")
+                        .value_name("DIR")
+                        .conflicts_with("shell")
+                        .help("不生成脚本，直接在DIR下创建目录和空文件（已存在的文件保持不变）"),
+                ),
+        )
+        .subcommand(
+            Command::new("verify")
+                .about("校验Excel清单与磁盘上的目录是否一致：列出缺失的、未登记的及大小不符的文件，不一致时以非零状态码退出（用于CI）")
+                .arg(
+                    Arg::new("xlsx")
+                        .value_name("INPUT")
+                        .required(true)
+                        .help("xlsx文件，按层级列（L1、L2…）或完整路径列读取第一个工作表"),
+                )
+                .arg(
+                    Arg::new("path")
+                        .short('p')
+                        .long("path")
+                        .value_name("DIR")
+                        .required(true)
+                        .help("要校验的目录"),
+                )
+                .arg(
+                    Arg::new("use_gitignore")
+                        .long("use-gitignore")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("遍历时遵循.gitignore规则（生成清单时使用了--use-gitignore时指定）"),
+                ),
+        )
+        .subcommand(
+            Command::new("completions")
+                .about("输出shell补全脚本，如 tree-to-excel completions bash > /etc/bash_completion.d/tree-to-excel")
+                .arg(
+                    Arg::new("shell")
+                        .value_name("SHELL")
+                        .required(true)
+                        .value_parser(clap::value_parser!(clap_complete::Shell))
+                        .help("shell类型：bash、zsh、fish、powershell或elvish"),
+                ),
+        )
+        .subcommand(
+            Command::new("man")
+                .about("输出man手册页（roff格式），如 tree-to-excel man > tree-to-excel.1")
+                .arg(
+                    Arg::new("dir")
+                        .long("dir")
+                        .value_name("DIR")
+                        .help("把主命令及各子命令的手册页（tree-to-excel.1、tree-to-excel-diff.1等）写到该目录，而不是输出到标准输出"),
+                ),
+        );
+    let matches = command.clone().get_matches();
+    set_verbosity(&matches);
+
+    // 补全脚本和手册页与配置文件无关
+    match matches.subcommand() {
+        Some(("completions", completions_matches)) => {
+            let shell = *completions_matches
+                .get_one::<clap_complete::Shell>("shell")
+                .unwrap();
+            clap_complete::generate(
+                shell,
+                &mut command.clone(),
+                "tree-to-excel",
+                &mut io::stdout(),
+            );
+            return Ok(());
         }
+        Some(("man", man_matches)) => return run_man(command, man_matches),
+        _ => {}
+    }
+    let (matches, config_path) = apply_config(&command, matches)?;
+    set_verbosity(&matches);
+    REPORT_JSON.store(matches.contains_id("report"), Ordering::Relaxed);
+
+    match matches.subcommand() {
+        Some(("diff", diff_matches)) => return run_diff(diff_matches),
+        Some(("reverse", reverse_matches)) => return run_reverse(reverse_matches),
+        Some(("scaffold", scaffold_matches)) => return run_scaffold(scaffold_matches),
+        Some(("verify", verify_matches)) => return run_verify(verify_matches),
+        _ => {}
+    }
 
-        // 重新计算统计信息（基于实际解析的内容）
-        let file_count = items.iter().filter(|item| item.is_file).count();
-        let dir_count = items.iter().filter(|item| !item.is_file).count();
-
-        let stats_text = if include_hidden {
-            // 如果包含隐藏目录，使用原始统计信息（如果有的话）
-            stats_line.unwrap_or_else(|| format!("{dir_count} directories, {file_count} files"))
+    // 未指定-o时按输出格式决定默认文件扩展名
+    let output_format = matches.get_one::<String>("to").unwrap().as_str();
+    let output_path = if matches.value_source("output") == Some(ValueSource::DefaultValue) {
+        let extension = if output_format == "markdown" {
+            "md"
         } else {
-            // 如果过滤了隐藏目录，使用重新计算的统计信息
-            format!("{dir_count} directories, {file_count} files")
+            output_format
         };
+        format!("tree_output.{extension}")
+    } else {
+        matches.get_one::<String>("output").unwrap().clone()
+    };
+    let output_path = output_path.as_str();
+    STDOUT_OUTPUT.store(output_path == "-", Ordering::Relaxed);
+    if let Some(config_path) = config_path {
+        status!("⚙️ 使用配置文件: {}", config_path.display());
+    }
+    let include_hidden = matches.get_flag("include_hidden");
 
-        items.push(TreeItem {
-            name: format!("📊 统计: {stats_text}"),
-            level: 0,
-            is_file: false,
-            full_path: format!("📊 统计: {stats_text}"),
-        });
+    if matches.get_flag("low_memory") {
+        return generate_low_memory(&matches, output_path, include_hidden);
+    }
 
-        Ok(items)
+    if matches.get_flag("watch") {
+        return watch(&matches, output_path, include_hidden);
     }
+    generate(&matches, output_path, include_hidden)
+}
 
-    /// 解析单行，返回(层级, 名称)
-    fn parse_line(&self, line: &str) -> Option<(usize, String)> {
-        // 跳过根目录标记（可能是 "." 或项目名如 "utzip-0.9.0/"）
-        let trimmed = line.trim();
-        if trimmed == "."
-            || (trimmed.ends_with('/') && !trimmed.contains("├") && !trimmed.contains("└"))
-        {
-            return None;
+/// 读取输入并生成输出文件
+fn generate(matches: &ArgMatches, output_path: &str, include_hidden: bool) -> Result<()> {
+    let output_format = matches.get_one::<String>("to").unwrap().as_str();
+    let lang = output_lang(matches)?;
+
+    // 每个输入对应一个(工作表名称, 项目列表)
+    let sheets: Vec<(String, Vec<TreeItem>)> = if let Some(dir) = matches.get_one::<String>("path")
+    {
+        // 直接遍历目录
+        if include_hidden {
+            status!("📂 遍历目录（包含隐藏目录）: {dir}");
+        } else {
+            status!("📂 遍历目录（默认忽略.git等隐藏目录）: {dir}");
         }
-
-        // 清理行，移除ANSI转义序列
-        let clean_line = self.remove_ansi_codes(line);
-        let chars: Vec<char> = clean_line.chars().collect();
-        let mut pos = 0;
-        let mut level = 0;
-
-        // 计算层级：支持两种缩进模式
-        // 1. "│   " 模式（垂直线 + 3个空格）
-        // 2. "    " 模式（4个空格，用于最后的子目录）
-        // 注意：tree输出可能使用不同类型的空格字符(U+0020普通空格, U+00A0非断空格)
-        while pos + 3 < chars.len() {
-            if chars[pos] == '│'
-                && chars[pos + 1].is_whitespace()
-                && chars[pos + 2].is_whitespace()
-                && chars[pos + 3].is_whitespace()
-            {
-                level += 1;
-                pos += 4;
-            } else if chars[pos] == ' '
-                && chars[pos + 1] == ' '
-                && chars[pos + 2] == ' '
-                && chars[pos + 3] == ' '
-            {
-                // 支持纯空格缩进（4个空格）
-                level += 1;
-                pos += 4;
-            } else {
-                break;
-            }
+        let mut walker = DirWalker::new();
+        if matches.get_flag("use_gitignore") {
+            walker = walker.use_gitignore();
         }
-
-        // 查找并跳过tree连接符 "├──" 或 "└──"
-        if pos + 2 < chars.len()
-            && (chars[pos] == '├' || chars[pos] == '└')
-            && chars[pos + 1] == '─'
-            && chars[pos + 2] == '─'
-        {
-            pos += 3;
-            // 跳过可能的空格
-            if pos < chars.len() && chars[pos] == ' ' {
-                pos += 1;
-            }
-        } else {
-            // 没有找到标准的tree符号，可能不是有效的tree行
-            return None;
+        if matches.get_flag("follow_symlinks") {
+            walker = walker.follow_symlinks();
         }
-
-        // 提取剩余部分作为文件/目录名
-        if pos >= chars.len() {
-            return None;
+        if matches.get_flag("git") {
+            walker = walker.git();
         }
+        let items = walker
+            .walk(Path::new(dir), include_hidden)
+            .with_context(|| format!("遍历目录失败: {dir}"))?;
+        // UNC路径（\\server\share）以共享名命名工作表
+        let sheet_name = dir.strip_prefix(r"\\").unwrap_or(dir);
+        vec![(sheet_name.to_string(), items)]
+    } else if let Some(input_files) = matches.get_many::<String>("input") {
+        let mut sheets = Vec::new();
+        for input_file in input_files {
+            status!("📖 读取tree输出文件: {input_file}");
+            let file = fs::File::open(input_file)
+                .with_context(|| format!("无法读取文件: {input_file}"))?;
+            let items = parse_input(matches, BufReader::new(file), include_hidden)
+                .with_context(|| format!("解析文件失败: {input_file}"))?;
+
+            // 工作表以源文件名（不含扩展名）命名
+            let sheet_name = Path::new(input_file).file_stem().map_or_else(
+                || input_file.clone(),
+                |stem| stem.to_string_lossy().into_owned(),
+            );
+            sheets.push((sheet_name, items));
+        }
+        sheets
+    } else if let Some(archive) = matches.get_one::<String>("archive") {
+        status!("📦 读取压缩包目录: {archive}");
+        let path = Path::new(archive);
+        let items = ArchiveReader::new()
+            .read_file(path, include_hidden)
+            .with_context(|| format!("解析压缩包失败: {archive}"))?;
+        let sheet_name = path.file_name().map_or_else(
+            || archive.clone(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        vec![(sheet_name, items)]
+    } else if let Some(url) = matches.get_one::<String>("s3") {
+        status!("☁️ 列出S3对象: {url}");
+        let items = list_s3(url, include_hidden)?;
+        // 工作表以存储桶命名
+        let bucket = url.trim_start_matches("s3://").split('/').next();
+        vec![(bucket.unwrap_or(url).to_string(), items)]
+    } else if let Some(target) = matches.get_one::<String>("ssh") {
+        status!("🔐 通过SFTP列出远程目录: {target}");
+        let port = *matches.get_one::<u16>("ssh_port").unwrap();
+        let (host, items) = list_sftp(target, port, include_hidden)?;
+        vec![(host, items)]
+    } else if let Some(image) = matches.get_one::<String>("docker_image") {
+        status!("🐳 读取Docker镜像: {image}");
+        let items = DockerImageReader::new()
+            .read_image(image, include_hidden)
+            .with_context(|| format!("读取Docker镜像失败: {image}"))?;
+        let sheet_name = Path::new(image)
+            .file_name()
+            .map_or_else(|| image.clone(), |name| name.to_string_lossy().into_owned());
+        vec![(sheet_name, items)]
+    } else if let Some(url) = matches.get_one::<String>("webdav") {
+        status!("🌐 列出WebDAV目录: {url}");
+        let (host, items) = list_webdav(url, include_hidden)?;
+        vec![(host, items)]
+    } else {
+        status!("📖 从标准输入读取tree输出（Ctrl+D结束）:");
+        let items = parse_input(matches, io::stdin().lock(), include_hidden)
+            .context("无法从标准输入读取")?;
+        vec![("stdin".to_string(), items)]
+    };
 
-        let name: String = chars[pos..].iter().collect::<String>().trim().to_string();
+    // 按--include/--exclude/--exclude-regex/--ext/--max-depth/--dirs-only/--files-only过滤
+    let patterns = |id: &str| -> Vec<String> {
+        matches
+            .get_many::<String>(id)
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect()
+    };
+    let mut filter = TreeFilter::new()
+        .include(&patterns("include"))?
+        .exclude(&patterns("exclude"))?
+        .exclude_regex(&patterns("exclude_regex"))?
+        .extensions(&patterns("ext"))
+        .lang(output_lang(matches)?);
+    if let Some(&max_depth) = matches.get_one::<usize>("max_depth") {
+        filter = filter.max_depth(max_depth, matches.get_flag("show_truncated"));
+    }
+    if matches.get_flag("dirs_only") {
+        filter = filter.dirs_only();
+    }
+    if matches.get_flag("files_only") {
+        filter = filter.files_only();
+    }
 
-        if name.is_empty() {
-            None
-        } else {
-            Some((level + 1, name)) // level+1 因为第一层是1，不是0
+    // 同级项目排序（先排序再过滤，截断占位行保持在各目录末尾）
+    let sort_key = match matches.get_one::<String>("sort").map(String::as_str) {
+        Some("name") => Some(SortKey::Name),
+        Some("ext") => Some(SortKey::Ext),
+        Some("size") => Some(SortKey::Size),
+        Some("type") => Some(SortKey::Type),
+        _ => None,
+    };
+    let sorter = TreeSorter::new(sort_key, matches.get_flag("dirs_first"));
+    let mut sheets: Vec<(String, Vec<TreeItem>)> = sheets
+        .into_iter()
+        .map(|(name, items)| (name, filter.apply(sorter.sort(items))))
+        .collect();
+
+    // 按文件内容识别类型（在过滤之后，只读取保留的文件）
+    if let (true, Some(dir)) = (
+        matches.get_flag("detect_type"),
+        matches.get_one::<String>("path"),
+    ) {
+        for (_, items) in &mut sheets {
+            sniff_items(Path::new(dir), items);
         }
     }
 
-    /// 移除ANSI转义序列
-    fn remove_ansi_codes(&self, text: &str) -> String {
-        // 简单的ANSI转义序列移除
-        let mut result = String::new();
-        let mut chars = text.chars().peekable();
-
-        while let Some(ch) = chars.next() {
-            if ch == '\x1b' {
-                // 跳过ANSI转义序列
-                if chars.peek() == Some(&'[') {
-                    chars.next(); // 跳过 '['
-                    for c in chars.by_ref() {
-                        if c.is_ascii_alphabetic() || c == '~' {
-                            break;
-                        }
-                    }
-                }
-            } else {
-                result.push(ch);
-            }
+    // 统计文本文件的行数
+    if let (true, Some(dir)) = (matches.get_flag("loc"), matches.get_one::<String>("path")) {
+        for (_, items) in &mut sheets {
+            count_lines(Path::new(dir), items);
         }
-        result
     }
 
-    /// 判断是否为文件
-    fn is_file(&self, name: &str) -> bool {
-        // 有扩展名的是文件
-        if name.contains('.') && !name.starts_with('.') {
-            if let Some(dot_pos) = name.rfind('.') {
-                return dot_pos > 0 && dot_pos < name.len() - 1;
-            }
+    // 计算校验和（在过滤之后，只计算保留的文件）
+    if let (Some(algorithm), Some(dir)) = (
+        matches.get_one::<String>("hash"),
+        matches.get_one::<String>("path"),
+    ) {
+        let algorithm = HashAlgorithm::from_name(algorithm)?;
+        status!("🔐 计算{}校验和...", algorithm.name());
+        for (_, items) in &mut sheets {
+            hash_items(Path::new(dir), items, algorithm, |done, total| {
+                // 每完成1%刷新一次进度条
+                if done == total || done % (total / 100).max(1) == 0 {
+                    progress!("\r⏳ [{}] {done}/{total}", progress_bar(done, total));
+                }
+            });
         }
-
-        // 常见的无扩展名文件
-        matches!(
-            name,
-            "Cargo.lock" | "Dockerfile" | "Makefile" | "LICENSE" | "README" | "CHANGELOG"
-        )
+        progress!("\n");
     }
-}
-
-/// Excel格式配置
-struct ExcelFormats {
-    dir_format: Format,
-    file_format: Format,
-    path_format: Format,
-    notes_format: Format,
-}
-
-impl ExcelFormats {
-    fn new() -> Self {
-        let dir_format = Format::new()
-            .set_background_color("#E8F4FD")
-            .set_border(rust_xlsxwriter::FormatBorder::Thin)
-            .set_bold()
-            .set_align(rust_xlsxwriter::FormatAlign::Center)
-            .set_align(rust_xlsxwriter::FormatAlign::VerticalCenter);
-
-        let file_format = Format::new()
-            .set_background_color("#F0F8E8")
-            .set_border(rust_xlsxwriter::FormatBorder::Thin);
-
-        let path_format = Format::new()
-            .set_background_color("#FFFEF7")
-            .set_border(rust_xlsxwriter::FormatBorder::Thin);
-
-        let notes_format = Format::new()
-            .set_background_color("#F5F5F5")
-            .set_border(rust_xlsxwriter::FormatBorder::Thin);
 
-        Self {
-            dir_format,
-            file_format,
-            path_format,
-            notes_format,
+    // 查找重复文件（在过滤之后，只比较保留的文件）
+    if let (true, Some(dir)) = (
+        matches.get_flag("find_duplicates"),
+        matches.get_one::<String>("path"),
+    ) {
+        status!("🔍 查找重复文件...");
+        for (_, items) in &mut sheets {
+            let groups = find_duplicates(Path::new(dir), items, |done, total| {
+                if done == total || done % (total / 100).max(1) == 0 {
+                    progress!("\r⏳ [{}] {done}/{total}", progress_bar(done, total));
+                }
+            });
+            progress!("\n");
+            let savings: u64 = groups.iter().map(DuplicateGroup::savings).sum();
+            status!(
+                "📑 发现 {} 组重复文件，只保留一份可节省 {savings} 字节",
+                groups.len()
+            );
         }
     }
-}
-
-/// Excel生成器
-struct ExcelGenerator;
 
-impl ExcelGenerator {
-    fn new() -> Self {
-        Self
+    // 与上次导出的基线对比（先读入基线，快照与基线可以是同一文件）
+    let baseline = match matches.get_one::<String>("baseline") {
+        Some(baseline_path) => {
+            status!("📖 读取基线: {baseline_path}");
+            Some(read_baseline(matches, baseline_path, include_hidden)?)
+        }
+        None => None,
+    };
+    if (baseline.is_some() || matches.contains_id("snapshot")) && sheets.len() > 1 {
+        anyhow::bail!("--baseline/--snapshot 只支持单个输入");
+    }
+    if let Some(snapshot_path) = matches.get_one::<String>("snapshot") {
+        let items = sheets
+            .first()
+            .map(|(_, items)| items.clone())
+            .unwrap_or_default();
+        JsonGenerator::new()
+            .generate(items, snapshot_path)
+            .context("保存快照失败")?;
+        status!("💾 快照已保存: {snapshot_path}");
     }
+    let sheets = match baseline {
+        Some(baseline) => sheets
+            .into_iter()
+            .map(|(name, items)| {
+                let (items, summary) = diff_trees(baseline.clone(), items, lang);
+                status!("🔍 与基线相比: {}", summary.text(lang));
+                (name, items)
+            })
+            .collect(),
+        None => sheets,
+    };
 
-    /// 生成Excel文件
-    fn generate(&self, items: Vec<TreeItem>, output_path: &str) -> Result<()> {
-        let mut workbook = Workbook::new();
-        let worksheet = workbook.add_worksheet();
+    // 保留根目录作为第一层
+    let sheets = if matches.get_flag("include_root") {
+        sheets
+            .into_iter()
+            .map(|(name, items)| (name, include_root(items)))
+            .collect()
+    } else {
+        sheets
+    };
 
-        // 转换为Excel行数据（先转换以获取max_level）
-        let rows = self.convert_to_rows(items);
-        let max_level = if rows.is_empty() {
-            1
-        } else {
-            rows[0].max_level
-        };
+    // 去掉统计行
+    let sheets = if matches.get_flag("no_stats") {
+        sheets
+            .into_iter()
+            .map(|(name, mut items)| {
+                items.retain(|item| !item.name.starts_with("📊"));
+                (name, items)
+            })
+            .collect()
+    } else {
+        sheets
+    };
 
-        // 设置标题和格式
-        self.setup_worksheet(worksheet, max_level)?;
+    let item_count: usize = sheets.iter().map(|(_, items)| items.len()).sum();
+    status!("📊 找到 {item_count} 个文件/目录");
+    if let Ok(mut report) = REPORT.lock() {
+        let all_items = || sheets.iter().flat_map(|(_, items)| items);
+        report.output = Some(output_path.to_string());
+        report.items = all_items()
+            .filter(|item| !item.name.starts_with("📊"))
+            .count();
+        report.errors = all_items().filter(|item| item.error.is_some()).count();
+        report.rows_written = item_count;
+    }
 
-        // 写入数据
-        self.write_data(worksheet, &rows)?;
+    // 导出SUMMARY.md
+    if let Some(summary_path) = matches.get_one::<String>("summary") {
+        status!("📝 生成SUMMARY文件: {summary_path}");
+        // 多个输入时合并到同一个SUMMARY中
+        let all_items: Vec<TreeItem> = sheets
+            .iter()
+            .flat_map(|(_, items)| items.iter().cloned())
+            .collect();
+        SummaryGenerator::new()
+            .generate(
+                &all_items,
+                summary_path,
+                matches.get_flag("summary_md_only"),
+            )
+            .context("生成SUMMARY文件失败")?;
+    }
 
-        // 保存文件
-        workbook
-            .save(output_path)
-            .with_context(|| format!("无法保存Excel文件: {output_path}"))?;
+    // 生成CSV（多个输入时合并到同一个文件）
+    if output_format == "csv" {
+        status!("📝 生成CSV文件: {output_path}");
+        let items = sheets.into_iter().flat_map(|(_, items)| items).collect();
+        CsvGenerator::new()
+            .delimiter(*matches.get_one::<char>("delimiter").unwrap())?
+            .lang(lang)
+            .generate(items, output_path)
+            .context("生成CSV文件失败")?;
+        status!("✅ 完成！CSV文件已保存");
+        return Ok(());
+    }
 
-        Ok(())
+    // 生成ODS（多个输入时合并到同一个工作表）
+    if output_format == "ods" {
+        status!("📝 生成ODS文件: {output_path}");
+        let items = sheets.into_iter().flat_map(|(_, items)| items).collect();
+        write_table(&mut OdsWriter::new(output_path), items, lang, &[])
+            .context("生成ODS文件失败")?;
+        status!("✅ 完成！ODS文件已保存");
+        return Ok(());
     }
 
-    /// 设置工作表
-    fn setup_worksheet(&self, worksheet: &mut Worksheet, max_level: usize) -> Result<()> {
-        let header_format = Format::new()
-            .set_bold()
-            .set_background_color("#4F81BD")
-            .set_font_color("#FFFFFF")
-            .set_border(rust_xlsxwriter::FormatBorder::Thin);
+    // 生成JSON/NDJSON（多个输入时合并到同一个文件）
+    if matches!(output_format, "json" | "ndjson") {
+        status!("📝 生成{output_format}文件: {output_path}");
+        let items: Vec<TreeItem> = sheets.into_iter().flat_map(|(_, items)| items).collect();
+        if output_format == "json" {
+            JsonGenerator::new()
+                .generate(items, output_path)
+                .context("生成JSON文件失败")?;
+        } else {
+            JsonGenerator::new()
+                .generate_ndjson(&items, output_path)
+                .context("生成NDJSON文件失败")?;
+        }
+        status!("✅ 完成！文件已保存");
+        return Ok(());
+    }
 
-        // 动态生成表头
-        let mut col = 0;
+    // 生成Markdown/HTML表格（多个输入时合并到同一个表格）
+    if matches!(output_format, "markdown" | "html") {
+        status!("📝 生成{output_format}文件: {output_path}");
+        let items = sheets.into_iter().flat_map(|(_, items)| items).collect();
+        if output_format == "markdown" {
+            MarkdownGenerator::new()
+                .lang(lang)
+                .generate(items, output_path)
+                .context("生成Markdown文件失败")?;
+        } else {
+            HtmlGenerator::new()
+                .lang(lang)
+                .generate(items, output_path)
+                .context("生成HTML文件失败")?;
+        }
+        status!("✅ 完成！文件已保存");
+        return Ok(());
+    }
 
-        // 层级列：L1, L2, L3, ...
-        for level in 1..=max_level {
-            let header = format!("L{level}");
-            worksheet.write_with_format(0, col as u16, &header, &header_format)?;
-            worksheet.set_column_width(col as u16, 20.0)?; // 层级列宽度
-            col += 1;
+    // 生成Excel
+    status!("📝 生成Excel文件: {output_path}");
+    let scan_secrets = matches.get_flag("scan_secrets");
+    if scan_secrets {
+        let secret_count = sheets
+            .iter()
+            .flat_map(|(_, items)| items)
+            .filter(|item| item.is_file && detect_secret(&item.name).is_some())
+            .count();
+        status!("🔐 发现 {secret_count} 个疑似敏感文件");
+        if !include_hidden {
+            status!("💡 提示: 隐藏文件（如.env）默认被过滤，可加上 -a 一并检查");
         }
+    }
 
-        // 完整路径列
-        worksheet.write_with_format(0, col as u16, "完整路径", &header_format)?;
-        worksheet.set_column_width(col as u16, 60.0)?; // 增加宽度以适应长路径和统计信息
-        col += 1;
+    let generator = excel_generator(matches)?;
+    if matches.get_flag("split_by_top") {
+        // 多个输入时工作表名称以输入名称为前缀
+        let multiple = sheets.len() > 1;
+        let groups: Vec<(String, Vec<TreeItem>)> = sheets
+            .into_iter()
+            .flat_map(|(name, items)| {
+                split_by_top(items, lang)
+                    .into_iter()
+                    .map(move |(top, items)| {
+                        let sheet_name = if multiple {
+                            format!("{name}-{top}")
+                        } else {
+                            top
+                        };
+                        (sheet_name, items)
+                    })
+            })
+            .collect();
+        status!("📑 按第一层目录拆分为 {} 个工作表", groups.len());
+        generator
+            .generate_with_index(groups, output_path)
+            .context("生成Excel文件失败")?;
+    } else if sheets.len() > 1 {
+        status!("📑 共 {} 个工作表", sheets.len());
+        generator
+            .generate_sheets(sheets, output_path)
+            .context("生成Excel文件失败")?;
+    } else {
+        let items = sheets.into_iter().flat_map(|(_, items)| items).collect();
+        generator
+            .generate(items, output_path)
+            .context("生成Excel文件失败")?;
+    }
 
-        // 备注列
-        worksheet.write_with_format(0, col as u16, "备注", &header_format)?;
-        worksheet.set_column_width(col as u16, 30.0)?;
+    status!("✅ 完成！Excel文件已保存");
 
-        Ok(())
+    Ok(())
+}
+
+/// --watch：先生成一次，之后目录有变化时（合并一段时间内的事件）重新生成；
+/// 生成失败（如文件正被Excel打开）时提示错误并继续监视
+fn watch(matches: &ArgMatches, output_path: &str, include_hidden: bool) -> Result<()> {
+    if output_path == "-" {
+        anyhow::bail!("--watch 不支持输出到标准输出");
     }
+    let dir = matches.get_one::<String>("path").unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).context("无法创建目录监视器")?;
+    watcher
+        .watch(Path::new(dir), RecursiveMode::Recursive)
+        .with_context(|| format!("无法监视目录: {dir}"))?;
+
+    generate(matches, output_path, include_hidden)?;
+
+    // 输出文件及Excel的锁文件（~$开头）在监视的目录内时，其变化不触发重新生成
+    let output = fs::canonicalize(output_path).ok();
+    let is_own_file = |path: &Path| {
+        path.file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("~$"))
+            || (output.is_some() && fs::canonicalize(path).ok() == output)
+    };
+    // 只读访问（包括遍历本身打开目录）不算变化
+    let is_change = |event: notify::Result<notify::Event>| -> Result<bool> {
+        let event = event.context("监视目录失败")?;
+        Ok(!matches!(event.kind, EventKind::Access(_))
+            && !event.paths.iter().all(|path| is_own_file(path)))
+    };
 
-    /// 将TreeItem转换为ExcelRow
-    fn convert_to_rows(&self, items: Vec<TreeItem>) -> Vec<ExcelRow> {
-        let mut rows = Vec::new();
-        let mut path_stack: Vec<String> = Vec::new();
+    status!("👀 监视目录变化（Ctrl+C结束）: {dir}");
+    while let Ok(event) = receiver.recv() {
+        let mut changes = usize::from(is_change(event)?);
+        // 等到一段时间内没有新事件再重新生成
+        while let Ok(event) = receiver.recv_timeout(WATCH_DEBOUNCE) {
+            changes += usize::from(is_change(event)?);
+        }
+        if changes == 0 {
+            continue;
+        }
+        status!("🔄 检测到 {changes} 处变化，重新生成");
+        if let Err(err) = generate(matches, output_path, include_hidden) {
+            log::error!("生成失败: {err:#}");
+        }
+        status!("👀 继续监视目录变化（Ctrl+C结束）: {dir}");
+    }
+    Ok(())
+}
 
-        // 首先找出最大层级深度
-        let max_level = items
-            .iter()
-            .filter(|item| !item.name.starts_with("📊"))
-            .map(|item| item.level)
-            .max()
-            .unwrap_or(1);
-
-        for item in items {
-            // 统计信息特殊处理
-            if item.name.starts_with("📊") {
-                let mut levels = vec!["".to_string(); max_level];
-                levels[0] = item.name.clone();
-
-                rows.push(ExcelRow {
-                    levels,
-                    full_path: item.name.clone(),
-                    max_level,
-                    is_file: false,
-                });
-                continue;
+/// 读取配置文件，把命令行中未指定（且不与命令行冲突）的选项加在参数前重新解析；
+/// 返回解析结果及实际使用的配置文件
+fn apply_config(command: &Command, matches: ArgMatches) -> Result<(ArgMatches, Option<PathBuf>)> {
+    if matches.get_flag("no_config") {
+        return Ok((matches, None));
+    }
+    let path = match matches.get_one::<String>("config") {
+        Some(path) => PathBuf::from(path),
+        None => match Config::find() {
+            Some(path) => path,
+            None => return Ok((matches, None)),
+        },
+    };
+    let config = Config::load(&path)?;
+
+    let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+    let mut args: Vec<OsString> = Vec::new();
+    for (key, value) in &config.options {
+        let Some(arg) = command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(key.as_str()))
+            .filter(|arg| !matches!(arg.get_id().as_str(), "config" | "no_config"))
+        else {
+            anyhow::bail!("配置文件中有未知选项 {key}: {}", path.display());
+        };
+        let overridden = given(arg.get_id().as_str())
+            || command
+                .get_arg_conflicts_with(arg)
+                .iter()
+                .any(|other| given(other.get_id().as_str()));
+        if overridden {
+            continue;
+        }
+        match value {
+            ConfigValue::Flag(true) => args.push(format!("--{key}").into()),
+            ConfigValue::Flag(false) => {}
+            ConfigValue::Values(values) => {
+                args.extend(values.iter().map(|value| format!("--{key}={value}").into()));
             }
+        }
+    }
 
-            // 调整路径栈到当前层级
-            path_stack.truncate(item.level.saturating_sub(1));
-            path_stack.push(item.name.clone());
-
-            // 构建levels数组，填充到对应层级
-            let mut levels = vec!["".to_string(); max_level];
-            for (i, path_item) in path_stack.iter().enumerate() {
-                if i < max_level {
-                    levels[i] = path_item.clone();
-                }
-            }
+    let mut argv: Vec<OsString> = std::env::args_os().collect();
+    argv.splice(1..1, args);
+    Ok((command.clone().get_matches_from(argv), Some(path)))
+}
 
-            rows.push(ExcelRow {
-                levels,
-                full_path: item.full_path.clone(),
-                max_level,
-                is_file: item.is_file,
-            });
-        }
+/// 输出内容的语言：--lang指定，否则按系统区域设置
+fn output_lang(matches: &ArgMatches) -> Result<Language> {
+    match matches.get_one::<String>("lang") {
+        Some(code) => Ok(Language::from_code(code)?),
+        None => Ok(Language::from_env()),
+    }
+}
 
-        rows
+/// 按命令行参数配置Excel生成器
+fn excel_generator(matches: &ArgMatches) -> Result<ExcelGenerator> {
+    let mut builder = ExcelGenerator::builder()
+        .scan_secrets(matches.get_flag("scan_secrets"))
+        .lang(output_lang(matches)?)
+        .stats_sheet(matches.get_flag("stats_sheet"))
+        .autofit(matches.get_flag("autofit"))
+        .detect_type(matches.get_flag("detect_type"))
+        .color_by_type(matches.get_flag("color_by_type"))
+        .link_names(matches.get_flag("link_names"));
+    if let Some(&n) = matches.get_one::<usize>("top") {
+        builder = builder.top(n);
+    }
+    if let Some(&n) = matches.get_one::<usize>("max_level_cols") {
+        builder = builder.max_level_cap(n);
+    }
+    match matches.get_one::<String>("size_format").map(String::as_str) {
+        Some("bytes") => builder = builder.size_format(SizeFormat::Bytes),
+        Some("human") => builder = builder.size_format(SizeFormat::Human),
+        _ => {}
+    }
+    match matches.get_one::<String>("layout").map(String::as_str) {
+        Some("outline") => builder = builder.layout(Layout::Outline),
+        Some("flat") => builder = builder.layout(Layout::Flat),
+        Some("indent") => builder = builder.layout(Layout::Indent),
+        _ => {}
+    }
+    if let Some(names) = matches.get_many::<String>("extra_col") {
+        builder = builder.extra_cols(names.cloned().collect());
+    }
+    if let Some(values) = matches.get_one::<String>("status_values") {
+        builder = builder.status_values(values.split(',').map(str::to_string).collect());
+    }
+    if let Some(spec) = matches.get_one::<String>("col_width") {
+        builder = builder.col_widths(spec);
     }
+    if let Some(theme) = matches.get_one::<String>("theme") {
+        builder = builder.theme(Theme::load(theme)?);
+    }
+    if matches.get_flag("hyperlink") {
+        let base = matches
+            .get_one::<String>("base_dir")
+            .or(matches.get_one::<String>("path"))
+            .context("--hyperlink 需要通过--base-dir指定tree输出所在的目录")?;
+        let base =
+            std::path::absolute(base).with_context(|| format!("无法解析超链接基准目录: {base}"))?;
+        builder = builder.hyperlinks(base);
+    }
+    if let Some(prefix) = matches.get_one::<String>("link_prefix") {
+        builder = builder.link_prefix(prefix.clone());
+    }
+    if let Some(chart) = matches.get_one::<String>("chart") {
+        let kind = if chart == "bar" {
+            ChartKind::Bar
+        } else {
+            ChartKind::Pie
+        };
+        let by_size = matches.get_one::<String>("chart_by").map(String::as_str) == Some("size");
+        builder = builder.chart(kind, by_size);
+    }
+    Ok(builder.build()?)
+}
 
-    /// 写入Excel数据（支持层级合并单元格）
-    fn write_data(&self, worksheet: &mut Worksheet, rows: &[ExcelRow]) -> Result<()> {
-        if rows.is_empty() {
-            return Ok(());
-        }
+/// diff子命令：对比两份tree输出，生成标出新增、删除及大小变化的Excel文件
+fn run_diff(matches: &ArgMatches) -> Result<()> {
+    let output_path = if matches.value_source("output") == Some(ValueSource::DefaultValue) {
+        "tree_diff.xlsx"
+    } else {
+        matches.get_one::<String>("output").unwrap().as_str()
+    };
+    STDOUT_OUTPUT.store(output_path == "-", Ordering::Relaxed);
+    let include_hidden = matches.get_flag("include_hidden");
+    let lang = output_lang(matches)?;
+
+    let mut snapshots = Vec::new();
+    for id in ["old", "new"] {
+        let input_file = matches.get_one::<String>(id).unwrap();
+        status!("📖 读取tree输出文件: {input_file}");
+        let file =
+            fs::File::open(input_file).with_context(|| format!("无法读取文件: {input_file}"))?;
+        let items = parse_input(matches, BufReader::new(file), include_hidden)
+            .with_context(|| format!("解析文件失败: {input_file}"))?;
+        snapshots.push(items);
+    }
+    let new = snapshots.pop().unwrap_or_default();
+    let old = snapshots.pop().unwrap_or_default();
 
-        let max_level = rows[0].max_level;
+    let (items, summary) = diff_trees(old, new, lang);
+    status!("🔍 {}", summary.text(lang));
 
-        // 创建格式配置
-        let formats = ExcelFormats::new();
+    status!("📝 生成Excel文件: {output_path}");
+    let mut builder = ExcelGenerator::builder().lang(lang);
+    if let Some(theme) = matches.get_one::<String>("theme") {
+        builder = builder.theme(Theme::load(theme)?);
+    }
+    builder
+        .build()?
+        .generate(items, output_path)
+        .context("生成Excel文件失败")?;
+    status!("✅ 完成！Excel文件已保存");
 
-        let stats_format = Format::new()
-            .set_background_color("#FFE4E1")
-            .set_border(rust_xlsxwriter::FormatBorder::Thin)
-            .set_bold()
-            .set_font_color("#8B0000");
+    Ok(())
+}
 
-        let mut current_row = 1u32;
+/// reverse子命令：读取xlsx，输出tree文本
+fn run_reverse(matches: &ArgMatches) -> Result<()> {
+    let output_path = if matches.value_source("output") == Some(ValueSource::DefaultValue) {
+        "-"
+    } else {
+        matches.get_one::<String>("output").unwrap().as_str()
+    };
+    STDOUT_OUTPUT.store(output_path == "-", Ordering::Relaxed);
 
-        // 分离统计行和数据行
-        let mut data_rows = Vec::new();
-        let mut stats_rows = Vec::new();
+    let input_file = matches.get_one::<String>("xlsx").unwrap();
+    status!("📖 读取Excel文件: {input_file}");
+    let items = XlsxTreeParser::new()
+        .parse_file(Path::new(input_file))
+        .with_context(|| format!("解析文件失败: {input_file}"))?;
 
-        for row in rows {
-            if row.levels[0].starts_with("📊") {
-                stats_rows.push(row);
-            } else {
-                data_rows.push(row);
-            }
-        }
+    status!("📝 生成tree文本: {output_path}");
+    TreeTextGenerator::new()
+        .generate(&items, output_path)
+        .context("生成tree文本失败")?;
+    status!("✅ 完成！");
 
-        // 写入数据行，实现层级合并单元格
-        self.write_data_with_merging(worksheet, &data_rows, max_level, &formats, &mut current_row)?;
+    Ok(())
+}
 
-        // 记录stats行数量，避免所有权问题
-        let stats_count = stats_rows.len();
+/// scaffold子命令：读取xlsx，生成创建目录结构的脚本或直接创建
+fn run_scaffold(matches: &ArgMatches) -> Result<()> {
+    let output_path = if matches.value_source("output") == Some(ValueSource::DefaultValue) {
+        "-"
+    } else {
+        matches.get_one::<String>("output").unwrap().as_str()
+    };
+    STDOUT_OUTPUT.store(output_path == "-", Ordering::Relaxed);
+
+    let input_file = matches.get_one::<String>("xlsx").unwrap();
+    status!("📖 读取Excel文件: {input_file}");
+    let items = XlsxTreeParser::new()
+        .parse_file(Path::new(input_file))
+        .with_context(|| format!("解析文件失败: {input_file}"))?;
+
+    if let Some(target) = matches.get_one::<String>("create") {
+        status!("📁 创建目录结构: {target}");
+        let (dirs, files) = create_structure(Path::new(target), &items)?;
+        status!("✅ 完成！已创建 {dirs} 个目录、{files} 个文件");
+        return Ok(());
+    }
 
-        // 写入统计行
-        for stats_row in stats_rows {
-            let total_cols = max_level + 2;
+    let shell = Shell::from_name(matches.get_one::<String>("shell").unwrap())?;
+    status!("📝 生成脚本: {output_path}");
+    write_script(&items, shell, output_path)?;
+    status!("✅ 完成！");
 
-            // 设置统计行行高为20
-            worksheet.set_row_height(current_row, 20.0)?;
+    Ok(())
+}
 
-            worksheet.merge_range(
-                current_row,
-                0,
-                current_row,
-                (total_cols - 1) as u16,
-                &stats_row.levels[0],
-                &stats_format,
-            )?;
-            current_row += 1;
+/// man子命令：输出主命令的手册页，或把全部手册页写到--dir
+fn run_man(command: Command, matches: &ArgMatches) -> Result<()> {
+    match matches.get_one::<String>("dir") {
+        Some(dir) => {
+            fs::create_dir_all(dir).with_context(|| format!("无法创建目录: {dir}"))?;
+            clap_mangen::generate_to(command, dir)
+                .with_context(|| format!("无法写入手册页: {dir}"))?;
+            status!("✅ 手册页已写入: {dir}");
         }
+        None => clap_mangen::Man::new(command)
+            .render(&mut io::stdout())
+            .context("无法输出手册页")?,
+    }
+    Ok(())
+}
 
-        // 冻结首行
-        let _ = worksheet.set_freeze_panes(1, 0);
-
-        // 自动筛选
-        if !data_rows.is_empty() {
-            let total_cols = max_level + 2;
-            worksheet.autofilter(
-                0,
-                0,
-                (data_rows.len() + stats_count) as u32,
-                (total_cols - 1) as u16,
-            )?;
+/// verify子命令：对比xlsx清单与磁盘上的目录，不一致时以状态码1退出
+fn run_verify(matches: &ArgMatches) -> Result<()> {
+    let input_file = matches.get_one::<String>("xlsx").unwrap();
+    status!("📖 读取Excel文件: {input_file}");
+    let sheet = XlsxTreeParser::new()
+        .parse_file(Path::new(input_file))
+        .with_context(|| format!("解析文件失败: {input_file}"))?;
+
+    let dir = matches.get_one::<String>("path").unwrap();
+    status!("📂 遍历目录: {dir}");
+    let mut walker = DirWalker::new();
+    if matches.get_flag("use_gitignore") {
+        walker = walker.use_gitignore();
+    }
+    let disk = walker
+        .walk(Path::new(dir), matches.get_flag("include_hidden"))
+        .with_context(|| format!("遍历目录失败: {dir}"))?;
+
+    // 差异写到标准输出（--report时标准输出留给运行摘要，改写到标准错误）
+    let report = VerifyReport::new(sheet, disk);
+    let mut differences = Vec::new();
+    for path in &report.missing {
+        differences.push(format!("缺失\t{path}"));
+    }
+    for path in &report.unlisted {
+        differences.push(format!("未登记\t{path}"));
+    }
+    for (path, expected, actual) in &report.mismatched {
+        differences.push(format!(
+            "大小不符\t{path}\t表格 {expected} 字节，实际 {actual} 字节"
+        ));
+    }
+    for line in differences {
+        if REPORT_JSON.load(Ordering::Relaxed) {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
         }
+    }
 
-        Ok(())
+    if report.is_ok() {
+        status!("✅ 校验通过：清单与目录一致");
+        return Ok(());
     }
+    Err(VerifyError(
+        report.missing.len(),
+        report.unlisted.len(),
+        report.mismatched.len(),
+    )
+    .into())
+}
 
-    /// 写入数据并实现层级合并单元格
-    fn write_data_with_merging(
-        &self,
-        worksheet: &mut Worksheet,
-        rows: &[&ExcelRow],
-        max_level: usize,
-        formats: &ExcelFormats,
-        current_row: &mut u32,
-    ) -> Result<()> {
-        if rows.is_empty() {
-            return Ok(());
-        }
+/// --s3：列出存储桶中的对象
+#[cfg(feature = "s3")]
+fn list_s3(url: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+    Ok(tree_to_excel::S3Lister::new().list(url, include_hidden)?)
+}
 
-        // 先写入所有单元格内容
-        for (row_idx, row) in rows.iter().enumerate() {
-            let row_num = *current_row + row_idx as u32;
-
-            // 层级列：写入每个层级的内容
-            for (level_idx, level_name) in row.levels.iter().enumerate() {
-                if !level_name.is_empty() {
-                    let format = if row.is_file && level_idx == row.levels.len() - 1 {
-                        &formats.file_format
-                    } else {
-                        &formats.dir_format
-                    };
-                    worksheet.write_with_format(row_num, level_idx as u16, level_name, format)?;
-                }
-            }
+/// 未启用s3特性时提示重新编译
+#[cfg(not(feature = "s3"))]
+fn list_s3(_url: &str, _include_hidden: bool) -> Result<Vec<TreeItem>> {
+    anyhow::bail!("--s3 需要启用s3特性重新编译: cargo build --release --features s3")
+}
 
-            // 完整路径列
-            let path_col = max_level as u16;
-            worksheet.write_with_format(row_num, path_col, &row.full_path, &formats.path_format)?;
+/// --ssh：通过SFTP列出远程目录，返回(主机名, 项目列表)
+#[cfg(feature = "ssh")]
+fn list_sftp(target: &str, port: u16, include_hidden: bool) -> Result<(String, Vec<TreeItem>)> {
+    let target = tree_to_excel::SshTarget::parse(target)?;
+    let items = tree_to_excel::SftpLister::new()
+        .port(port)
+        .list(&target, include_hidden)?;
+    Ok((target.host, items))
+}
 
-            // 备注列
-            let notes_col = max_level as u16 + 1;
-            worksheet.write_with_format(row_num, notes_col, "", &formats.notes_format)?;
-        }
+/// 未启用ssh特性时提示重新编译
+#[cfg(not(feature = "ssh"))]
+fn list_sftp(_target: &str, _port: u16, _include_hidden: bool) -> Result<(String, Vec<TreeItem>)> {
+    anyhow::bail!("--ssh 需要启用ssh特性重新编译: cargo build --release --features ssh")
+}
 
-        // 然后实现合并单元格逻辑
-        for level_idx in 0..max_level {
-            self.merge_level_column(
-                worksheet,
-                rows,
-                level_idx,
-                *current_row,
-                &formats.dir_format,
-            )?;
-        }
+/// --webdav：列出WebDAV目录，返回(主机名, 项目列表)
+#[cfg(feature = "webdav")]
+fn list_webdav(url: &str, include_hidden: bool) -> Result<(String, Vec<TreeItem>)> {
+    let items = tree_to_excel::WebDavLister::new().list(url, include_hidden)?;
+    let host = url.split("://").nth(1).unwrap_or(url);
+    let host = host.split('/').next().unwrap_or(host);
+    Ok((host.rsplit('@').next().unwrap_or(host).to_string(), items))
+}
 
-        *current_row += rows.len() as u32;
-        Ok(())
-    }
-
-    /// 合并指定层级列的单元格
-    fn merge_level_column(
-        &self,
-        worksheet: &mut Worksheet,
-        rows: &[&ExcelRow],
-        level_idx: usize,
-        start_row: u32,
-        dir_format: &Format,
-    ) -> Result<()> {
-        let mut i = 0;
-        while i < rows.len() {
-            let current_value = &rows[i].levels[level_idx];
-
-            // 跳过空值
-            if current_value.is_empty() {
-                i += 1;
-                continue;
-            }
+/// 未启用webdav特性时提示重新编译
+#[cfg(not(feature = "webdav"))]
+fn list_webdav(_url: &str, _include_hidden: bool) -> Result<(String, Vec<TreeItem>)> {
+    anyhow::bail!("--webdav 需要启用webdav特性重新编译: cargo build --release --features webdav")
+}
 
-            // 找到相同值的连续范围，考虑前面层级的约束
-            let mut j = i + 1;
-            while j < rows.len() {
-                // 检查当前层级值是否相同
-                if rows[j].levels[level_idx] != *current_value {
-                    break;
-                }
+/// 读取--baseline：.xlsx为之前生成的Excel，.json为--snapshot快照（或tree -J输出），
+/// 其他按--format/--dialect作为tree输出解析
+fn read_baseline(
+    matches: &ArgMatches,
+    baseline_path: &str,
+    include_hidden: bool,
+) -> Result<Vec<TreeItem>> {
+    let extension = Path::new(baseline_path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+    let items = match extension.as_deref() {
+        Some("xlsx") => XlsxTreeParser::new()
+            .parse_file(Path::new(baseline_path))
+            .map_err(anyhow::Error::from),
+        Some("json") => fs::read_to_string(baseline_path)
+            .with_context(|| format!("无法读取文件: {baseline_path}"))
+            .and_then(|content| Ok(JsonTreeParser::new().parse(&content, include_hidden)?)),
+        _ => fs::File::open(baseline_path)
+            .with_context(|| format!("无法读取文件: {baseline_path}"))
+            .and_then(|file| parse_input(matches, BufReader::new(file), include_hidden)),
+    };
+    items.with_context(|| format!("解析基线失败: {baseline_path}"))
+}
 
-                // 检查前面的层级是否也相同（重要：确保是同一个父目录下）
-                let mut same_parent = true;
-                for prev_level in 0..level_idx {
-                    if rows[i].levels[prev_level] != rows[j].levels[prev_level] {
-                        same_parent = false;
-                        break;
-                    }
-                }
+/// --low-memory：tree文本逐行解析并直接写入Excel，不在内存中保留项目列表
+fn generate_low_memory(
+    matches: &ArgMatches,
+    output_path: &str,
+    include_hidden: bool,
+) -> Result<()> {
+    if !matches!(
+        matches.get_one::<String>("format").map(String::as_str),
+        Some("auto" | "tree")
+    ) {
+        anyhow::bail!("--low-memory 只支持tree文本输入");
+    }
+    if matches.get_one::<String>("to").map(String::as_str) != Some("xlsx") {
+        anyhow::bail!("--low-memory 只支持xlsx输出");
+    }
+    match matches.get_one::<String>("dialect").map(String::as_str) {
+        Some("windows") => anyhow::bail!("--low-memory 不支持Windows tree /F输出"),
+        Some("ls") => anyhow::bail!("--low-memory 不支持ls -lR输出"),
+        _ => {}
+    }
 
-                if !same_parent {
-                    break;
-                }
+    let reader: Box<dyn BufRead> = match matches.get_many::<String>("input") {
+        Some(input_files) => {
+            let input_files: Vec<&String> = input_files.collect();
+            if input_files.len() > 1 {
+                anyhow::bail!("--low-memory 只支持单个输入文件");
+            }
+            status!("📖 读取tree输出文件: {}", input_files[0]);
+            let file = fs::File::open(input_files[0])
+                .with_context(|| format!("无法读取文件: {}", input_files[0]))?;
+            Box::new(BufReader::new(file))
+        }
+        None => {
+            status!("📖 从标准输入读取tree输出（Ctrl+D结束）:");
+            Box::new(io::stdin().lock())
+        }
+    };
 
-                j += 1;
+    let reader = input_encoding(matches)?.reader(reader);
+    status!("🔄 边解析边写入Excel文件（低内存模式）: {output_path}");
+    let no_stats = matches.get_flag("no_stats");
+    let lines_read = std::cell::Cell::new(0);
+    let lines = reader
+        .lines()
+        .inspect(|_| lines_read.set(lines_read.get() + 1));
+    let mut stream = TreeParser::new()
+        .parse_lines(lines, include_hidden)
+        .context("解析tree输出失败")?;
+    let stats_rows = std::cell::Cell::new(0);
+    let items = stream
+        .by_ref()
+        .filter(|item| !(no_stats && item.as_ref().is_ok_and(|item| item.name.starts_with("📊"))))
+        .inspect(|item| {
+            if item.as_ref().is_ok_and(|item| item.name.starts_with("📊")) {
+                stats_rows.set(stats_rows.get() + 1);
             }
+        });
 
-            // 如果有多行相同值，进行合并
-            if j - i > 1 {
-                let start_merge_row = start_row + i as u32;
-                let end_merge_row = start_row + (j - 1) as u32;
-
-                worksheet.merge_range(
-                    start_merge_row,
-                    level_idx as u16,
-                    end_merge_row,
-                    level_idx as u16,
-                    current_value,
-                    dir_format,
-                )?;
+    let generator = excel_generator(matches)?;
+    let row_count = generator
+        .generate_streaming(items, output_path, |rows| {
+            if rows % PROGRESS_INTERVAL == 0 {
+                progress!("\r⏳ 已读取 {} 行，已写入 {rows} 行", lines_read.get());
             }
+        })
+        .context("生成Excel文件失败")?;
+    if row_count >= PROGRESS_INTERVAL {
+        progress!(
+            "\r⏳ 已读取 {} 行，已写入 {row_count} 行\n",
+            lines_read.get()
+        );
+    }
 
-            i = j;
+    if let Ok(mut report) = REPORT.lock() {
+        report.output = Some(output_path.to_string());
+        report.items = row_count;
+        report.rows_written = row_count + stats_rows.get();
+    }
+    // 边解析边写入，--strict失败时删除已写入的文件
+    if let Err(err) = check_skipped(matches, stream.skipped_lines()) {
+        if output_path != "-" {
+            let _ = fs::remove_file(output_path);
         }
-
-        Ok(())
+        return Err(err);
     }
-}
-
-fn main() -> Result<()> {
-    let matches = Command::new("tree-to-excel")
-        .about("将tree命令输出转换为Excel表格，支持合并单元格层级展示")
-        .version("1.0")
-        .arg(
-            Arg::new("input")
-                .short('i')
-                .long("input")
-                .value_name("FILE")
-                .help("输入文件路径（tree命令输出）"),
-        )
-        .arg(
-            Arg::new("output")
-                .short('o')
-                .long("output")
-                .value_name("FILE")
-                .help("输出Excel文件路径")
-                .default_value("tree_output.xlsx"),
-        )
-        .arg(
-            Arg::new("include_hidden")
-                .short('a')
-                .long("include-hidden")
-                .action(clap::ArgAction::SetTrue)
-                .help("包含隐藏目录/文件（以.开头的项目，如.git）"),
-        )
-        .get_matches();
 
-    // 读取输入
-    let input_content = if let Some(input_file) = matches.get_one::<String>("input") {
-        println!("📖 读取tree输出文件: {input_file}");
-        fs::read_to_string(input_file).with_context(|| format!("无法读取文件: {input_file}"))?
-    } else {
-        println!("📖 从标准输入读取tree输出（Ctrl+D结束）:");
-        let mut buffer = String::new();
-        io::stdin()
-            .read_to_string(&mut buffer)
-            .context("无法从标准输入读取")?;
-        buffer
-    };
+    status!("📊 写入 {row_count} 个文件/目录");
+    status!("✅ 完成！Excel文件已保存");
 
-    let output_path = matches.get_one::<String>("output").unwrap();
-    let include_hidden = matches.get_flag("include_hidden");
+    Ok(())
+}
 
+/// 按 --format/--dialect 选择输入方言，逐行读入解析，超大输入时显示进度
+fn parse_input(
+    matches: &ArgMatches,
+    reader: impl BufRead,
+    include_hidden: bool,
+) -> Result<Vec<TreeItem>> {
     if include_hidden {
-        println!("🔄 解析tree结构（包含隐藏目录）...");
+        status!("🔄 解析tree结构（包含隐藏目录）...");
     } else {
-        println!("🔄 解析tree结构（默认忽略.git等隐藏目录）...");
+        status!("🔄 解析tree结构（默认忽略.git等隐藏目录）...");
     }
 
-    // 解析tree输出
-    let parser = TreeParser::new();
-    let items = parser
-        .parse(&input_content, include_hidden)
-        .context("解析tree输出失败")?;
-
-    println!("📊 找到 {} 个文件/目录", items.len());
-
-    // 生成Excel
-    println!("📝 生成Excel文件: {output_path}");
-    let generator = ExcelGenerator::new();
-    generator
-        .generate(items, output_path)
-        .context("生成Excel文件失败")?;
-
-    println!("✅ 完成！Excel文件已保存");
+    // 未指定-f时先读入前50行识别格式，tree文本再识别方言（指定--dialect时按tree文本解析）
+    let mut lines = input_encoding(matches)?.reader(reader).lines();
+    let mut prefix = Vec::new();
+    let dialect = matches
+        .get_one::<String>("dialect")
+        .map(String::as_str)
+        .filter(|dialect| *dialect != "auto");
+    let mut format = match matches.get_one::<String>("format").map(String::as_str) {
+        Some("auto") | None if dialect.is_some() => "tree",
+        Some(format) => format,
+        None => "auto",
+    };
+    if matches!(format, "auto" | "tree") {
+        prefix = lines
+            .by_ref()
+            .take(50)
+            .collect::<io::Result<_>>()
+            .context("无法读取输入")?;
+    }
+    if format == "auto" {
+        format = detect_format(&prefix.join("\n"));
+        if format != "tree" {
+            status!("🔍 自动识别为{format}格式（可用-f指定）");
+        }
+    }
+    let name = match format {
+        "tree" => dialect.unwrap_or_else(|| detect_dialect(&prefix.join("\n"))),
+        format => format,
+    };
+    log::debug!("输入方言: {name}");
+    let mut dialect =
+        input_dialect(name, include_hidden).with_context(|| format!("不支持的输入格式: {name}"))?;
+    match name {
+        "ls" => status!("📂 按ls -lR格式解析"),
+        "windows" => status!("🪟 按Windows tree /F格式解析"),
+        _ => {}
+    }
 
-    Ok(())
+    // 逐行读入，每10万行输出一次进度
+    let mut lines_read = 0;
+    for line in prefix.into_iter().map(Ok).chain(lines) {
+        dialect.parse(&line.context("无法读取输入")?)?;
+        lines_read += 1;
+        if lines_read % PROGRESS_INTERVAL == 0 {
+            progress!("\r⏳ 已读取 {lines_read} 行");
+        }
+    }
+    let items = dialect
+        .finish()
+        .with_context(|| format!("解析{}失败", dialect.description()))?;
+    if lines_read >= PROGRESS_INTERVAL {
+        progress!("\r⏳ 已读取 {lines_read} 行，解析出 {} 项\n", items.len());
+    }
+    check_skipped(matches, dialect.skipped_lines())?;
+    Ok(items)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_line() {
-        let parser = TreeParser::new();
-
-        let test_cases = vec![
-            ("├── src", Some((1, "src".to_string()))),
-            ("│   ├── main.rs", Some((2, "main.rs".to_string()))),
-            ("│   │   └── lib.rs", Some((3, "lib.rs".to_string()))),
-        ];
+/// --encoding指定的输入编码
+fn input_encoding(matches: &ArgMatches) -> Result<InputEncoding> {
+    let label = matches.get_one::<String>("encoding").unwrap();
+    InputEncoding::from_label(label)
+        .with_context(|| format!("不支持的编码: {label}（如utf-8、gbk、cp437、latin1、utf-16le）"))
+}
 
-        for (input, expected) in test_cases {
-            let result = parser.parse_line(input);
-            assert_eq!(result, expected, "Failed for input: {input}");
-        }
+/// 处理无法解析的tree行：--strict时列出行号并返回StrictError，否则警告跳过的行数
+fn check_skipped(matches: &ArgMatches, skipped: &[SkippedLine]) -> Result<()> {
+    if skipped.is_empty() {
+        return Ok(());
+    }
+    if let Ok(mut report) = REPORT.lock() {
+        report.skipped_lines += skipped.len();
+    }
+    if !matches.get_flag("strict") {
+        log::warn!(
+            "{} 行无法按tree格式解析，已跳过（-v显示具体的行，--strict时视为错误）",
+            skipped.len()
+        );
+        return Ok(());
+    }
+    for line in skipped.iter().take(STRICT_SHOWN_LINES) {
+        log::error!("第 {} 行无法解析: {}", line.line_number, line.text);
+    }
+    if skipped.len() > STRICT_SHOWN_LINES {
+        log::error!("……另有 {} 行无法解析", skipped.len() - STRICT_SHOWN_LINES);
     }
+    Err(StrictError(skipped.len()).into())
 }
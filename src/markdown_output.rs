@@ -0,0 +1,105 @@
+//! Markdown表格输出（--to markdown）
+
+use anyhow::Context;
+use std::io::Write;
+
+use crate::error::Result;
+use crate::excel::{convert_to_rows, level_spans};
+use crate::i18n::Language;
+use crate::output::open_output;
+use crate::parser::TreeItem;
+
+/// Markdown表格生成器：层级列中同一父目录只在首行显示，模拟合并单元格的效果
+#[derive(Debug, Default)]
+pub struct MarkdownGenerator {
+    lang: Language, // 表头及统计标签的语言
+}
+
+impl MarkdownGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置表头及统计标签的语言
+    pub fn lang(mut self, lang: Language) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// 生成Markdown文件（"-"表示标准输出）
+    pub fn generate(&self, items: Vec<TreeItem>, output_path: &str) -> Result<()> {
+        let mut writer = open_output(output_path)?;
+        writer
+            .write_all(self.render(items).as_bytes())
+            .and_then(|()| writer.flush())
+            .with_context(|| format!("无法保存Markdown文件: {output_path}"))?;
+        Ok(())
+    }
+
+    /// 渲染为Markdown表格，统计信息写在表格之后
+    pub fn render(&self, items: Vec<TreeItem>) -> String {
+        let rows = convert_to_rows(items);
+        let max_level = rows.first().map_or(1, |row| row.max_level);
+        let (stats_rows, data_rows): (Vec<_>, Vec<_>) =
+            rows.iter().partition(|row| row.levels[0].starts_with("📊"));
+        let spans = level_spans(&data_rows);
+
+        let mut content = String::from("|");
+        for level in 1..=max_level {
+            content.push_str(&format!(" L{level} |"));
+        }
+        content.push_str(&format!(
+            " {} |\n|",
+            self.lang.text("完整路径", "Full Path")
+        ));
+        content.push_str(&" --- |".repeat(max_level + 1));
+        content.push('\n');
+
+        for (row, row_spans) in data_rows.iter().zip(&spans) {
+            content.push('|');
+            for (level_name, span) in row.levels.iter().zip(row_spans) {
+                // 被合并范围覆盖的单元格留空
+                let cell = if *span == 0 { "" } else { level_name };
+                content.push_str(&format!(" {} |", self.escape(cell)));
+            }
+            // GFM在代码段内同样按 "|" 拆分单元格，需转义（代码段内反斜杠无需转义）
+            let path = row.full_path.replace('`', "'").replace('|', "\\|");
+            content.push_str(&format!(" `{path}` |\n"));
+        }
+
+        for stats_row in stats_rows {
+            content.push_str(&format!(
+                "\n{}\n",
+                self.lang.stats_label(&stats_row.levels[0])
+            ));
+        }
+        content
+    }
+
+    /// 转义表格单元格中的特殊字符
+    fn escape(&self, text: &str) -> String {
+        text.replace('\\', "\\\\").replace('|', "\\|")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_render_markdown() {
+        let input =
+            ".\n├── src\n│   ├── a|b.rs\n│   └── lib.rs\n└── README.md\n\n1 directory, 3 files\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        assert_eq!(
+            MarkdownGenerator::new().render(items),
+            "| L1 | L2 | 完整路径 |\n| --- | --- | --- |\n\
+             | src |  | `src` |\n\
+             |  | a\\|b.rs | `src/a\\|b.rs` |\n\
+             |  | lib.rs | `src/lib.rs` |\n\
+             | README.md |  | `README.md` |\n\
+             \n📊 统计: 1 directories, 3 files\n"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! ncdu导出文件（ncdu -o scan.json）解析
+
+use anyhow::Context;
+use chrono::{DateTime, Local};
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::flat::{build_tree, mode_string, FlatEntry};
+use crate::parser::TreeItem;
+
+/// ncdu导出文件解析器：格式为 [主版本, 次版本, 元数据, 根目录]，
+/// 目录为以自身信息开头的数组，文件为对象
+#[derive(Debug, Default)]
+pub struct NcduParser;
+
+impl NcduParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 解析ncdu导出的JSON，返回与TreeParser::parse相同结构的项目列表。
+    /// 大小取表观大小（asize，与tree -s及目录遍历一致），根目录的名称记录在项目的root中
+    pub fn parse(&self, input: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let document: Value = serde_json::from_str(input).context("无法解析ncdu导出的JSON")?;
+        let Some(Value::Array(root)) = document.get(3) else {
+            return Err(Error::parse(None, "不是ncdu导出文件（缺少根目录）"));
+        };
+        let root_name = root
+            .first()
+            .and_then(|info| info["name"].as_str())
+            .unwrap_or(".")
+            .to_string();
+
+        let mut entries = Vec::new();
+        for child in &root[1..] {
+            self.collect(child, "", &mut entries);
+        }
+        let mut items = build_tree(entries, include_hidden);
+        for item in items.iter_mut().filter(|item| !item.name.starts_with("📊")) {
+            item.root = Some(root_name.clone());
+        }
+        Ok(items)
+    }
+
+    /// 递归收集条目（排除的项目未被扫描，不输出）
+    fn collect(&self, node: &Value, parent_path: &str, entries: &mut Vec<FlatEntry>) {
+        let (info, children) = match node {
+            Value::Array(array) => match array.split_first() {
+                Some((info, children)) => (info, Some(children)),
+                None => return,
+            },
+            info => (info, None),
+        };
+        let Some(name) = info["name"].as_str() else {
+            return;
+        };
+        if info.get("excluded").is_some() {
+            return;
+        }
+        let path = if parent_path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{parent_path}/{name}")
+        };
+
+        let is_dir = children.is_some();
+        let mode = info["mode"].as_u64().map(|mode| mode as u32);
+        let type_char = match mode.map(|mode| mode & 0o170000) {
+            _ if is_dir => 'd',
+            Some(0o120000) => 'l',
+            _ => '-',
+        };
+        entries.push(FlatEntry {
+            path: path.clone(),
+            is_dir,
+            size: info["asize"].as_u64().filter(|_| !is_dir),
+            modified: info["mtime"]
+                .as_i64()
+                .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+                .map(|datetime| datetime.with_timezone(&Local).naive_local()),
+            permissions: mode.map(|mode| mode_string(type_char, mode)),
+            error: (info["read_error"].as_bool() == Some(true)).then(|| "read error".to_string()),
+            ..Default::default()
+        });
+        for child in children.into_iter().flatten() {
+            self.collect(child, &path, entries);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ncdu() {
+        let input = r#"[1,2,{"progname":"ncdu","progver":"1.19","timestamp":1700000000},
+[{"name":"/srv/data","asize":4096,"dsize":4096},
+ {"name":"backup.tar","asize":1048576,"dsize":1052672,"mtime":1700000000,"mode":33188},
+ [{"name":"logs","asize":4096,"dsize":4096},
+  {"name":"app.log","asize":2048,"dsize":4096}],
+ [{"name":"locked","asize":4096,"read_error":true}],
+ {"name":"tmp","excluded":"pattern"},
+ {"name":".cache","asize":10}
+]]"#;
+        let items = NcduParser::new().parse(input, false).unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file, item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("backup.tar", true, Some(1048576)),
+                ("logs", false, None),
+                ("logs/app.log", true, Some(2048)),
+                ("locked", false, None),
+                ("📊 统计: 2 directories, 2 files", false, None),
+            ]
+        );
+        assert_eq!(items[0].root.as_deref(), Some("/srv/data"));
+        assert_eq!(items[0].permissions.as_deref(), Some("-rw-r--r--"));
+        assert_eq!(items[3].error.as_deref(), Some("read error"));
+    }
+}
@@ -0,0 +1,265 @@
+//! ODS（OpenDocument电子表格）输出（--to ods）
+
+use anyhow::Context;
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::error::Result;
+use crate::output::{open_output, Cell, CellKind, CellValue, OutputWriter, TableBuffer};
+
+/// 与Excel输出一致的单元格样式
+const CELL_STYLES: &str = r##"<style:style style:name="header" style:family="table-cell"><style:table-cell-properties fo:background-color="#4F81BD" fo:border="0.5pt solid #000000"/><style:text-properties fo:color="#FFFFFF" fo:font-weight="bold"/></style:style>
+<style:style style:name="dir" style:family="table-cell"><style:table-cell-properties fo:background-color="#E8F4FD" fo:border="0.5pt solid #000000" style:vertical-align="middle"/><style:paragraph-properties fo:text-align="center"/><style:text-properties fo:font-weight="bold"/></style:style>
+<style:style style:name="file" style:family="table-cell"><style:table-cell-properties fo:background-color="#F0F8E8" fo:border="0.5pt solid #000000"/></style:style>
+<style:style style:name="path" style:family="table-cell"><style:table-cell-properties fo:background-color="#FFFEF7" fo:border="0.5pt solid #000000"/></style:style>
+<style:style style:name="plain" style:family="table-cell"><style:table-cell-properties fo:border="0.5pt solid #000000"/></style:style>
+<number:date-style style:name="datetime"><number:year number:style="long"/><number:text>-</number:text><number:month number:style="long"/><number:text>-</number:text><number:day number:style="long"/><number:text> </number:text><number:hours number:style="long"/><number:text>:</number:text><number:minutes number:style="long"/></number:date-style>
+<style:style style:name="date" style:family="table-cell" style:data-style-name="datetime"><style:table-cell-properties fo:border="0.5pt solid #000000"/></style:style>
+<style:style style:name="notes" style:family="table-cell"><style:table-cell-properties fo:background-color="#F5F5F5" fo:border="0.5pt solid #000000"/></style:style>
+<style:style style:name="stats" style:family="table-cell"><style:table-cell-properties fo:background-color="#FFE4E1" fo:border="0.5pt solid #000000"/><style:text-properties fo:color="#8B0000" fo:font-weight="bold"/></style:style>"##;
+
+/// 每个字符对应的列宽（厘米），与Excel的列宽单位大致相当
+const CM_PER_CHAR: f64 = 0.175;
+
+const MANIFEST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
+ <manifest:file-entry manifest:full-path="/" manifest:version="1.2" manifest:media-type="application/vnd.oasis.opendocument.spreadsheet"/>
+ <manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>
+"#;
+
+/// ODS输出后端：缓存全部行，保存时按合并范围写出跨行/跨列单元格
+#[derive(Debug)]
+pub struct OdsWriter {
+    output_path: String,
+    table: TableBuffer,
+}
+
+impl OdsWriter {
+    pub fn new(output_path: &str) -> Self {
+        Self {
+            output_path: output_path.to_string(),
+            table: TableBuffer::default(),
+        }
+    }
+
+    /// 生成content.xml
+    fn content_xml(&self) -> String {
+        // 合并范围首个单元格的(跨行数, 跨列数)，及被覆盖的单元格
+        let mut spans = HashMap::new();
+        let mut covered = HashSet::new();
+        for &(first_row, first_col, last_row, last_col) in &self.table.merges {
+            spans.insert(
+                (first_row, first_col),
+                (last_row - first_row + 1, last_col - first_col + 1),
+            );
+            for row in first_row..=last_row {
+                for col in first_col..=last_col {
+                    if (row, col) != (first_row, first_col) {
+                        covered.insert((row, col));
+                    }
+                }
+            }
+        }
+
+        let mut content = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" xmlns:number="urn:oasis:names:tc:opendocument:xmlns:datastyle:1.0" office:version="1.2">
+<office:automatic-styles>
+"#,
+        );
+        content.push_str(CELL_STYLES);
+        // 每列一个列宽样式
+        for (col, width) in self.table.widths.iter().enumerate() {
+            content.push_str(&format!(
+                "\n<style:style style:name=\"co{col}\" style:family=\"table-column\"><style:table-column-properties style:column-width=\"{:.2}cm\"/></style:style>",
+                width * CM_PER_CHAR
+            ));
+        }
+        content.push_str("\n</office:automatic-styles>\n<office:body><office:spreadsheet><table:table table:name=\"Sheet1\">\n");
+        for col in 0..self.table.widths.len() {
+            content.push_str(&format!(
+                "<table:table-column table:style-name=\"co{col}\"/>\n"
+            ));
+        }
+
+        for (row_idx, row) in self.table.rows.iter().enumerate() {
+            content.push_str("<table:table-row>");
+            for (col_idx, cell) in row.iter().enumerate() {
+                if covered.contains(&(row_idx, col_idx)) {
+                    content.push_str("<table:covered-table-cell/>");
+                    continue;
+                }
+
+                let style = match cell.kind {
+                    CellKind::Header => "header",
+                    CellKind::Directory => "dir",
+                    CellKind::File => "file",
+                    CellKind::Path => "path",
+                    CellKind::Size => "plain",
+                    CellKind::Date => "date",
+                    CellKind::Notes => "notes",
+                    CellKind::Stats => "stats",
+                };
+                let span = match spans.get(&(row_idx, col_idx)) {
+                    Some((rows, cols)) => format!(
+                        " table:number-rows-spanned=\"{rows}\" table:number-columns-spanned=\"{cols}\""
+                    ),
+                    None => String::new(),
+                };
+                match &cell.value {
+                    CellValue::Empty => content.push_str(&format!(
+                        "<table:table-cell table:style-name=\"{style}\"{span}/>"
+                    )),
+                    CellValue::Text(text) => content.push_str(&format!(
+                        "<table:table-cell table:style-name=\"{style}\"{span} office:value-type=\"string\"><text:p>{}</text:p></table:table-cell>",
+                        self.escape(text)
+                    )),
+                    CellValue::Number(number) => content.push_str(&format!(
+                        "<table:table-cell table:style-name=\"{style}\"{span} office:value-type=\"float\" office:value=\"{number}\"><text:p>{number}</text:p></table:table-cell>"
+                    )),
+                    CellValue::DateTime(datetime) => content.push_str(&format!(
+                        "<table:table-cell table:style-name=\"{style}\"{span} office:value-type=\"date\" office:date-value=\"{}\"><text:p>{}</text:p></table:table-cell>",
+                        datetime.format("%Y-%m-%dT%H:%M:%S"),
+                        datetime.format("%Y-%m-%d %H:%M")
+                    )),
+                }
+            }
+            content.push_str("</table:table-row>\n");
+        }
+
+        content.push_str(
+            "</table:table></office:spreadsheet></office:body>\n</office:document-content>\n",
+        );
+        content
+    }
+
+    /// 转义XML特殊字符
+    fn escape(&self, text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
+
+impl OutputWriter for OdsWriter {
+    fn write_header(&mut self, headers: &[String]) -> Result<()> {
+        self.table.push_header(headers);
+        Ok(())
+    }
+
+    fn write_row(&mut self, cells: &[Cell]) -> Result<()> {
+        self.table.push_row(cells);
+        Ok(())
+    }
+
+    fn set_column_widths(&mut self, widths: &[f64]) -> Result<()> {
+        self.table.widths = widths.to_vec();
+        Ok(())
+    }
+
+    fn merge(
+        &mut self,
+        first_row: usize,
+        first_col: usize,
+        last_row: usize,
+        last_col: usize,
+    ) -> Result<()> {
+        self.table
+            .push_merge(first_row, first_col, last_row, last_col);
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<()> {
+        // 先在内存中生成，以便同样支持写到标准输出（"-"）
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+        // mimetype必须是第一个且不压缩
+        zip.start_file(
+            "mimetype",
+            SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+        )?;
+        zip.write_all(b"application/vnd.oasis.opendocument.spreadsheet")?;
+
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip.start_file("META-INF/manifest.xml", options)?;
+        zip.write_all(MANIFEST.as_bytes())?;
+        zip.start_file("content.xml", options)?;
+        zip.write_all(self.content_xml().as_bytes())?;
+        let buffer = zip.finish()?.into_inner();
+
+        let mut writer = open_output(&self.output_path)?;
+        writer
+            .write_all(&buffer)
+            .and_then(|()| writer.flush())
+            .with_context(|| format!("无法保存ODS文件: {}", self.output_path))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Language;
+    use crate::output::write_table;
+    use crate::parser::{TreeItem, TreeParser};
+
+    #[test]
+    fn test_ods_merged_cells() {
+        let input = ".\n├── src\n│   ├── a.rs\n│   └── b.rs\n└── README.md\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+
+        let path = std::env::temp_dir().join("tree_to_excel_test.ods");
+        let mut writer = OdsWriter::new(path.to_str().unwrap());
+        write_table(&mut writer, items, Language::Zh, &[]).unwrap();
+        assert_eq!(writer.table.merges[0], (1, 0, 3, 0));
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(archive.by_index(0).unwrap().name(), "mimetype");
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("content.xml").unwrap(), &mut content)
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(content.contains(
+            r#"<table:table-cell table:style-name="dir" table:number-rows-spanned="3" table:number-columns-spanned="1" office:value-type="string"><text:p>src</text:p>"#
+        ));
+        // 层级列宽20，完整路径列宽60
+        assert!(content.contains(r#"style:name="co0" style:family="table-column"><style:table-column-properties style:column-width="3.50cm"/>"#));
+        assert!(content.contains(r#"style:name="co2" style:family="table-column"><style:table-column-properties style:column-width="10.50cm"/>"#));
+        // src下的2行及统计行中的3列被覆盖
+        assert_eq!(
+            content.matches("<table:covered-table-cell/>").count(),
+            2 + 3
+        );
+    }
+
+    #[test]
+    fn test_ods_date_cells() {
+        let modified = chrono::NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(12, 34, 56)
+            .unwrap();
+        let items = vec![TreeItem {
+            name: "a.rs".to_string(),
+            level: 1,
+            is_file: true,
+            full_path: "a.rs".to_string(),
+            modified: Some(modified),
+            ..Default::default()
+        }];
+
+        let path = std::env::temp_dir().join("tree_to_excel_test_date.ods");
+        let mut writer = OdsWriter::new(path.to_str().unwrap());
+        write_table(&mut writer, items, Language::Zh, &[]).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let content = writer.content_xml();
+        assert!(content.contains(
+            r#"<table:table-cell table:style-name="date" office:value-type="date" office:date-value="2024-01-15T12:34:56"><text:p>2024-01-15 12:34</text:p>"#
+        ));
+    }
+}
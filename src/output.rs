@@ -0,0 +1,216 @@
+//! 可插拔的表格输出后端
+
+use anyhow::Context;
+use chrono::NaiveDateTime;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::sync::Arc;
+
+use crate::column::{ColumnProvider, ModifiedColumn, NotesColumn, PathColumn, SizeColumn};
+use crate::error::Result;
+use crate::excel::{convert_to_rows, level_spans};
+use crate::i18n::Language;
+use crate::parser::TreeItem;
+
+/// 打开输出目标，"-"表示标准输出
+pub(crate) fn open_output(output_path: &str) -> Result<Box<dyn Write>> {
+    if output_path == "-" {
+        return Ok(Box::new(io::stdout().lock()));
+    }
+    let file =
+        File::create(output_path).with_context(|| format!("无法创建输出文件: {output_path}"))?;
+    Ok(Box::new(BufWriter::new(file)))
+}
+
+/// 单元格的值
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    Empty,
+    Text(String),
+    Number(f64),
+    DateTime(NaiveDateTime), // 日期时间，后端写为日期值以便排序/筛选
+}
+
+/// 单元格的用途，后端据此选择样式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    Header,    // 表头
+    Directory, // 目录（层级列中的上级目录及目录本身）
+    File,      // 文件
+    Path,      // 完整路径
+    Size,      // 大小
+    Date,      // 日期时间
+    Notes,     // 备注
+    Stats,     // 统计行
+}
+
+/// 一个单元格
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    pub value: CellValue,
+    pub kind: CellKind,
+}
+
+impl Cell {
+    pub fn new(value: CellValue, kind: CellKind) -> Self {
+        Self { value, kind }
+    }
+
+    /// 文本单元格，空字符串为空单元格
+    pub fn text(text: &str, kind: CellKind) -> Self {
+        let value = if text.is_empty() {
+            CellValue::Empty
+        } else {
+            CellValue::Text(text.to_string())
+        };
+        Self::new(value, kind)
+    }
+}
+
+/// 表格输出后端：按行写入单元格，最后统一处理合并范围并保存。
+/// 行号从0开始（第0行为表头）。内置实现只有OdsWriter，xlsx由ExcelGenerator直接生成
+pub trait OutputWriter {
+    /// 写入表头行
+    fn write_header(&mut self, headers: &[String]) -> Result<()>;
+
+    /// 追加一行数据
+    fn write_row(&mut self, cells: &[Cell]) -> Result<()>;
+
+    /// 设置各列的列宽（字符数），在表头之前调用；不支持列宽的后端忽略
+    fn set_column_widths(&mut self, _widths: &[f64]) -> Result<()> {
+        Ok(())
+    }
+
+    /// 合并单元格范围（包含首尾行列）
+    fn merge(
+        &mut self,
+        first_row: usize,
+        first_col: usize,
+        last_row: usize,
+        last_col: usize,
+    ) -> Result<()>;
+
+    /// 完成输出（保存文件等）
+    fn finalize(&mut self) -> Result<()>;
+}
+
+/// 缓存的整张表格：供保存时才一次写出的后端（如ODS）使用
+#[derive(Debug, Default)]
+pub(crate) struct TableBuffer {
+    pub(crate) rows: Vec<Vec<Cell>>,
+    pub(crate) merges: Vec<(usize, usize, usize, usize)>, // (首行, 首列, 末行, 末列)
+    pub(crate) widths: Vec<f64>,                          // 各列的列宽（字符数）
+}
+
+impl TableBuffer {
+    pub(crate) fn push_header(&mut self, headers: &[String]) {
+        self.rows.push(
+            headers
+                .iter()
+                .map(|header| Cell::text(header, CellKind::Header))
+                .collect(),
+        );
+    }
+
+    pub(crate) fn push_row(&mut self, cells: &[Cell]) {
+        self.rows.push(cells.to_vec());
+    }
+
+    pub(crate) fn push_merge(
+        &mut self,
+        first_row: usize,
+        first_col: usize,
+        last_row: usize,
+        last_col: usize,
+    ) {
+        self.merges.push((first_row, first_col, last_row, last_col));
+    }
+}
+
+/// 以层级列+完整路径（+大小、修改时间）+自定义列+备注的布局将项目列表写入输出后端，
+/// 层级列按Excel相同的规则合并，统计行跨所有列合并
+pub fn write_table(
+    writer: &mut dyn OutputWriter,
+    items: Vec<TreeItem>,
+    lang: Language,
+    custom_columns: &[Arc<dyn ColumnProvider>],
+) -> Result<()> {
+    let data_items = items.iter().filter(|item| !item.name.starts_with("📊"));
+    let mut columns: Vec<&dyn ColumnProvider> = Vec::new();
+    let (path, size, modified, notes) = (
+        PathColumn::new(lang),
+        SizeColumn::new(lang),
+        ModifiedColumn::new(lang),
+        NotesColumn::new(lang),
+    );
+    columns.push(&path);
+    if data_items.clone().any(|item| item.size.is_some()) {
+        columns.push(&size);
+    }
+    if data_items.clone().any(|item| item.modified.is_some()) {
+        columns.push(&modified);
+    }
+    columns.extend(custom_columns.iter().map(|column| column.as_ref()));
+    columns.push(&notes);
+    let values: Vec<Vec<Cell>> = data_items
+        .map(|item| {
+            columns
+                .iter()
+                .map(|column| Cell::new(column.value(item), column.kind()))
+                .collect()
+        })
+        .collect();
+
+    let rows = convert_to_rows(items);
+    let max_level = rows.first().map_or(1, |row| row.max_level);
+    let (stats_rows, data_rows): (Vec<_>, Vec<_>) =
+        rows.iter().partition(|row| row.levels[0].starts_with("📊"));
+
+    let mut headers: Vec<String> = (1..=max_level).map(|level| format!("L{level}")).collect();
+    headers.extend(columns.iter().map(|column| column.name().to_string()));
+    let total_cols = headers.len();
+    let mut widths = vec![20.0; max_level];
+    widths.extend(columns.iter().map(|column| column.width()));
+    writer.set_column_widths(&widths)?;
+    writer.write_header(&headers)?;
+
+    for (row, values) in data_rows.iter().zip(values) {
+        let leaf_idx = row.levels.iter().rposition(|name| !name.is_empty());
+        let mut cells: Vec<Cell> = row
+            .levels
+            .iter()
+            .enumerate()
+            .map(|(level_idx, level_name)| {
+                let kind = if Some(level_idx) == leaf_idx && row.is_file {
+                    CellKind::File
+                } else {
+                    CellKind::Directory
+                };
+                Cell::text(level_name, kind)
+            })
+            .collect();
+        cells.extend(values);
+        writer.write_row(&cells)?;
+    }
+
+    // 层级列合并
+    for (row_idx, row_spans) in level_spans(&data_rows).iter().enumerate() {
+        for (level_idx, span) in row_spans.iter().enumerate() {
+            if *span > 1 {
+                writer.merge(row_idx + 1, level_idx, row_idx + span, level_idx)?;
+            }
+        }
+    }
+
+    // 统计行
+    for (offset, stats_row) in stats_rows.iter().enumerate() {
+        let label = lang.stats_label(&stats_row.levels[0]);
+        let mut cells = vec![Cell::text(&label, CellKind::Stats)];
+        cells.resize(total_cols, Cell::new(CellValue::Empty, CellKind::Stats));
+        writer.write_row(&cells)?;
+        let row_idx = data_rows.len() + offset + 1;
+        writer.merge(row_idx, 0, row_idx, total_cols - 1)?;
+    }
+
+    writer.finalize()
+}
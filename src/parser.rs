@@ -0,0 +1,1411 @@
+//! tree命令输出解析
+
+use anyhow::Context;
+use chrono::{Datelike, Local, Month, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io;
+
+use crate::diff::Change;
+use crate::docker::ImageLayer;
+use crate::error::Result;
+use crate::git::GitInfo;
+use crate::hash::FileHash;
+use crate::i18n::Language;
+
+/// 条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    #[default]
+    File,
+    Directory,
+    Symlink,
+    Executable,
+    Socket,
+    Fifo,
+    Device,
+}
+
+impl EntryKind {
+    /// 根据tree -J/-X输出中的类型名称判断
+    pub(crate) fn from_tree_type(kind: &str) -> Self {
+        match kind {
+            "directory" => EntryKind::Directory,
+            "link" => EntryKind::Symlink,
+            "socket" => EntryKind::Socket,
+            "fifo" => EntryKind::Fifo,
+            "char" | "block" => EntryKind::Device,
+            _ => EntryKind::File,
+        }
+    }
+
+    /// tree -J/-X输出中的类型名称
+    pub(crate) fn tree_type(self) -> &'static str {
+        match self {
+            EntryKind::Directory => "directory",
+            EntryKind::Symlink => "link",
+            EntryKind::Socket => "socket",
+            EntryKind::Fifo => "fifo",
+            EntryKind::Device => "char",
+            EntryKind::File | EntryKind::Executable => "file",
+        }
+    }
+
+    /// 根据ls风格权限字符串判断，如 "drwxr-xr-x"
+    pub(crate) fn from_permissions(permissions: &str) -> Self {
+        match permissions.chars().next() {
+            Some('d') => EntryKind::Directory,
+            Some('l') => EntryKind::Symlink,
+            Some('s') => EntryKind::Socket,
+            Some('p') => EntryKind::Fifo,
+            Some('c' | 'b') => EntryKind::Device,
+            _ if permissions.contains(['x', 's', 't']) => EntryKind::Executable,
+            _ => EntryKind::File,
+        }
+    }
+}
+
+/// 各语言tree统计行中的(目录, 文件)关键词，如 "3 directories, 12 files"、
+/// "3 Verzeichnisse, 12 Dateien"（按小写匹配，兼容单复数）
+const STATS_KEYWORDS: &[(&str, &str)] = &[
+    ("director", "file"),         // 英语、意大利语（directori, file）
+    ("verzeichnis", "datei"),     // 德语
+    ("répertoire", "fichier"),    // 法语
+    ("repertoire", "fichier"),    // 法语（无重音）
+    ("directorio", "archivo"),    // 西班牙语
+    ("diretório", "arquivo"),     // 葡萄牙语
+    ("katalog", "fil"),           // 瑞典语、挪威语、丹麦语
+    ("каталог", "файл"),          // 俄语
+    ("目录", "文件"),             // 简体中文
+    ("目錄", "檔案"),             // 繁体中文
+    ("ディレクトリ", "ファイル"), // 日语
+    ("디렉터리", "파일"),         // 韩语
+];
+
+/// 文件/目录项；可序列化为JSON保存解析结果，处理后再交给生成器（缺少的字段取默认值）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TreeItem {
+    pub name: String,
+    pub level: usize,
+    pub is_file: bool,
+    pub kind: EntryKind,
+    pub full_path: String,
+    pub size: Option<u64>,               // 字节数（输入中提供时）
+    pub modified: Option<NaiveDateTime>, // 修改时间（输入中提供时）
+    pub permissions: Option<String>,     // 权限字符串，如 "drwxr-xr-x"（tree -p）
+    pub owner: Option<String>,           // 所有者（tree -u）
+    pub group: Option<String>,           // 所属组（tree -g）
+    pub link_target: Option<String>,     // 符号链接目标（"name -> target"）
+    pub error: Option<String>,           // 读取错误（如tree的 "[error opening dir]"）
+    pub root: Option<String>, // 所属根目录（如 "." 或 "project"，多根输入时用于区分分段）
+    pub git: Option<GitInfo>, // git状态及最后提交（目录遍历时的--git）
+    pub hash: Option<FileHash>, // 文件校验和（目录遍历时的--hash）
+    pub sniffed_type: Option<String>, // 按文件内容识别出的类型（扩展名，如 "png"，目录遍历时的--detect-type）
+    pub lines: Option<usize>,         // 文本文件的行数（目录遍历时的--loc）
+    pub duplicate_group: Option<usize>, // 重复文件的组编号（目录遍历时的--find-duplicates）
+    pub change: Option<Change>,       // 与另一份快照相比的变化（diff子命令）
+    pub old_size: Option<u64>,        // 大小变化的文件在旧快照中的大小
+    pub storage_class: Option<String>, // 对象存储的存储类别（--s3）
+    pub layer: Option<ImageLayer>,    // 最后修改该文件的镜像层（--docker-image）
+}
+
+impl TreeItem {
+    /// 是否为符号链接
+    pub fn is_symlink(&self) -> bool {
+        self.link_target.is_some()
+    }
+
+    /// 构建统计信息项（始终位于列表末尾）
+    pub(crate) fn stats(stats_text: &str) -> Self {
+        Self {
+            name: format!("📊 统计: {stats_text}"),
+            level: 0,
+            is_file: false,
+            full_path: format!("📊 统计: {stats_text}"),
+            ..Default::default()
+        }
+    }
+}
+
+/// 将根目录作为第一层（L1）保留：每个根目录插入一个目录项，
+/// 其下项目整体下移一层，完整路径以根目录开头（--include-root）
+pub fn include_root(items: Vec<TreeItem>) -> Vec<TreeItem> {
+    let mut result = Vec::with_capacity(items.len() + 1);
+    let mut current_root: Option<String> = None;
+
+    for mut item in items {
+        let Some(root) = item.root.take() else {
+            // 统计信息及没有根目录信息的项目保持不变
+            result.push(item);
+            continue;
+        };
+
+        if current_root.as_ref() != Some(&root) {
+            result.push(TreeItem {
+                name: root.clone(),
+                level: 1,
+                kind: EntryKind::Directory,
+                full_path: root.clone(),
+                ..Default::default()
+            });
+            current_root = Some(root.clone());
+        }
+
+        item.level += 1;
+        item.full_path = format!("{}/{}", root.trim_end_matches('/'), item.full_path);
+        result.push(item);
+    }
+
+    result
+}
+
+/// 按第一层目录（L1）拆分为多组，每组以目录名称命名（--split-by-top）。
+/// 第一层的文件归入"根目录文件"组，统计行保留在最后一组末尾
+pub fn split_by_top(items: Vec<TreeItem>, lang: Language) -> Vec<(String, Vec<TreeItem>)> {
+    let mut groups: Vec<(String, Vec<TreeItem>)> = Vec::new();
+    let mut root_files = Vec::new();
+    let mut stats_items = Vec::new();
+
+    for item in items {
+        if item.name.starts_with("📊") {
+            stats_items.push(item);
+        } else if item.level <= 1 && item.kind == EntryKind::Directory {
+            groups.push((item.name.clone(), vec![item]));
+        } else if item.level <= 1 {
+            root_files.push(item);
+        } else if let Some((_, group)) = groups.last_mut() {
+            group.push(item);
+        } else {
+            root_files.push(item);
+        }
+    }
+
+    if !root_files.is_empty() {
+        let name = lang.text("根目录文件", "Root Files");
+        groups.push((name.to_string(), root_files));
+    }
+    match groups.last_mut() {
+        Some((_, group)) => group.extend(stats_items),
+        None if !stats_items.is_empty() => {
+            let name = lang.text("统计", "Statistics");
+            groups.push((name.to_string(), stats_items))
+        }
+        None => {}
+    }
+    groups
+}
+
+/// "[...]" 注释前缀中解析出的元数据
+#[derive(Debug, Default)]
+struct Annotation {
+    size: Option<u64>,
+    modified: Option<NaiveDateTime>,
+    permissions: Option<String>,
+    owner: Option<String>,
+    group: Option<String>,
+}
+
+/// 流式解析时用于检测格式的前部行数
+pub(crate) const DETECT_LINES: usize = 10_000;
+
+/// 既不是项目也不是根目录或统计信息、无法解析而跳过的输入行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedLine {
+    pub line_number: usize, // 从1开始的行号
+    pub text: String,
+}
+
+/// 从输入（前部）检测出的格式设置
+#[derive(Debug)]
+struct Layout {
+    tree_column: Option<usize>, // eza/lsd长格式中树形部分的起始列
+    classified: bool,           // 是否为tree -F输出
+    indent_unit: usize,         // 缩进单位
+    path_root: Option<String>,  // tree -f输出的根前缀
+}
+
+/// Tree输出解析器
+#[derive(Debug, Default)]
+pub struct TreeParser;
+
+impl TreeParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 解析tree输出，返回扁平化的项目列表
+    pub fn parse(&self, input: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let lines = input.lines().map(|line| Ok(line.to_string()));
+        self.stream(lines, usize::MAX, include_hidden)?.collect()
+    }
+
+    /// 逐行流式解析（用于超大输入，无需整体读入内存），格式按前10000行检测
+    pub fn parse_lines<I>(
+        &self,
+        lines: I,
+        include_hidden: bool,
+    ) -> Result<TreeItemStream<I::IntoIter>>
+    where
+        I: IntoIterator<Item = io::Result<String>>,
+    {
+        self.stream(lines, DETECT_LINES, include_hidden)
+    }
+
+    /// 读入前detect_lines行检测格式，创建逐行解析的迭代器
+    fn stream<I>(
+        &self,
+        lines: I,
+        detect_lines: usize,
+        include_hidden: bool,
+    ) -> Result<TreeItemStream<I::IntoIter>>
+    where
+        I: IntoIterator<Item = io::Result<String>>,
+    {
+        let mut lines = lines.into_iter();
+        let prefix = lines
+            .by_ref()
+            .take(detect_lines)
+            .collect::<io::Result<VecDeque<String>>>()
+            .context("读取输入失败")?;
+        let prefix_lines: Vec<&str> = prefix.iter().map(String::as_str).collect();
+        let layout = self.detect_layout(&prefix_lines);
+        log::debug!("检测到的tree格式: {layout:?}");
+
+        Ok(TreeItemStream {
+            parser: TreeParser,
+            lines,
+            prefix,
+            layout,
+            include_hidden,
+            lines_read: 0,
+            skipped_lines: Vec::new(),
+            path_stack: Vec::new(),
+            stats_lines: Vec::new(),
+            section_root: None,
+            section_open: false,
+            hidden_levels: Vec::new(),
+            pending: None,
+            ready: VecDeque::new(),
+            file_count: 0,
+            dir_count: 0,
+            finished: false,
+        })
+    }
+
+    /// 检测输入的格式设置
+    fn detect_layout(&self, raw_lines: &[&str]) -> Layout {
+        // eza/lsd的长格式（-l --tree）在树形符号前输出对齐的权限、大小等列
+        let tree_column = self.detect_tree_column(raw_lines);
+        let lines: Vec<&str> = raw_lines
+            .iter()
+            .map(|line| self.split_columns(line, tree_column).1)
+            .collect();
+
+        // tree -F会给目录加 "/" 后缀，出现时按分类后缀解析所有名称
+        let classified = lines
+            .iter()
+            .filter_map(|line| self.split_line(line))
+            .any(|(_, name)| name.ends_with('/'));
+
+        // 推断缩进单位：第一个有缩进的行一定是第二层，其缩进宽度即为一个单位
+        // （tree为4个字符，部分工具为2个空格或制表符）
+        let indent_unit = lines
+            .iter()
+            .filter_map(|line| self.split_line(line))
+            .map(|(indent, _)| indent)
+            .find(|indent| *indent > 0)
+            .unwrap_or(4);
+
+        // 根目录行（tree dirA dirB会输出多个根目录分段）
+        let roots: Vec<String> = lines
+            .iter()
+            .filter(|line| !self.is_stats_line(line))
+            .filter_map(|line| self.root_name(line))
+            .collect();
+
+        // tree -f输出的每一行都是完整路径，此时层级和名称直接从路径推出
+        let path_root = self.detect_path_root(&lines, &roots);
+
+        Layout {
+            tree_column,
+            classified,
+            indent_unit,
+            path_root,
+        }
+    }
+
+    /// 拆分为(元数据列, 树形部分)，非eza/lsd长格式时元数据列为空
+    fn split_columns<'a>(&self, line: &'a str, tree_column: Option<usize>) -> (&'a str, &'a str) {
+        match tree_column {
+            Some(column) => {
+                let split = line
+                    .char_indices()
+                    .nth(column)
+                    .map_or(line.len(), |(index, _)| index);
+                line.split_at(split)
+            }
+            None => ("", line),
+        }
+    }
+
+    /// 解析单行，返回(层级, 名称)，按tree标准的4字符缩进计算层级
+    pub fn parse_line(&self, line: &str) -> Option<(usize, String)> {
+        let (indent, name) = self.split_line(line)?;
+        Some((self.indent_level(indent, 4), name))
+    }
+
+    /// 拆分行尾的错误标记，如 "secret  [error opening dir]"，返回(名称, 错误信息)
+    fn split_error<'a>(&self, name: &'a str) -> Option<(&'a str, &'a str)> {
+        let (rest, marker) = name.strip_suffix(']')?.rsplit_once(" [")?;
+        marker
+            .to_lowercase()
+            .starts_with("error")
+            .then(|| (rest.trim_end(), marker))
+    }
+
+    /// 是否为统计行（以数字开头，且包含某种语言的目录和文件关键词）
+    fn is_stats_line(&self, line: &str) -> bool {
+        let line = self.remove_ansi_codes(line).trim().to_lowercase();
+        line.starts_with(|c: char| c.is_ascii_digit())
+            && STATS_KEYWORDS
+                .iter()
+                .any(|(dir, file)| line.contains(dir) && line.contains(file))
+    }
+
+    /// 根目录行是否像路径（"."、以 "/" 结尾或绝对路径），这样的行在任何位置都可开始新的分段
+    fn is_root_path(&self, line: &str) -> bool {
+        let clean_line = self.remove_ansi_codes(line);
+        let name = clean_line.trim_start_matches(|c| self.is_icon(c)).trim();
+        let mut chars = name.chars();
+        name == "."
+            || name.ends_with('/')
+            || name.starts_with(['/', '~'])
+            || name.starts_with("./")
+            || name.starts_with("../")
+            || (chars.next().is_some_and(|c| c.is_ascii_alphabetic()) && chars.next() == Some(':'))
+    }
+
+    /// 是否为分段的最后一行：各层都是最后一项，缩进中没有竖线，如 "    └── main.rs"
+    fn closes_section(&self, line: &str) -> bool {
+        let clean_line = self.remove_ansi_codes(line);
+        let tree = clean_line.trim_start();
+        tree.starts_with('└') || tree.starts_with("`--") || tree.starts_with("\\--")
+    }
+
+    /// 识别根目录行（如 "."、"project/"、"/var/log"），返回去掉 "/" 后缀的根名称
+    fn root_name(&self, line: &str) -> Option<String> {
+        let clean_line = self.remove_ansi_codes(line);
+        if clean_line.starts_with(char::is_whitespace) || self.split_line(line).is_some() {
+            return None;
+        }
+        let name = clean_line.trim_start_matches(|c| self.is_icon(c)).trim();
+        if name.is_empty() {
+            return None;
+        }
+        Some(match name.strip_suffix('/') {
+            Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+            _ => name.to_string(),
+        })
+    }
+
+    /// 检测tree -f输出：所有名称都是根目录下的路径时，返回回退用的根前缀
+    /// （取第一项的父路径，用于缺少根目录行的输入）
+    fn detect_path_root(&self, lines: &[&str], roots: &[String]) -> Option<String> {
+        let paths: Vec<String> = lines
+            .iter()
+            .filter_map(|line| self.split_line(line))
+            .map(|(_, name)| {
+                let name = self
+                    .split_annotation(&name)
+                    .map_or(name.as_str(), |(_, rest)| rest);
+                let name = name.split_once(" -> ").map_or(name, |(name, _)| name);
+                name.trim_end_matches('/').to_string()
+            })
+            .collect();
+
+        // 第一项一定位于第一层，其父路径即为根前缀（如 "." 或 "project"）
+        let (root, _) = paths.first()?.rsplit_once('/')?;
+        let prefixes: Vec<String> = roots
+            .iter()
+            .map(String::as_str)
+            .chain([root])
+            .map(|root| format!("{}/", root.trim_end_matches('/')))
+            .collect();
+        paths
+            .iter()
+            .all(|path| prefixes.iter().any(|prefix| path.starts_with(prefix)))
+            .then(|| root.to_string())
+    }
+
+    /// 根据缩进宽度和缩进单位计算层级
+    fn indent_level(&self, indent: usize, unit: usize) -> usize {
+        (indent + unit / 2) / unit + 1 // +1 因为第一层是1，不是0
+    }
+
+    /// 拆分单行，返回(缩进宽度, 名称)
+    fn split_line(&self, line: &str) -> Option<(usize, String)> {
+        // 跳过根目录标记（可能是 "." 或项目名如 "utzip-0.9.0/"）
+        let trimmed = line.trim();
+        if trimmed == "."
+            || (trimmed.ends_with('/') && !trimmed.starts_with(['├', '└', '│', '|', '`', '\\']))
+        {
+            return None;
+        }
+
+        // 清理行，移除ANSI转义序列
+        let clean_line = self.remove_ansi_codes(line);
+        let chars: Vec<char> = clean_line.chars().collect();
+        let mut pos = 0;
+        let mut indent = 0;
+
+        // 计算缩进宽度：缩进由垂直线 "│"（ASCII字符集下为 "|"）和空白组成
+        // 注意：tree输出可能使用不同类型的空格字符(U+0020普通空格, U+00A0非断空格)
+        // 制表符按4列制表位展开
+        while pos < chars.len() {
+            match chars[pos] {
+                '|' if chars.get(pos + 1) == Some(&'-') => break, // ASCII连接符 "|--"
+                '\t' => indent = (indent / 4 + 1) * 4,
+                '│' | '|' => indent += 1,
+                c if c.is_whitespace() => indent += 1,
+                _ => break,
+            }
+            pos += 1;
+        }
+
+        // 查找并跳过tree连接符 "├──"、"└──"（部分工具为 "├─"）
+        // 以及ASCII变体 "|--"、"`--"、"\--"（tree --charset=ascii及部分Windows工具）
+        if !matches!(chars.get(pos), Some('├' | '└' | '|' | '`' | '\\')) {
+            // 没有找到tree符号，可能不是有效的tree行
+            return None;
+        }
+        let dashes = chars[pos + 1..]
+            .iter()
+            .take_while(|c| matches!(c, '─' | '-'))
+            .count();
+        if dashes == 0 {
+            return None;
+        }
+        pos += 1 + dashes;
+        // 跳过可能的空格
+        if pos < chars.len() && chars[pos] == ' ' {
+            pos += 1;
+        }
+
+        // 提取剩余部分作为文件/目录名
+        if pos >= chars.len() {
+            return None;
+        }
+
+        let name: String = chars[pos..].iter().collect::<String>().trim().to_string();
+
+        // 去除eza/lsd --icons输出的Nerd Font图标
+        let name = name.trim_start_matches(|c| self.is_icon(c)).trim_start();
+
+        if name.is_empty() {
+            None
+        } else {
+            Some((indent, name.to_string()))
+        }
+    }
+
+    /// 判断是否为Nerd Font图标（位于Unicode私有区）
+    fn is_icon(&self, c: char) -> bool {
+        matches!(c, '\u{E000}'..='\u{F8FF}' | '\u{F0000}'..='\u{FFFFD}' | '\u{100000}'..='\u{10FFFD}')
+    }
+
+    /// 检测eza/lsd长格式中树形部分的起始列，普通tree输出返回None
+    fn detect_tree_column(&self, lines: &[&str]) -> Option<usize> {
+        lines
+            .iter()
+            .filter_map(|line| {
+                let column = line.chars().position(|c| matches!(c, '├' | '└' | '│'))?;
+                let prefix: String = line.chars().take(column).collect();
+                (!prefix.trim().is_empty()).then_some(column)
+            })
+            .min()
+    }
+
+    /// 拆分tree -F的分类后缀，返回(名称, 类型)
+    fn split_classifier(&self, name: &str) -> (String, Option<EntryKind>) {
+        let kind = match name.chars().last() {
+            Some('/') => EntryKind::Directory,
+            Some('*') => EntryKind::Executable,
+            Some('@') => EntryKind::Symlink,
+            Some('=') => EntryKind::Socket,
+            Some('|') => EntryKind::Fifo,
+            _ => return (name.to_string(), Some(EntryKind::File)),
+        };
+        (name[..name.len() - 1].to_string(), Some(kind))
+    }
+
+    /// 拆分名称前的 "[...]" 注释前缀，返回(注释, 名称)
+    ///
+    /// 名称本身也可能以 "[" 开头（如Next.js的 "[slug]"），
+    /// 因此只有 "]" 后跟空白和名称、且前缀内容可识别时才视为注释。
+    fn split_annotation<'a>(&self, name: &'a str) -> Option<(Annotation, &'a str)> {
+        let rest = name.strip_prefix('[')?;
+        let end = rest.find(']')?;
+        let (content, remainder) = (&rest[..end], &rest[end + 1..]);
+        if !remainder.starts_with(char::is_whitespace) || remainder.trim().is_empty() {
+            return None;
+        }
+        let annotation = self.parse_annotation(content)?;
+        Some((annotation, remainder.trim()))
+    }
+
+    /// 解析注释内容，无法识别任何字段时返回None
+    fn parse_annotation(&self, content: &str) -> Option<Annotation> {
+        let mut tokens: Vec<&str> = content.split_whitespace().collect();
+
+        // 权限字符串总是在最前面（tree -p）
+        let permissions = match tokens.first() {
+            Some(token) if self.is_permissions(token) => Some(tokens.remove(0).to_string()),
+            _ => None,
+        };
+
+        // 先取出日期，避免日期中的数字被当作大小
+        let modified = self.take_date(&mut tokens);
+
+        // 大小在用户/组之后（顺序为：权限 用户 组 大小 日期）
+        let size = tokens.last().and_then(|token| self.parse_size(token));
+        if size.is_some() {
+            tokens.pop();
+        }
+
+        // 只有用户/组时无法与 "[draft] notes.txt" 这类名称区分，因此不单独识别
+        if size.is_none() && modified.is_none() && permissions.is_none() {
+            return None;
+        }
+
+        // 剩余字段依次为用户和组（tree -u -g）
+        let mut names = tokens.into_iter().map(str::to_string);
+        Some(Annotation {
+            size,
+            modified,
+            permissions,
+            owner: names.next(),
+            group: names.next(),
+        })
+    }
+
+    /// 从注释中取出日期（tree -D），支持 "Jan 15 12:34"、"Jan 15  2023" 和 "2024-01-15 12:34"
+    pub(crate) fn take_date(&self, tokens: &mut Vec<&str>) -> Option<NaiveDateTime> {
+        for i in 0..tokens.len() {
+            // ISO格式：日期后可跟时间
+            if let Ok(date) = NaiveDate::parse_from_str(tokens[i], "%Y-%m-%d") {
+                let time = tokens.get(i + 1).and_then(|token| self.parse_time(token));
+                tokens.drain(i..i + 1 + usize::from(time.is_some()));
+                return Some(date.and_time(time.unwrap_or(NaiveTime::MIN)));
+            }
+
+            // ls风格：月份 日 时间/年份
+            let Ok(month) = tokens[i].parse::<Month>() else {
+                continue;
+            };
+            let (Some(day), Some(last)) = (tokens.get(i + 1), tokens.get(i + 2)) else {
+                continue;
+            };
+            let Ok(day) = day.parse::<u32>() else {
+                continue;
+            };
+
+            let datetime = if let Some(time) = self.parse_time(last) {
+                // 近期文件只显示时间，年份取今年（若晚于今天则为去年）
+                let today = Local::now().date_naive();
+                let date = NaiveDate::from_ymd_opt(today.year(), month.number_from_month(), day)?;
+                let date = if date > today {
+                    date.with_year(today.year() - 1)?
+                } else {
+                    date
+                };
+                date.and_time(time)
+            } else {
+                let year = last.parse::<i32>().ok()?;
+                NaiveDate::from_ymd_opt(year, month.number_from_month(), day)?
+                    .and_time(NaiveTime::MIN)
+            };
+            tokens.drain(i..i + 3);
+            return Some(datetime);
+        }
+        None
+    }
+
+    /// 判断是否为ls风格的权限字符串，如 "drwxr-xr-x"、"-rw-r--r--@"
+    pub(crate) fn is_permissions(&self, token: &str) -> bool {
+        let chars: Vec<char> = token.chars().collect();
+        (chars.len() == 10 || (chars.len() == 11 && matches!(chars[10], '+' | '.' | '@')))
+            && matches!(chars[0], '-' | 'd' | 'l' | 'c' | 'b' | 'p' | 's')
+            && chars[1..10]
+                .iter()
+                .all(|c| matches!(c, 'r' | 'w' | 'x' | 's' | 'S' | 't' | 'T' | '-'))
+    }
+
+    /// 解析 "12:34" 或 "12:34:56"
+    fn parse_time(&self, token: &str) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(token, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(token, "%H:%M"))
+            .ok()
+    }
+
+    /// 解析大小，支持字节数（-s/--du）和可读单位（-h，如 "4.0K"、"1.2M"）
+    pub(crate) fn parse_size(&self, token: &str) -> Option<u64> {
+        let (number, unit) = match token.find(|c: char| c.is_ascii_alphabetic()) {
+            Some(pos) => token.split_at(pos),
+            None => (token, ""),
+        };
+        let exponent = match unit.to_ascii_uppercase().as_str() {
+            "" => 0,
+            "K" => 1,
+            "M" => 2,
+            "G" => 3,
+            "T" => 4,
+            "P" => 5,
+            "E" => 6,
+            _ => return None,
+        };
+        let value: f64 = number.parse().ok()?;
+        Some((value * 1024f64.powi(exponent)).round() as u64)
+    }
+
+    /// 移除ANSI转义序列
+    fn remove_ansi_codes(&self, text: &str) -> String {
+        // 简单的ANSI转义序列移除
+        let mut result = String::new();
+        let mut chars = text.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            if ch == '\x1b' {
+                // 跳过ANSI转义序列
+                if chars.peek() == Some(&'[') {
+                    chars.next(); // 跳过 '['
+                    for c in chars.by_ref() {
+                        if c.is_ascii_alphabetic() || c == '~' {
+                            break;
+                        }
+                    }
+                }
+            } else {
+                result.push(ch);
+            }
+        }
+        result
+    }
+
+    /// 判断是否为文件
+    fn is_file(&self, name: &str) -> bool {
+        // 有扩展名的是文件
+        if name.contains('.') && !name.starts_with('.') {
+            if let Some(dot_pos) = name.rfind('.') {
+                return dot_pos > 0 && dot_pos < name.len() - 1;
+            }
+        }
+
+        // 常见的无扩展名文件
+        matches!(
+            name,
+            "Cargo.lock" | "Dockerfile" | "Makefile" | "LICENSE" | "README" | "CHANGELOG"
+        )
+    }
+}
+
+/// 逐行解析tree输出的迭代器（由TreeParser::parse_lines创建），最后一项为统计信息
+#[derive(Debug)]
+pub struct TreeItemStream<I> {
+    parser: TreeParser,
+    lines: I,
+    prefix: VecDeque<String>, // 已读入用于检测格式的前部行
+    layout: Layout,
+    include_hidden: bool,
+    lines_read: usize,
+    skipped_lines: Vec<SkippedLine>,
+    path_stack: Vec<String>,
+    stats_lines: Vec<String>,
+    section_root: Option<String>,
+    section_open: bool, // 当前分段是否还有后续项目（最后一行为 "└──" 且无竖线时结束）
+    hidden_levels: Vec<usize>, // 记录被过滤的隐藏目录的层级
+    pending: Option<(TreeItem, bool)>, // 等待下一项确认类型的项目（项目, 类型是否仅由扩展名推测）
+    ready: VecDeque<TreeItem>,
+    file_count: usize,
+    dir_count: usize,
+    finished: bool,
+}
+
+impl<I: Iterator<Item = io::Result<String>>> TreeItemStream<I> {
+    /// 已读取的输入行数
+    pub fn lines_read(&self) -> usize {
+        self.lines_read
+    }
+
+    /// 无法解析而跳过的行
+    pub fn skipped_lines(&self) -> &[SkippedLine] {
+        &self.skipped_lines
+    }
+
+    /// 由调用方逐行送入输入（代替从迭代器读取），解析出的项目由take_ready取出
+    pub(crate) fn push_line(&mut self, line: &str) {
+        while let Some(prefix_line) = self.prefix.pop_front() {
+            self.lines_read += 1;
+            self.process_line(&prefix_line);
+        }
+        self.lines_read += 1;
+        self.process_line(line);
+    }
+
+    /// 取出已解析完成的项目
+    pub(crate) fn take_ready(&mut self) -> impl Iterator<Item = TreeItem> + '_ {
+        self.ready.drain(..)
+    }
+
+    /// 解析一行输入
+    fn process_line(&mut self, line: &str) {
+        let (meta, line) = self.parser.split_columns(line, self.layout.tree_column);
+
+        // 空行和统计行结束当前分段
+        if line.trim().is_empty() {
+            self.section_open = false;
+            return;
+        }
+
+        // 检查统计行
+        if self.parser.is_stats_line(line) {
+            self.stats_lines.push(line.trim().to_string());
+            self.section_open = false;
+            return;
+        }
+
+        // 根目录行：开始新的分段。分段中间的其他行（如混入的文字）不是根目录，按无法解析跳过
+        if let Some(root) = self.parser.root_name(line) {
+            if !self.section_open || self.parser.is_root_path(line) {
+                self.section_root = Some(root);
+                self.path_stack.clear();
+                self.hidden_levels.clear();
+                self.section_open = true;
+                return;
+            }
+        }
+
+        // 解析层级和名称
+        if let Some((indent, name)) = self.parser.split_line(line) {
+            self.section_open = !self.parser.closes_section(line);
+            let level = self.parser.indent_level(indent, self.layout.indent_unit);
+
+            // 拆分tree -s/-h/--du/-D输出的 "[ 4096 Jan 15 12:34]" 前缀
+            let (mut annotation, name) = match self.parser.split_annotation(&name) {
+                Some((annotation, rest)) => (annotation, rest.to_string()),
+                None => (Annotation::default(), name),
+            };
+
+            // eza/lsd长格式的权限列（普通文件以 "." 开头，如 ".rw-r--r--"）
+            if annotation.permissions.is_none() {
+                annotation.permissions = meta
+                    .split_whitespace()
+                    .next()
+                    .map(|token| token.replacen('.', "-", usize::from(token.starts_with('.'))))
+                    .filter(|token| self.parser.is_permissions(token));
+            }
+
+            // 拆分tree对无法读取的目录追加的 "[error opening dir]" 标记
+            let (name, error) = match self.parser.split_error(&name) {
+                Some((name, error)) => (name.to_string(), Some(error.to_string())),
+                None => (name, None),
+            };
+
+            // 拆分符号链接 "name -> target"
+            let (name, link_target) = match name.split_once(" -> ") {
+                Some((name, target)) => (name.to_string(), Some(target.to_string())),
+                None => (name, None),
+            };
+
+            // 拆分tree -F的分类后缀（/ * @ = |）
+            let (name, classified_kind) = if self.layout.classified {
+                self.parser.split_classifier(&name)
+            } else {
+                (name, None)
+            };
+
+            // tree -f：去掉根前缀后，最后一段为名称，段数为层级
+            let (name, level, exact_path) = match &self.layout.path_root {
+                Some(root) => {
+                    let root = self.section_root.as_ref().unwrap_or(root);
+                    let path = name
+                        .strip_prefix(root.as_str())
+                        .unwrap_or(&name)
+                        .trim_start_matches('/')
+                        .to_string();
+                    let leaf = path.rsplit('/').next().unwrap_or(&path).to_string();
+                    (leaf, path.split('/').count(), Some(path))
+                }
+                None => (name, level, None),
+            };
+
+            // 清理过期的隐藏层级记录（当前层级小于等于隐藏层级时）
+            self.hidden_levels
+                .retain(|&hidden_level| hidden_level < level);
+
+            // 检查是否在隐藏目录内
+            let in_hidden_dir = !self.hidden_levels.is_empty();
+
+            // 过滤隐藏目录/文件（以.开头的项目，如.git）
+            if !self.include_hidden && (name.starts_with('.') || in_hidden_dir) {
+                if name.starts_with('.') {
+                    // 记录这个隐藏目录的层级，用于过滤其子项目
+                    self.hidden_levels.push(level);
+                }
+                return;
+            }
+
+            // 调整路径栈到当前层级
+            self.path_stack.truncate(level.saturating_sub(1));
+
+            // 构建完整路径（tree -f直接使用行内路径）
+            let full_path = if let Some(path) = exact_path {
+                path
+            } else if self.path_stack.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", self.path_stack.join("/"), name)
+            };
+
+            // 添加到路径栈
+            self.path_stack.push(name.clone());
+
+            // 判断类型：分类后缀和权限类型位最可靠，其次是符号链接，最后才用扩展名推测
+            let guessed = classified_kind.is_none()
+                && annotation.permissions.is_none()
+                && error.is_none()
+                && link_target.is_none();
+            let kind = if let Some(kind) = classified_kind {
+                kind
+            } else if let Some(permissions) = &annotation.permissions {
+                EntryKind::from_permissions(permissions)
+            } else if error.is_some() {
+                EntryKind::Directory // tree只对目录输出打开错误
+            } else if link_target.is_some() {
+                EntryKind::Symlink
+            } else if self.parser.is_file(&name) {
+                EntryKind::File
+            } else {
+                EntryKind::Directory
+            };
+            let is_file = kind != EntryKind::Directory;
+
+            let item = TreeItem {
+                name: name.clone(),
+                level,
+                is_file,
+                kind,
+                full_path,
+                size: annotation.size,
+                modified: annotation.modified,
+                permissions: annotation.permissions,
+                owner: annotation.owner,
+                group: annotation.group,
+                link_target,
+                error,
+                root: self.section_root.clone(),
+                ..Default::default()
+            };
+            log::trace!(
+                "第 {} 行 → 第{level}层: {}",
+                self.lines_read,
+                item.full_path
+            );
+            self.push_item(item, guessed);
+        } else {
+            log::debug!(
+                "第 {} 行无法解析，已跳过: {}",
+                self.lines_read,
+                line.trim_end()
+            );
+            self.skipped_lines.push(SkippedLine {
+                line_number: self.lines_read,
+                text: line.trim_end().to_string(),
+            });
+        }
+    }
+
+    /// 后面紧跟更深层级项目的一定是目录（如 "node.js"、"v1.2.3"），
+    /// 因此每一项都等到下一项解析后再输出
+    fn push_item(&mut self, item: TreeItem, guessed: bool) {
+        if let Some((mut previous, previous_guessed)) = self.pending.take() {
+            if previous_guessed && item.level > previous.level {
+                previous.kind = EntryKind::Directory;
+                previous.is_file = false;
+            }
+            self.emit(previous);
+        }
+        self.pending = Some((item, guessed));
+    }
+
+    /// 输出一项并计数
+    fn emit(&mut self, item: TreeItem) {
+        if item.is_file {
+            self.file_count += 1;
+        } else {
+            self.dir_count += 1;
+        }
+        self.ready.push_back(item);
+    }
+
+    /// 输入结束：输出最后一项和统计信息
+    fn finish(&mut self) {
+        self.finished = true;
+        if let Some((item, _)) = self.pending.take() {
+            self.emit(item);
+        }
+
+        let (dir_count, file_count) = (self.dir_count, self.file_count);
+        let stats_text = if self.include_hidden && self.stats_lines.len() == 1 {
+            // 如果包含隐藏目录，使用原始统计信息（拼接的多份输出各有一行，只能重新计算）
+            self.stats_lines.remove(0)
+        } else {
+            // 如果过滤了隐藏目录，使用重新计算的统计信息
+            format!("{dir_count} directories, {file_count} files")
+        };
+        self.ready.push_back(TreeItem::stats(&stats_text));
+    }
+}
+
+impl<I: Iterator<Item = io::Result<String>>> Iterator for TreeItemStream<I> {
+    type Item = Result<TreeItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.ready.pop_front() {
+                return Some(Ok(item));
+            }
+            if self.finished {
+                return None;
+            }
+
+            let line = match self.prefix.pop_front() {
+                Some(line) => Some(Ok(line)),
+                None => self.lines.next(),
+            };
+            match line {
+                Some(Ok(line)) => {
+                    self.lines_read += 1;
+                    self.process_line(&line);
+                }
+                Some(Err(err)) => {
+                    self.finished = true;
+                    return Some(Err(anyhow::Error::new(err).context("读取输入失败").into()));
+                }
+                None => self.finish(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        let parser = TreeParser::new();
+
+        let test_cases = vec![
+            ("├── src", Some((1, "src".to_string()))),
+            ("│   ├── main.rs", Some((2, "main.rs".to_string()))),
+            ("│   │   └── lib.rs", Some((3, "lib.rs".to_string()))),
+            ("|-- src/", Some((1, "src/".to_string()))),
+            ("|   |-- main.rs", Some((2, "main.rs".to_string()))),
+            ("|   `-- lib.rs", Some((2, "lib.rs".to_string()))),
+            ("    \\-- build.rs", Some((2, "build.rs".to_string()))),
+        ];
+
+        for (input, expected) in test_cases {
+            let result = parser.parse_line(input);
+            assert_eq!(result, expected, "Failed for input: {input}");
+        }
+    }
+
+    #[test]
+    fn test_adaptive_indent() {
+        let parser = TreeParser::new();
+
+        // 2个字符缩进（如eza --tree）
+        let input = "├─ src\n│ ├─ main.rs\n│ └─ bin\n│   └─ tool.rs\n└─ build.rs\n";
+        let items = parser.parse(input, false).unwrap();
+        let levels: Vec<_> = items[..5].iter().map(|item| item.level).collect();
+        assert_eq!(levels, [1, 2, 2, 3, 1]);
+
+        // 制表符缩进
+        let input = "├── src\n\t├── main.rs\n\t└── bin\n\t\t└── tool.rs\n";
+        let items = parser.parse(input, false).unwrap();
+        assert_eq!(items[3].full_path, "src/bin/tool.rs");
+    }
+
+    #[test]
+    fn test_parse_eza_long_with_icons() {
+        let parser = TreeParser::new();
+        let input = "drwxr-xr-x    - user 15 Jan 12:00 \u{f115} .
+.rw-r--r--  120 user 15 Jan 12:00 ├── \u{e7a8} Cargo.toml
+drwxr-xr-x    - user 15 Jan 12:00 └── \u{f115} src
+.rw-r--r--  300 user 15 Jan 12:00    └── \u{e7a8} main.rs
+";
+        let items = parser.parse(input, false).unwrap();
+
+        let parsed: Vec<_> = items[..3]
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.kind))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("Cargo.toml", EntryKind::File),
+                ("src", EntryKind::Directory),
+                ("src/main.rs", EntryKind::File),
+            ]
+        );
+        assert_eq!(items[0].permissions.as_deref(), Some("-rw-r--r--"));
+    }
+
+    #[test]
+    fn test_structural_directory_detection() {
+        let parser = TreeParser::new();
+        let input = "├── node.js\n│   └── index.js\n└── notes.txt\n";
+        let items = parser.parse(input, false).unwrap();
+
+        assert_eq!(items[0].kind, EntryKind::Directory);
+        assert!(!items[0].is_file);
+        assert!(items[1].is_file && items[2].is_file);
+    }
+
+    #[test]
+    fn test_parse_full_path() {
+        let parser = TreeParser::new();
+        let input = "project\n├── project/src\n│   ├── project/src/main.rs\n│   └── project/src/node.js\n│       └── project/src/node.js/index.js\n└── project/Cargo.toml\n";
+        let items = parser.parse(input, false).unwrap();
+
+        let names: Vec<&str> = items.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(
+            names[..5],
+            ["src", "main.rs", "node.js", "index.js", "Cargo.toml"]
+        );
+        let levels: Vec<usize> = items.iter().map(|item| item.level).collect();
+        assert_eq!(levels[..5], [1, 2, 2, 3, 1]);
+        assert_eq!(items[3].full_path, "src/node.js/index.js");
+        assert_eq!(items[2].kind, EntryKind::Directory);
+
+        // "./" 前缀的相对路径
+        let items = parser
+            .parse(".\n└── ./docs\n    └── ./docs/a.md\n", false)
+            .unwrap();
+        assert_eq!(items[1].name, "a.md");
+        assert_eq!(items[1].full_path, "docs/a.md");
+    }
+
+    #[test]
+    fn test_parse_multiple_roots() {
+        let parser = TreeParser::new();
+        let input = "dirA\n└── src\n    └── main.rs\ndirB/\n└── src\n\n2 directories, 3 files\n";
+        let items = parser.parse(input, false).unwrap();
+
+        let roots: Vec<_> = items.iter().map(|item| item.root.as_deref()).collect();
+        assert_eq!(roots, [Some("dirA"), Some("dirA"), Some("dirB"), None]);
+        assert_eq!(items[2].level, 1);
+        assert_eq!(items[2].full_path, "src");
+
+        // tree -f的路径按各自的根目录去掉前缀
+        let input = "dirA\n└── dirA/a.txt\ndirB\n└── dirB/b.txt\n";
+        let items = parser.parse(input, false).unwrap();
+        assert_eq!(items[1].full_path, "b.txt");
+        assert_eq!(items[1].root.as_deref(), Some("dirB"));
+
+        // --include-root：根目录作为第一层，完整路径以根目录开头
+        let items = include_root(
+            parser
+                .parse("project/\n└── src\n    └── a.rs\n", false)
+                .unwrap(),
+        );
+        let paths: Vec<_> = items.iter().map(|item| item.full_path.as_str()).collect();
+        assert_eq!(paths[..3], ["project", "project/src", "project/src/a.rs"]);
+        let levels: Vec<_> = items.iter().map(|item| item.level).collect();
+        assert_eq!(levels[..3], [1, 2, 3]);
+        assert_eq!(items[0].kind, EntryKind::Directory);
+    }
+
+    #[test]
+    fn test_split_by_top() {
+        let input = ".\n├── docs\n│   └── a.md\n├── src\n│   └── lib.rs\n└── README.md\n\n2 directories, 3 files\n";
+        let groups = split_by_top(TreeParser::new().parse(input, false).unwrap(), Language::Zh);
+
+        let names: Vec<_> = groups.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["docs", "src", "根目录文件"]);
+        let paths: Vec<_> = groups[1]
+            .1
+            .iter()
+            .map(|item| item.full_path.as_str())
+            .collect();
+        assert_eq!(paths, ["src", "src/lib.rs"]);
+        assert!(groups[2].1.last().unwrap().name.starts_with("📊"));
+    }
+
+    #[test]
+    fn test_localized_stats_line() {
+        let parser = TreeParser::new();
+        for stats in [
+            "1 directory, 1 file",
+            "2 Verzeichnisse, 5 Dateien",
+            "2 répertoires, 5 fichiers",
+            "2 个目录，5 个文件",
+        ] {
+            let input = format!(".\n└── a.txt\n\n{stats}\n");
+            let items = parser.parse(&input, true).unwrap();
+            assert_eq!(items.len(), 2, "{stats}");
+            assert_eq!(items[1].name, format!("📊 统计: {stats}"));
+        }
+    }
+
+    #[test]
+    fn test_parse_error_marker() {
+        let parser = TreeParser::new();
+        let input = ".\n├── private.d  [error opening dir]\n└── a.txt\n";
+        let items = parser.parse(input, false).unwrap();
+
+        assert_eq!(items[0].name, "private.d");
+        assert_eq!(items[0].full_path, "private.d");
+        assert_eq!(items[0].error.as_deref(), Some("error opening dir"));
+        assert_eq!(items[0].kind, EntryKind::Directory);
+        assert_eq!(items[1].error, None);
+    }
+
+    #[test]
+    fn test_skipped_lines() {
+        let input = ".\n├── a.txt\n   \t? garbled\n└── b.txt\n\n0 directories, 2 files\n";
+        let lines = input.lines().map(|line| Ok(line.to_string()));
+        let mut stream = TreeParser::new().parse_lines(lines, false).unwrap();
+        let items: Vec<TreeItem> = stream.by_ref().map(Result::unwrap).collect();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(stream.lines_read(), 6);
+        assert_eq!(
+            stream.skipped_lines(),
+            [SkippedLine {
+                line_number: 3,
+                text: "   \t? garbled".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_lines_stream() {
+        // 结果与一次解析整个输入相同
+        let input = ".\n├── src\n│   └── main.rs\n└── README.md\n\n1 directory, 2 files\n";
+        let lines = input.lines().map(|line| Ok(line.to_string()));
+        let streamed: Vec<TreeItem> = TreeParser::new()
+            .parse_lines(lines, false)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(streamed, TreeParser::new().parse(input, false).unwrap());
+
+        // 只预读检测格式的前部行，其余按需读取
+        let total = DETECT_LINES + 1000;
+        let pulled = std::cell::Cell::new(0);
+        let lines = std::iter::once(".".to_string())
+            .chain((1..total).map(|idx| format!("├── f{idx}.rs")))
+            .inspect(|_| pulled.set(pulled.get() + 1))
+            .map(Ok);
+        let mut stream = TreeParser::new().parse_lines(lines, false).unwrap();
+        assert_eq!(pulled.get(), DETECT_LINES);
+        assert_eq!(stream.next().unwrap().unwrap().full_path, "f1.rs");
+        assert!(pulled.get() < total);
+        assert_eq!(stream.by_ref().count(), total - 2 + 1);
+        assert_eq!(stream.lines_read(), total);
+
+        // 前部行读取失败时直接返回错误；之后读取失败时先返回已解析的项目，再返回错误
+        let broken = || {
+            std::iter::once(Ok(".".to_string()))
+                .chain((1..total).map(|idx| Ok(format!("├── f{idx}.rs"))))
+                .chain(std::iter::once(Err(io::Error::other("broken pipe"))))
+        };
+        let prefix_error = TreeParser::new().parse_lines(broken().skip(total - 5), false);
+        assert!(prefix_error.is_err_and(|err| err.to_string().contains("读取输入失败")));
+        let results: Vec<_> = TreeParser::new()
+            .parse_lines(broken(), false)
+            .unwrap()
+            .collect();
+        assert!(results.len() > DETECT_LINES);
+        assert_eq!(results[0].as_ref().unwrap().full_path, "f1.rs");
+        assert!(results
+            .last()
+            .unwrap()
+            .as_ref()
+            .is_err_and(|err| err.to_string().contains("读取输入失败")));
+    }
+
+    #[test]
+    fn test_garbage_line_is_not_root() {
+        // 分段中间混入的文字不会成为新的根目录
+        let input = ".\n├── a.rs\nhello world\n│   ]]]] weird\n└── src\n    └── main.rs\n";
+        let lines = input.lines().map(|line| Ok(line.to_string()));
+        let mut stream = TreeParser::new().parse_lines(lines, false).unwrap();
+        let items: Vec<TreeItem> = stream.by_ref().map(Result::unwrap).collect();
+
+        let roots: Vec<_> = items[..3].iter().map(|item| item.root.as_deref()).collect();
+        assert_eq!(roots, [Some("."), Some("."), Some(".")]);
+        assert_eq!(items[2].full_path, "src/main.rs");
+        let skipped: Vec<_> = stream
+            .skipped_lines()
+            .iter()
+            .map(|line| line.line_number)
+            .collect();
+        assert_eq!(skipped, [3, 4]);
+
+        // 上一分段结束后的行、空行之后的行及路径形式的行仍是根目录
+        let input = "dirA\n└── a.rs\ndirB\n└── b.rs\n\nnotes\n└── c.rs\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let roots: Vec<_> = items[..3].iter().map(|item| item.root.as_deref()).collect();
+        assert_eq!(roots, [Some("dirA"), Some("dirB"), Some("notes")]);
+        let input = ".\n├── a.rs\n/var/log\n└── syslog\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        assert_eq!(items[1].root.as_deref(), Some("/var/log"));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let input = ".\n├── [       2048 Jan 15 12:34]  docs\n│   └── [  10 Jan 15 12:34]  a.md\n└── run*\n\n1 directory, 2 files\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let json = serde_json::to_string(&items).unwrap();
+        let restored: Vec<TreeItem> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, items);
+
+        // 缺少的字段取默认值
+        let item: TreeItem =
+            serde_json::from_str(r#"{"name": "a.md", "level": 1, "is_file": true, "full_path": "a.md", "kind": "executable"}"#)
+                .unwrap();
+        assert_eq!(item.kind, EntryKind::Executable);
+        assert_eq!(item.size, None);
+    }
+
+    #[test]
+    fn test_parse_classified() {
+        let parser = TreeParser::new();
+        let input = "├── v1.2.3/\n│   └── run*\n├── latest@ -> v1.2.3/\n└── LICENSE\n";
+        let items = parser.parse(input, false).unwrap();
+
+        let parsed: Vec<_> = items[..4]
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.kind, item.is_file))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("v1.2.3", EntryKind::Directory, false),
+                ("v1.2.3/run", EntryKind::Executable, true),
+                ("latest", EntryKind::Symlink, true),
+                ("LICENSE", EntryKind::File, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_size_annotation() {
+        let parser = TreeParser::new();
+        let input = "├── [       4096]  src\n│   └── [ 1.5K Mar  3  2021]  main.rs\n└── [slug]\n";
+        let items = parser.parse(input, false).unwrap();
+
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.name.as_str(), item.size))
+            .collect();
+        assert_eq!(
+            parsed[..3],
+            [
+                ("src", Some(4096)),
+                ("main.rs", Some(1536)),
+                ("[slug]", None)
+            ]
+        );
+
+        let modified = items[1].modified.unwrap();
+        assert_eq!(
+            modified.date(),
+            NaiveDate::from_ymd_opt(2021, 3, 3).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_permissions_owner_date() {
+        let parser = TreeParser::new();
+        let input = "├── [drwxr-xr-x alice    staff        4096]  src\n│   └── [-rw-r--r-- root     staff  1.5K Mar  3  2021]  main.rs\n└── [-rwx------]  run.sh\n";
+        let items = parser.parse(input, false).unwrap();
+
+        let parsed: Vec<_> = items[..3]
+            .iter()
+            .map(|item| {
+                (
+                    item.name.as_str(),
+                    item.permissions.as_deref(),
+                    item.owner.as_deref(),
+                    item.group.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("src", Some("drwxr-xr-x"), Some("alice"), Some("staff")),
+                ("main.rs", Some("-rw-r--r--"), Some("root"), Some("staff")),
+                ("run.sh", Some("-rwx------"), None, None),
+            ]
+        );
+        assert_eq!(items[0].size, Some(4096));
+        assert_eq!(items[1].size, Some(1536));
+        assert_eq!(
+            items[1].modified.unwrap().date(),
+            NaiveDate::from_ymd_opt(2021, 3, 3).unwrap()
+        );
+        assert!(items[2].modified.is_none());
+    }
+
+    #[test]
+    fn test_parse_bracketed_names() {
+        let parser = TreeParser::new();
+        // Next.js等的路由文件名带方括号，不是tree的元数据
+        let input =
+            "├── [slug]\n│   └── [id].tsx\n├── [...all].tsx\n└── [        100]  [lang].json\n";
+        let items = parser.parse(input, false).unwrap();
+
+        let parsed: Vec<_> = items[..4]
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("[slug]", None),
+                ("[slug]/[id].tsx", None),
+                ("[...all].tsx", None),
+                ("[lang].json", Some(100)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_symlink_target() {
+        let parser = TreeParser::new();
+        let input = "├── [lrwxrwxrwx        11]  latest -> src/main.rs\n├── lib -> ../shared/lib\n└── notes - draft.md\n";
+        let items = parser.parse(input, false).unwrap();
+
+        let parsed: Vec<_> = items[..3]
+            .iter()
+            .map(|item| (item.name.as_str(), item.link_target.as_deref()))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("latest", Some("src/main.rs")),
+                ("lib", Some("../shared/lib")),
+                ("notes - draft.md", None),
+            ]
+        );
+        assert_eq!(items[0].permissions.as_deref(), Some("lrwxrwxrwx"));
+        assert_eq!(items[0].size, Some(11));
+    }
+}
@@ -0,0 +1,376 @@
+//! PowerShell `Get-ChildItem -Recurse` 输出解析（默认表格、Format-List及Export-Csv）
+
+use anyhow::Context;
+use chrono::NaiveDateTime;
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::flat::{build_tree, FlatEntry};
+use crate::parser::TreeItem;
+
+/// Get-ChildItem输出解析器
+///
+/// 表格和Format-List输出按 "Directory: C:\..." 标题分段，条目路径为标题目录加名称；
+/// Export-Csv按FullName列取路径。所有条目共同的上级目录作为根目录。
+#[derive(Debug, Default)]
+pub struct PowerShellParser;
+
+impl PowerShellParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 解析Get-ChildItem输出，返回与TreeParser::parse相同结构的项目列表
+    pub fn parse(&self, input: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let input = input.trim_start_matches('\u{feff}');
+        let first_line = input.lines().find(|line| !line.trim().is_empty());
+        let entries = match first_line {
+            Some(line) if line.starts_with("#TYPE") || line.starts_with('"') => {
+                self.parse_csv(input)?
+            }
+            _ if input.lines().any(|line| self.list_field(line).is_some()) => {
+                self.parse_list(input)
+            }
+            _ => self.parse_table(input),
+        };
+        if entries.is_empty() {
+            return Err(Error::parse(
+                None,
+                "没有找到Get-ChildItem的条目，请确认输入为Get-ChildItem -Recurse的输出",
+            ));
+        }
+
+        // 以所有条目共同的上级目录为根目录，条目路径改为相对路径
+        let root = entries
+            .iter()
+            .filter_map(|entry| entry.path.rsplit_once('/').map(|(parent, _)| parent))
+            .min_by_key(|parent| parent.len())
+            .filter(|root| {
+                entries
+                    .iter()
+                    .all(|entry| entry.path.starts_with(&format!("{root}/")))
+            })
+            .map(str::to_string);
+        let entries = entries
+            .into_iter()
+            .map(|entry| match &root {
+                Some(root) => FlatEntry {
+                    path: entry.path[root.len() + 1..].to_string(),
+                    ..entry
+                },
+                None => entry,
+            })
+            .collect();
+
+        let mut items = build_tree(entries, include_hidden);
+        if let Some(root) = root {
+            // 保留Windows路径的反斜杠
+            let root = if input.contains('\\') {
+                root.replace('/', "\\")
+            } else {
+                root
+            };
+            for item in items.iter_mut().filter(|item| !item.name.starts_with("📊")) {
+                item.root = Some(root.clone());
+            }
+        }
+        Ok(items)
+    }
+
+    /// 默认表格输出：分隔线 "----  -------------  ------ ----" 的最后一段对齐Name列，
+    /// 之前依次为Mode、LastWriteTime和Length（目录没有Length）
+    fn parse_table(&self, input: &str) -> Vec<FlatEntry> {
+        let mut entries = Vec::new();
+        let mut directory: Option<String> = None;
+        let mut name_col = None;
+        for line in input.lines() {
+            let line = line.trim_end();
+            if let Some(path) = self.directory_header(line) {
+                directory = Some(path);
+                name_col = None;
+                continue;
+            }
+            if line.starts_with("----") {
+                name_col = line.rfind(' ').map(|col| col + 1);
+                continue;
+            }
+            let (Some(directory), Some(name_col)) = (&directory, name_col) else {
+                continue;
+            };
+            let chars: Vec<char> = line.chars().collect();
+            if chars.len() <= name_col {
+                continue;
+            }
+            let meta: String = chars[..name_col].iter().collect();
+            let name: String = chars[name_col..].iter().collect();
+            let tokens: Vec<&str> = meta.split_whitespace().collect();
+            let Some((&mode, rest)) = tokens.split_first() else {
+                continue;
+            };
+            if !self.is_mode(mode) {
+                continue;
+            }
+            let (size, date) = match rest.split_last() {
+                Some((length, date)) if !mode.starts_with('d') && length.parse::<u64>().is_ok() => {
+                    (length.parse().ok(), date)
+                }
+                _ => (None, rest),
+            };
+            entries.push(self.entry(
+                &format!("{directory}/{name}"),
+                mode,
+                size,
+                self.parse_datetime(&date.join(" ")),
+            ));
+        }
+        entries
+    }
+
+    /// Format-List输出：空行分隔的 "属性 : 值" 块（FullName或Directory标题加Name确定路径）
+    fn parse_list(&self, input: &str) -> Vec<FlatEntry> {
+        let mut entries = Vec::new();
+        let mut directory = String::new();
+        let mut block: HashMap<&str, &str> = HashMap::new();
+        for line in input.lines().chain([""]) {
+            if let Some((key, value)) = self.list_field(line) {
+                block.insert(key, value);
+                continue;
+            }
+            if !block.is_empty() {
+                let path = match (block.get("FullName"), block.get("Name")) {
+                    (Some(full_name), _) => Some(full_name.replace('\\', "/")),
+                    (None, Some(name)) => Some(format!("{directory}/{name}")),
+                    (None, None) => None,
+                };
+                if let Some(path) = path {
+                    let mode = block.get("Mode").copied().unwrap_or("-a---");
+                    let mut entry = self.entry(
+                        &path,
+                        mode,
+                        block.get("Length").and_then(|length| length.parse().ok()),
+                        block
+                            .get("LastWriteTime")
+                            .and_then(|time| self.parse_datetime(time)),
+                    );
+                    entry.link_target = ["LinkTarget", "Target"]
+                        .iter()
+                        .find_map(|key| block.get(key).filter(|target| !target.is_empty()))
+                        .map(|target| target.to_string());
+                    entries.push(entry);
+                }
+                block.clear();
+            }
+            if let Some(path) = self.directory_header(line.trim_end()) {
+                directory = path;
+            }
+        }
+        entries
+    }
+
+    /// Export-Csv输出（可带 "#TYPE" 行），按列名读取FullName、Mode、Length、LastWriteTime
+    fn parse_csv(&self, input: &str) -> Result<Vec<FlatEntry>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .comment(Some(b'#'))
+            .flexible(true)
+            .from_reader(input.as_bytes());
+        let headers = reader.headers().context("无法读取CSV表头")?.clone();
+        let column = |name: &str| headers.iter().position(|header| header == name);
+        let Some(full_name) = column("FullName") else {
+            return Err(Error::parse(
+                Some(1),
+                "CSV中没有FullName列，请用Export-Csv导出Get-ChildItem的完整属性",
+            ));
+        };
+        let (mode, length, modified, container, target) = (
+            column("Mode"),
+            column("Length"),
+            column("LastWriteTime"),
+            column("PSIsContainer"),
+            column("LinkTarget").or_else(|| column("Target")),
+        );
+
+        let mut entries = Vec::new();
+        for record in reader.records() {
+            let record = record.context("无法读取CSV记录")?;
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|index| record.get(index))
+                    .filter(|value| !value.is_empty())
+            };
+            let Some(path) = field(Some(full_name)) else {
+                continue;
+            };
+            let is_dir = field(container) == Some("True");
+            let mode = field(mode).unwrap_or(if is_dir { "d----" } else { "-a---" });
+            let mut entry = self.entry(
+                &path.replace('\\', "/"),
+                mode,
+                field(length).and_then(|length| length.parse().ok()),
+                field(modified).and_then(|time| self.parse_datetime(time)),
+            );
+            entry.link_target = field(target).map(str::to_string);
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    /// 按Mode创建条目，Mode（如 "d----"、"-a---"）写入权限列
+    fn entry(
+        &self,
+        path: &str,
+        mode: &str,
+        size: Option<u64>,
+        modified: Option<NaiveDateTime>,
+    ) -> FlatEntry {
+        let is_dir = mode.starts_with('d');
+        let (path, link_target) = match path.split_once(" -> ") {
+            Some((path, target)) if mode.starts_with('l') => (path, Some(target.to_string())),
+            _ => (path, None),
+        };
+        FlatEntry {
+            path: path.replace('\\', "/"),
+            is_dir,
+            size: size.filter(|_| !is_dir),
+            modified,
+            permissions: Some(mode.to_string()),
+            link_target,
+            ..Default::default()
+        }
+    }
+
+    /// "Directory: C:\path"（中文系统为 "目录: C:\path"）标题行，返回以"/"分隔的目录
+    fn directory_header(&self, line: &str) -> Option<String> {
+        let (label, path) = line.trim().split_once(": ")?;
+        let absolute = path.starts_with(['/', '\\'])
+            || path.get(1..3).is_some_and(|drive| drive == ":\\")
+            || path.starts_with("Microsoft.PowerShell.Core\\");
+        (!label.is_empty() && !label.contains(' ') && absolute).then(|| {
+            path.trim()
+                .replace('\\', "/")
+                .trim_end_matches('/')
+                .to_string()
+        })
+    }
+
+    /// Format-List的 "Name           : value" 行（属性名后有对齐用的空格）
+    fn list_field<'a>(&self, line: &'a str) -> Option<(&'a str, &'a str)> {
+        let (key, value) = line
+            .split_once(" : ")
+            .or_else(|| line.trim_end().strip_suffix(" :").map(|key| (key, "")))?;
+        let key = key.trim_end();
+        (!key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric()))
+            .then(|| (key, value.trim()))
+    }
+
+    /// 判断是否为Mode列，如 "d----"、"-a---"、"la---"、"d-r---"
+    fn is_mode(&self, token: &str) -> bool {
+        (5..=6).contains(&token.len())
+            && token
+                .chars()
+                .all(|c| matches!(c, 'd' | 'l' | 'a' | 'r' | 'h' | 's' | '-'))
+    }
+
+    /// 解析LastWriteTime，格式随系统区域设置而不同（如 "1/15/2024 12:34 PM"、"2024/1/15 12:34"）
+    fn parse_datetime(&self, text: &str) -> Option<NaiveDateTime> {
+        const FORMATS: [&str; 10] = [
+            "%m/%d/%Y %I:%M:%S %p",
+            "%m/%d/%Y %I:%M %p",
+            "%m/%d/%Y %H:%M:%S",
+            "%m/%d/%Y %H:%M",
+            "%d/%m/%Y %H:%M:%S",
+            "%d/%m/%Y %H:%M",
+            "%Y/%m/%d %H:%M:%S",
+            "%Y/%m/%d %H:%M",
+            "%d.%m.%Y %H:%M:%S",
+            "%d.%m.%Y %H:%M",
+        ];
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(&text, format).ok())
+            .or_else(|| crate::listing::parse_datetime(&text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_get_child_item() {
+        let table = r"
+    Directory: C:\Users\alice\project
+
+Mode                 LastWriteTime         Length Name
+----                 -------------         ------ ----
+d-----         1/15/2024  12:34 PM                src
+-a----         1/15/2024  12:30 PM            120 README.md
+
+    Directory: C:\Users\alice\project\src
+
+Mode                 LastWriteTime         Length Name
+----                 -------------         ------ ----
+-a----         1/15/2024  12:34 PM           1234 main.rs
+";
+        let items = PowerShellParser::new().parse(table, false).unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file, item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("src", false, None),
+                ("src/main.rs", true, Some(1234)),
+                ("README.md", true, Some(120)),
+                ("📊 统计: 1 directories, 2 files", false, None),
+            ]
+        );
+        assert_eq!(items[0].root.as_deref(), Some(r"C:\Users\alice\project"));
+        assert_eq!(
+            items[2].modified.unwrap().to_string(),
+            "2024-01-15 12:30:00"
+        );
+        assert_eq!(items[2].permissions.as_deref(), Some("-a----"));
+
+        let list = r"
+    目录: C:\data
+
+Name           : docs
+LastWriteTime  : 2024/1/15 12:34:56
+Mode           : d-----
+
+Name           : notes.txt
+Length         : 42
+LastWriteTime  : 2024/1/15 12:34:56
+Mode           : -a----
+
+    目录: C:\data\docs
+
+Name           : guide.md
+Length         : 7
+Mode           : -a----
+";
+        let items = PowerShellParser::new().parse(list, false).unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("docs", None),
+                ("docs/guide.md", Some(7)),
+                ("notes.txt", Some(42)),
+                ("📊 统计: 1 directories, 2 files", None),
+            ]
+        );
+
+        let csv = "#TYPE System.IO.FileInfo\n\"FullName\",\"Length\",\"Mode\",\"PSIsContainer\",\"LastWriteTime\"\n\"C:\\data\\docs\",\"\",\"d-----\",\"True\",\"1/15/2024 12:34:56 PM\"\n\"C:\\data\\docs\\a, b.md\",\"7\",\"-a----\",\"False\",\"1/15/2024 12:34:56 PM\"\n";
+        let items = PowerShellParser::new().parse(csv, false).unwrap();
+        assert_eq!(items[1].full_path, "docs/a, b.md");
+        assert_eq!(items[1].size, Some(7));
+        assert!(!items[0].is_file);
+        assert_eq!(items[0].root.as_deref(), Some(r"C:\data"));
+    }
+}
@@ -0,0 +1,114 @@
+//! S3存储桶对象列表（--s3，需启用s3特性）：键的前缀按"/"拆分为目录
+
+use anyhow::{anyhow, Context};
+use aws_sdk_s3::error::DisplayErrorContext;
+use chrono::{DateTime, Local};
+
+use crate::error::{bail, Result};
+use crate::flat::{build_tree, FlatEntry};
+use crate::parser::TreeItem;
+
+/// 拆分 "s3://bucket/prefix" 为(存储桶, 前缀)
+pub fn parse_s3_url(url: &str) -> Result<(String, String)> {
+    let Some(rest) = url.strip_prefix("s3://") else {
+        bail!("S3地址应以s3://开头: {url}");
+    };
+    let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+    if bucket.is_empty() {
+        bail!("S3地址中缺少存储桶名称: {url}");
+    }
+    Ok((bucket.to_string(), prefix.to_string()))
+}
+
+/// S3对象列表读取器：凭据、区域及自定义端点（AWS_ENDPOINT_URL）按AWS SDK的默认方式加载
+#[derive(Debug, Default)]
+pub struct S3Lister;
+
+impl S3Lister {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 列出 "s3://bucket/prefix" 下的全部对象，返回与TreeParser::parse相同结构的项目列表；
+    /// 路径相对于前缀中最后一个"/"，大小、修改时间及存储类别来自对象元数据
+    pub fn list(&self, url: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let (bucket, prefix) = parse_s3_url(url)?;
+        let base = prefix.rfind('/').map_or("", |end| &prefix[..=end]);
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .context("无法创建异步运行时")?;
+        let entries = runtime.block_on(async {
+            let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+            // 兼容S3的自定义端点（如MinIO）通常只支持路径形式的地址
+            let s3_config = aws_sdk_s3::config::Builder::from(&config)
+                .force_path_style(config.endpoint_url().is_some())
+                .build();
+            let client = aws_sdk_s3::Client::from_conf(s3_config);
+            let mut pages = client
+                .list_objects_v2()
+                .bucket(&bucket)
+                .prefix(&prefix)
+                .into_paginator()
+                .send();
+
+            let mut entries = Vec::new();
+            while let Some(page) = pages.next().await {
+                let page = page.map_err(|err| anyhow!("{}", DisplayErrorContext(err)))?;
+                for object in page.contents() {
+                    let Some(key) = object.key() else {
+                        continue;
+                    };
+                    let entry = FlatEntry::new(key.strip_prefix(base).unwrap_or(key));
+                    entries.push(FlatEntry {
+                        size: object
+                            .size()
+                            .and_then(|size| u64::try_from(size).ok())
+                            .filter(|_| !entry.is_dir),
+                        modified: object
+                            .last_modified()
+                            .and_then(|datetime| DateTime::from_timestamp(datetime.secs(), 0))
+                            .map(|datetime| datetime.with_timezone(&Local).naive_local()),
+                        storage_class: object
+                            .storage_class()
+                            .map(|class| class.as_str().to_string()),
+                        ..entry
+                    });
+                }
+            }
+            anyhow::Ok(entries)
+        });
+        let entries = entries.with_context(|| format!("无法列出S3对象: {url}"))?;
+        if entries.is_empty() {
+            bail!("{url} 下没有对象");
+        }
+
+        let root = format!("s3://{bucket}/{base}");
+        let root = root.trim_end_matches('/');
+        let mut items = build_tree(entries, include_hidden);
+        for item in items.iter_mut().filter(|item| !item.name.starts_with("📊")) {
+            item.root = Some(root.to_string());
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_s3_url() {
+        assert_eq!(
+            parse_s3_url("s3://logs/2024/app").unwrap(),
+            ("logs".to_string(), "2024/app".to_string())
+        );
+        assert_eq!(
+            parse_s3_url("s3://logs").unwrap(),
+            ("logs".to_string(), String::new())
+        );
+        assert!(parse_s3_url("https://logs").is_err());
+        assert!(parse_s3_url("s3:///prefix").is_err());
+    }
+}
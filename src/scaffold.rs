@@ -0,0 +1,154 @@
+//! 按表格中的目录结构生成mkdir/touch脚本或直接创建（scaffold子命令）
+
+use anyhow::Context;
+use std::fs;
+use std::io::Write;
+use std::path::{Component, Path};
+
+use crate::error::{bail, Result};
+use crate::output::open_output;
+use crate::parser::{EntryKind, TreeItem};
+
+/// 生成的脚本类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    PowerShell,
+}
+
+impl Shell {
+    /// 按名称（bash或powershell）选择脚本类型
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "bash" | "sh" => Ok(Shell::Bash),
+            "powershell" | "pwsh" => Ok(Shell::PowerShell),
+            _ => bail!("不支持的脚本类型: {name}（可选bash或powershell）"),
+        }
+    }
+}
+
+/// 要创建的目录和文件（相对路径，按表格中的顺序）
+fn entries(items: &[TreeItem]) -> Result<Vec<(String, bool)>> {
+    let mut entries = Vec::new();
+    for item in items {
+        // 统计行及符号链接不创建
+        if item.name.starts_with("📊") || item.kind == EntryKind::Symlink {
+            continue;
+        }
+        let path = match item.root.as_deref() {
+            Some(root) if root != "." => format!("{root}/{}", item.full_path),
+            _ => item.full_path.clone(),
+        };
+        // 只允许在目标目录之内创建
+        let safe = Path::new(&path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if !safe {
+            bail!("路径超出目标目录，已拒绝: {path}");
+        }
+        entries.push((path, item.is_file));
+    }
+    Ok(entries)
+}
+
+/// 渲染为bash或PowerShell脚本：目录用mkdir -p，文件用touch（已存在的文件不覆盖）
+pub fn render_script(items: &[TreeItem], shell: Shell) -> Result<String> {
+    let mut script = match shell {
+        Shell::Bash => String::from(
+            "#!/usr/bin/env bash\n# 由tree-to-excel scaffold生成\nset -euo pipefail\n\n",
+        ),
+        Shell::PowerShell => {
+            String::from("# 由tree-to-excel scaffold生成\n$ErrorActionPreference = 'Stop'\n\n")
+        }
+    };
+    for (path, is_file) in entries(items)? {
+        let line = match (shell, is_file) {
+            (Shell::Bash, false) => format!("mkdir -p -- '{}'", path.replace('\'', r"'\''")),
+            (Shell::Bash, true) => format!("touch -- '{}'", path.replace('\'', r"'\''")),
+            (Shell::PowerShell, false) => format!(
+                "New-Item -ItemType Directory -Force -Path '{}' | Out-Null",
+                path.replace('\'', "''")
+            ),
+            (Shell::PowerShell, true) => {
+                let path = path.replace('\'', "''");
+                format!(
+                    "if (-not (Test-Path -LiteralPath '{path}')) {{ New-Item -ItemType File -Path '{path}' | Out-Null }}"
+                )
+            }
+        };
+        script.push_str(&line);
+        script.push('\n');
+    }
+    Ok(script)
+}
+
+/// 生成脚本文件（"-"表示标准输出）
+pub fn write_script(items: &[TreeItem], shell: Shell, output_path: &str) -> Result<()> {
+    let script = render_script(items, shell)?;
+    let mut writer = open_output(output_path)?;
+    writer
+        .write_all(script.as_bytes())
+        .and_then(|()| writer.flush())
+        .with_context(|| format!("无法保存脚本: {output_path}"))?;
+    Ok(())
+}
+
+/// 在target下直接创建目录和空文件（已存在的文件保持不变），返回(目录数, 文件数)
+pub fn create_structure(target: &Path, items: &[TreeItem]) -> Result<(usize, usize)> {
+    let (mut dirs, mut files) = (0, 0);
+    for (path, is_file) in entries(items)? {
+        let path = target.join(path);
+        if is_file {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("无法创建目录: {}", parent.display()))?;
+            }
+            fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .with_context(|| format!("无法创建文件: {}", path.display()))?;
+            files += 1;
+        } else {
+            fs::create_dir_all(&path)
+                .with_context(|| format!("无法创建目录: {}", path.display()))?;
+            dirs += 1;
+        }
+    }
+    Ok((dirs, files))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_scaffold() {
+        let input =
+            ".\n├── docs\n│   └── it's.md\n└── src\n    └── main.rs\n\n2 directories, 2 files\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+
+        let script = render_script(&items, Shell::Bash).unwrap();
+        assert!(script.ends_with(
+            "mkdir -p -- 'docs'\ntouch -- 'docs/it'\\''s.md'\nmkdir -p -- 'src'\ntouch -- 'src/main.rs'\n"
+        ));
+        let script = render_script(&items, Shell::PowerShell).unwrap();
+        assert!(script.contains("Test-Path -LiteralPath 'docs/it''s.md'"));
+
+        let target = std::env::temp_dir().join("tree_to_excel_scaffold_test");
+        let _ = fs::remove_dir_all(&target);
+        assert_eq!(create_structure(&target, &items).unwrap(), (2, 2));
+        assert!(target.join("src/main.rs").is_file());
+        fs::remove_dir_all(&target).unwrap();
+
+        let escape = TreeItem {
+            name: "passwd".to_string(),
+            level: 1,
+            is_file: true,
+            full_path: "../etc/passwd".to_string(),
+            ..Default::default()
+        };
+        assert!(render_script(&[escape], Shell::Bash).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! 通过SFTP列出远程目录（--ssh，需启用ssh特性），远程主机上无需安装tree或本工具
+
+use anyhow::Context;
+use chrono::{DateTime, Local};
+use ssh2::{CheckResult, KnownHostFileKind, Session, Sftp};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+
+use crate::error::{bail, Result};
+use crate::flat::{build_tree, mode_string, FlatEntry};
+use crate::parser::TreeItem;
+
+/// "user@host:/path" 形式的远程目录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshTarget {
+    pub user: String,
+    pub host: String,
+    pub path: String,
+}
+
+impl SshTarget {
+    /// 解析 "[user@]host:/path"，省略用户名时取当前用户，省略路径时为登录目录
+    pub fn parse(target: &str) -> Result<Self> {
+        let Some((login, path)) = target.split_once(':') else {
+            bail!("SSH目标应为 user@host:/path 的形式: {target}");
+        };
+        let (user, host) = match login.split_once('@') {
+            Some((user, host)) => (user.to_string(), host),
+            None => (
+                std::env::var("USER")
+                    .or_else(|_| std::env::var("USERNAME"))
+                    .context("无法确定SSH用户名，请使用 user@host:/path 的形式")?,
+                login,
+            ),
+        };
+        if host.is_empty() || user.is_empty() {
+            bail!("SSH目标中缺少用户名或主机名: {target}");
+        }
+        Ok(Self {
+            user,
+            host: host.to_string(),
+            path: if path.is_empty() { "." } else { path }.to_string(),
+        })
+    }
+}
+
+/// SFTP目录读取器：主机密钥按~/.ssh/known_hosts校验，
+/// 依次尝试ssh-agent、~/.ssh下的默认密钥及SSHPASS环境变量中的密码
+#[derive(Debug)]
+pub struct SftpLister {
+    port: u16,
+}
+
+impl Default for SftpLister {
+    fn default() -> Self {
+        Self { port: 22 }
+    }
+}
+
+impl SftpLister {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置SSH端口
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// 递归列出远程目录，返回与TreeParser::parse相同结构的项目列表；
+    /// 无法读取的子目录记入错误，符号链接不跟随
+    pub fn list(&self, target: &SshTarget, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let tcp = TcpStream::connect((target.host.as_str(), self.port))
+            .with_context(|| format!("无法连接到 {}:{}", target.host, self.port))?;
+        let mut session = Session::new().context("无法创建SSH会话")?;
+        session.set_tcp_stream(tcp);
+        session.set_timeout(30_000);
+        session.handshake().context("SSH握手失败")?;
+        self.verify_host_key(&session, &target.host)?;
+        self.authenticate(&session, &target.user)?;
+
+        let sftp = session.sftp().context("无法启动SFTP子系统")?;
+        let root = PathBuf::from(&target.path);
+        let mut entries = Vec::new();
+        self.walk(&sftp, &root, "", include_hidden, &mut entries)
+            .with_context(|| format!("无法读取远程目录: {}", target.path))?;
+
+        let root = format!("{}:{}", target.host, target.path);
+        let mut items = build_tree(entries, include_hidden);
+        for item in items.iter_mut().filter(|item| !item.name.starts_with("📊")) {
+            item.root = Some(root.clone());
+        }
+        Ok(items)
+    }
+
+    /// 按known_hosts校验主机密钥，未登记或不一致时拒绝连接
+    fn verify_host_key(&self, session: &Session, host: &str) -> Result<()> {
+        let mut known_hosts = session.known_hosts()?;
+        let file = ssh_dir().map(|dir| dir.join("known_hosts"));
+        if let Some(file) = file.filter(|file| file.exists()) {
+            known_hosts
+                .read_file(&file, KnownHostFileKind::OpenSSH)
+                .with_context(|| format!("无法读取 {}", file.display()))?;
+        }
+        let (key, _) = session.host_key().context("无法获取远程主机密钥")?;
+        match known_hosts.check_port(host, self.port, key) {
+            CheckResult::Match => Ok(()),
+            CheckResult::Mismatch => {
+                bail!("{host} 的主机密钥与known_hosts中的记录不符，已拒绝连接")
+            }
+            CheckResult::NotFound | CheckResult::Failure => {
+                bail!("known_hosts中没有 {host} 的主机密钥，请先用ssh连接一次并确认指纹")
+            }
+        }
+    }
+
+    /// 依次尝试ssh-agent、默认密钥文件和SSHPASS中的密码
+    fn authenticate(&self, session: &Session, user: &str) -> Result<()> {
+        if session.userauth_agent(user).is_ok() && session.authenticated() {
+            return Ok(());
+        }
+        for name in ["id_ed25519", "id_ecdsa", "id_rsa"] {
+            let Some(key) = ssh_dir().map(|dir| dir.join(name)) else {
+                break;
+            };
+            if key.exists() && session.userauth_pubkey_file(user, None, &key, None).is_ok() {
+                return Ok(());
+            }
+        }
+        if let Ok(password) = std::env::var("SSHPASS") {
+            session
+                .userauth_password(user, &password)
+                .context("SSH密码认证失败")?;
+            return Ok(());
+        }
+        bail!("SSH认证失败：请使用ssh-agent、~/.ssh下的默认密钥，或通过SSHPASS环境变量提供密码")
+    }
+
+    /// 深度优先列出dir，同一目录下按名称排序
+    fn walk(
+        &self,
+        sftp: &Sftp,
+        dir: &Path,
+        parent_path: &str,
+        include_hidden: bool,
+        entries: &mut Vec<FlatEntry>,
+    ) -> Result<(), ssh2::Error> {
+        let mut listing = sftp.readdir(dir)?;
+        listing.sort_by(|(a, _), (b, _)| a.file_name().cmp(&b.file_name()));
+        for (path, stat) in listing {
+            let Some(name) = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+            else {
+                continue;
+            };
+            if name == "." || name == ".." || (!include_hidden && name.starts_with('.')) {
+                continue;
+            }
+            let relative = if parent_path.is_empty() {
+                name
+            } else {
+                format!("{parent_path}/{name}")
+            };
+            let is_symlink = stat.file_type().is_symlink();
+            let is_dir = stat.is_dir();
+            let type_char = match () {
+                _ if is_dir => 'd',
+                _ if is_symlink => 'l',
+                _ => '-',
+            };
+            entries.push(FlatEntry {
+                is_dir,
+                size: stat.size.filter(|_| !is_dir),
+                modified: stat
+                    .mtime
+                    .and_then(|seconds| DateTime::from_timestamp(seconds as i64, 0))
+                    .map(|datetime| datetime.with_timezone(&Local).naive_local()),
+                permissions: stat.perm.map(|mode| mode_string(type_char, mode)),
+                owner: stat.uid.map(|uid| uid.to_string()),
+                group: stat.gid.map(|gid| gid.to_string()),
+                link_target: is_symlink
+                    .then(|| sftp.readlink(&path).ok())
+                    .flatten()
+                    .map(|target| target.to_string_lossy().into_owned()),
+                ..FlatEntry::new(&relative)
+            });
+            if is_dir {
+                let index = entries.len() - 1;
+                if let Err(err) = self.walk(sftp, &path, &relative, include_hidden, entries) {
+                    entries[index].error = Some(err.message().to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 当前用户的~/.ssh目录
+fn ssh_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".ssh"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ssh_target() {
+        let target = SshTarget::parse("deploy@web01:/var/www").unwrap();
+        assert_eq!(
+            target,
+            SshTarget {
+                user: "deploy".to_string(),
+                host: "web01".to_string(),
+                path: "/var/www".to_string(),
+            }
+        );
+        assert_eq!(SshTarget::parse("deploy@web01:").unwrap().path, ".");
+        assert!(SshTarget::parse("web01").is_err());
+        assert!(SshTarget::parse("@web01:/srv").is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! 同级项目排序（--sort/--dirs-first）
+
+use std::cmp::Ordering;
+
+use crate::parser::TreeItem;
+
+/// 排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Name, // 按名称（不区分大小写）
+    Ext,  // 按扩展名，其次按名称
+    Size, // 按大小从大到小，无大小的排在最后
+    Type, // 按条目类型，其次按名称
+}
+
+/// 同级项目排序器：整棵子树随父项目一起移动，保证层级列合并正确
+#[derive(Debug, Default)]
+pub struct TreeSorter {
+    key: Option<SortKey>,
+    dirs_first: bool, // 目录排在文件之前
+}
+
+impl TreeSorter {
+    pub fn new(key: Option<SortKey>, dirs_first: bool) -> Self {
+        Self { key, dirs_first }
+    }
+
+    /// 对项目列表排序，统计信息保持在末尾
+    pub fn sort(&self, items: Vec<TreeItem>) -> Vec<TreeItem> {
+        if self.key.is_none() && !self.dirs_first {
+            return items;
+        }
+
+        let (items, stats): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .partition(|item| !item.name.starts_with("📊"));
+
+        // 多根输入时只在各自根目录的分段内排序
+        let mut result = Vec::with_capacity(items.len() + stats.len());
+        let mut start = 0;
+        for end in 1..=items.len() {
+            if end == items.len() || items[end].root != items[start].root {
+                result.extend(self.sort_siblings(&items[start..end]));
+                start = end;
+            }
+        }
+        result.extend(stats);
+        result
+    }
+
+    /// 将同一父目录下的项目按子树分组，排序后递归处理每个子树的子项目
+    fn sort_siblings(&self, items: &[TreeItem]) -> Vec<TreeItem> {
+        let Some(first) = items.first() else {
+            return Vec::new();
+        };
+
+        let mut subtrees: Vec<&[TreeItem]> = Vec::new();
+        let mut start = 0;
+        for end in 1..=items.len() {
+            if end == items.len() || items[end].level <= first.level {
+                subtrees.push(&items[start..end]);
+                start = end;
+            }
+        }
+        subtrees.sort_by(|a, b| self.compare(&a[0], &b[0]));
+
+        let mut result = Vec::with_capacity(items.len());
+        for subtree in subtrees {
+            result.push(subtree[0].clone());
+            result.extend(self.sort_siblings(&subtree[1..]));
+        }
+        result
+    }
+
+    /// 比较两个同级项目
+    fn compare(&self, a: &TreeItem, b: &TreeItem) -> Ordering {
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        let dirs_first = if self.dirs_first {
+            a.is_file.cmp(&b.is_file)
+        } else {
+            Ordering::Equal
+        };
+
+        let by_key = match self.key {
+            None => Ordering::Equal, // 保持原有顺序
+            Some(SortKey::Name) => by_name(),
+            Some(SortKey::Ext) => self.extension(a).cmp(&self.extension(b)).then_with(by_name),
+            Some(SortKey::Size) => match (a.size, b.size) {
+                (Some(a_size), Some(b_size)) => b_size.cmp(&a_size),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+            .then_with(by_name),
+            Some(SortKey::Type) => (a.kind as u8).cmp(&(b.kind as u8)).then_with(by_name),
+        };
+        dirs_first.then(by_key)
+    }
+
+    /// 小写扩展名，目录和无扩展名的项目为空
+    fn extension(&self, item: &TreeItem) -> String {
+        match item.name.rsplit_once('.') {
+            Some((stem, ext)) if item.is_file && !stem.is_empty() => ext.to_lowercase(),
+            _ => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_sort_name_dirs_first() {
+        let input = ".\n├── b.txt\n├── zeta\n│   ├── y.rs\n│   └── x.md\n├── A.rs\n└── alpha\n    └── c.txt\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+
+        let sorted = TreeSorter::new(Some(SortKey::Name), true).sort(items.clone());
+        let paths: Vec<_> = sorted.iter().map(|item| item.full_path.as_str()).collect();
+        assert_eq!(
+            paths[..7],
+            [
+                "alpha",
+                "alpha/c.txt",
+                "zeta",
+                "zeta/x.md",
+                "zeta/y.rs",
+                "A.rs",
+                "b.txt"
+            ]
+        );
+        assert!(sorted[7].name.starts_with("📊"));
+
+        let sorted = TreeSorter::new(Some(SortKey::Ext), false).sort(items);
+        let names: Vec<_> = sorted.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names[..3], ["alpha", "c.txt", "zeta"]);
+        assert_eq!(names[5..7], ["A.rs", "b.txt"]);
+    }
+}
@@ -0,0 +1,258 @@
+//! 统计工作表的数据汇总（--stats-sheet）
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::parser::TreeItem;
+
+/// 按扩展名汇总的文件数及大小
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExtensionStats {
+    pub extension: String, // 小写扩展名，无扩展名为空
+    pub file_count: usize,
+    pub size: u64,
+}
+
+/// 按目录汇总的文件数及大小
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DirectoryStats {
+    pub file_count: usize,       // 直接包含的文件数
+    pub total_file_count: usize, // 含子目录的文件数
+    pub total_size: u64,         // 含子目录的文件大小
+    pub total_dir_count: usize,  // 含子目录的子目录数
+}
+
+/// 整棵树的统计信息，可逐项累加（流式生成时无需保留全部项目）
+#[derive(Debug, Default)]
+pub struct TreeStats {
+    pub file_count: usize,
+    pub dir_count: usize,
+    pub total_size: Option<u64>, // 文件总大小，输入没有大小时为None
+    pub max_depth: usize,
+    extensions: HashMap<String, ExtensionStats>,
+    directories: BTreeMap<String, DirectoryStats>,
+}
+
+impl TreeStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 汇总项目列表（统计行被忽略）
+    pub fn from_items<'a>(items: impl IntoIterator<Item = &'a TreeItem>) -> Self {
+        let mut stats = Self::new();
+        for item in items {
+            stats.add(item);
+        }
+        stats
+    }
+
+    /// 累加一个项目
+    pub fn add(&mut self, item: &TreeItem) {
+        if item.name.starts_with("📊") {
+            return;
+        }
+        self.max_depth = self.max_depth.max(item.level);
+
+        if !item.is_file {
+            self.dir_count += 1;
+            self.directories.entry(item.full_path.clone()).or_default();
+            // 各级上级目录（直到根目录"."）计入含子目录的子目录数
+            let mut ancestor = Some(parent_of(&item.full_path));
+            while let Some(path) = ancestor {
+                ancestor = (path != ".").then(|| parent_of(&path));
+                self.directories.entry(path).or_default().total_dir_count += 1;
+            }
+            return;
+        }
+
+        self.file_count += 1;
+        let size = item.size.unwrap_or(0);
+        if let Some(file_size) = item.size {
+            self.total_size = Some(self.total_size.unwrap_or(0) + file_size);
+        }
+
+        let extension = match item.name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() => ext.to_lowercase(),
+            _ => String::new(),
+        };
+        let entry = self
+            .extensions
+            .entry(extension.clone())
+            .or_insert_with(|| ExtensionStats {
+                extension,
+                ..Default::default()
+            });
+        entry.file_count += 1;
+        entry.size += size;
+
+        // 所在目录计入直接文件数，各级上级目录（直到根目录"."）计入含子目录的文件数
+        let parent = parent_of(&item.full_path);
+        self.directories
+            .entry(parent.clone())
+            .or_default()
+            .file_count += 1;
+        let mut ancestor = Some(parent);
+        while let Some(path) = ancestor {
+            ancestor = (path != ".").then(|| parent_of(&path));
+            let directory = self.directories.entry(path).or_default();
+            directory.total_file_count += 1;
+            directory.total_size += size;
+        }
+    }
+
+    /// 各扩展名的统计，按文件数从多到少排列
+    pub fn extensions(&self) -> Vec<&ExtensionStats> {
+        let mut extensions: Vec<_> = self.extensions.values().collect();
+        extensions.sort_by(|a, b| {
+            b.file_count
+                .cmp(&a.file_count)
+                .then_with(|| a.extension.cmp(&b.extension))
+        });
+        extensions
+    }
+
+    /// 单个目录的统计（"."为根目录）
+    pub fn directory(&self, path: &str) -> Option<&DirectoryStats> {
+        self.directories.get(path)
+    }
+
+    /// 各目录的统计（"."为根目录），按路径排列
+    pub fn directories(&self) -> impl Iterator<Item = (&str, &DirectoryStats)> {
+        self.directories
+            .iter()
+            .map(|(path, stats)| (path.as_str(), stats))
+    }
+}
+
+/// Windows传统的路径长度上限（MAX_PATH）
+pub const MAX_PATH_LEN: usize = 260;
+
+/// 最大的文件及最长的路径（--top），可逐项累加，只保留前limit项
+#[derive(Debug, Default)]
+pub struct TopItems {
+    limit: usize,
+    largest: Vec<(Reverse<u64>, String)>, // (大小, 路径)，排序后从大到小
+    longest: Vec<(Reverse<usize>, Reverse<usize>, String)>, // (路径长度, 深度, 路径)
+}
+
+impl TopItems {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    /// 汇总项目列表（统计行被忽略）
+    pub fn from_items<'a>(limit: usize, items: impl IntoIterator<Item = &'a TreeItem>) -> Self {
+        let mut top = Self::new(limit);
+        for item in items {
+            top.add(item);
+        }
+        top
+    }
+
+    /// 累加一个项目；超过limit的两倍时排序并截断，不必保留全部项目
+    pub fn add(&mut self, item: &TreeItem) {
+        if item.name.starts_with("📊") {
+            return;
+        }
+        if let (true, Some(size)) = (item.is_file, item.size) {
+            self.largest.push((Reverse(size), item.full_path.clone()));
+            if self.largest.len() > self.limit * 2 {
+                keep_first(&mut self.largest, self.limit);
+            }
+        }
+        let length = item.full_path.chars().count();
+        let depth = item.full_path.split('/').count();
+        self.longest
+            .push((Reverse(length), Reverse(depth), item.full_path.clone()));
+        if self.longest.len() > self.limit * 2 {
+            keep_first(&mut self.longest, self.limit);
+        }
+    }
+
+    /// 最大的文件：(路径, 大小)，从大到小排列；输入没有大小时为空
+    pub fn largest(&self) -> Vec<(&str, u64)> {
+        let mut entries: Vec<_> = self.largest.iter().collect();
+        entries.sort();
+        entries
+            .into_iter()
+            .take(self.limit)
+            .map(|(Reverse(size), path)| (path.as_str(), *size))
+            .collect()
+    }
+
+    /// 最长的路径：(路径, 长度（字符数）, 深度)，从长到短排列
+    pub fn longest(&self) -> Vec<(&str, usize, usize)> {
+        let mut entries: Vec<_> = self.longest.iter().collect();
+        entries.sort();
+        entries
+            .into_iter()
+            .take(self.limit)
+            .map(|(Reverse(length), Reverse(depth), path)| (path.as_str(), *length, *depth))
+            .collect()
+    }
+}
+
+/// 排序并只保留前limit项
+fn keep_first<T: Ord>(entries: &mut Vec<T>, limit: usize) {
+    entries.sort();
+    entries.truncate(limit);
+}
+
+/// 上级目录的路径，第一层的项目为"."
+fn parent_of(path: &str) -> String {
+    path.rsplit_once('/')
+        .map_or(".", |(parent, _)| parent)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_tree_stats() {
+        let input = ".\n├── [        100]  README.md\n└── [       4096]  src\n    ├── [         20]  lib.rs\n    └── [       4096]  bin\n        └── [         30]  main.rs\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let stats = TreeStats::from_items(&items);
+
+        assert_eq!((stats.file_count, stats.dir_count), (3, 2));
+        assert_eq!(stats.total_size, Some(150));
+        assert_eq!(stats.max_depth, 3);
+
+        let extensions = stats.extensions();
+        assert_eq!(extensions[0].extension, "rs");
+        assert_eq!((extensions[0].file_count, extensions[0].size), (2, 50));
+
+        let directories: Vec<_> = stats.directories().collect();
+        let (path, root) = directories[0];
+        assert_eq!(path, ".");
+        assert_eq!((root.file_count, root.total_file_count), (1, 3));
+        assert_eq!(root.total_dir_count, 2);
+        let (path, src) = directories[1];
+        assert_eq!(path, "src");
+        assert_eq!(
+            (src.file_count, src.total_file_count, src.total_size),
+            (1, 2, 50)
+        );
+        assert_eq!(src.total_dir_count, 1);
+        assert_eq!(stats.directory("src/bin").unwrap().total_size, 30);
+    }
+
+    #[test]
+    fn test_top_items() {
+        let input = ".\n├── [        100]  README.md\n└── [       4096]  src\n    ├── [         20]  lib.rs\n    └── [       4096]  bin\n        └── [         30]  main.rs\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let top = TopItems::from_items(2, &items);
+
+        assert_eq!(top.largest(), [("README.md", 100), ("src/bin/main.rs", 30)]);
+        assert_eq!(
+            top.longest(),
+            [("src/bin/main.rs", 15, 3), ("src/lib.rs", 10, 2)]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! mdBook风格SUMMARY.md导出
+
+use anyhow::Context;
+use std::fs;
+
+use crate::error::Result;
+use crate::parser::TreeItem;
+
+/// mdBook风格SUMMARY.md生成器
+#[derive(Debug, Default)]
+pub struct SummaryGenerator;
+
+impl SummaryGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 生成SUMMARY.md文件
+    pub fn generate(&self, items: &[TreeItem], output_path: &str, md_only: bool) -> Result<()> {
+        let content = self.render(items, md_only);
+        fs::write(output_path, content)
+            .with_context(|| format!("无法保存SUMMARY文件: {output_path}"))?;
+        Ok(())
+    }
+
+    /// 将层级结构渲染为嵌套的Markdown链接列表
+    pub fn render(&self, items: &[TreeItem], md_only: bool) -> String {
+        let entries: Vec<&TreeItem> = items
+            .iter()
+            .filter(|item| !item.name.starts_with("📊"))
+            .collect();
+
+        // 仅保留.md文件时，目录只有在其下存在.md文件时才保留
+        let md_paths: Vec<&str> = entries
+            .iter()
+            .filter(|item| item.is_file && self.is_markdown(&item.name))
+            .map(|item| item.full_path.as_str())
+            .collect();
+
+        let mut content = String::from("# Summary\n\n");
+        for item in entries {
+            if md_only {
+                let keep = if item.is_file {
+                    self.is_markdown(&item.name)
+                } else {
+                    let prefix = format!("{}/", item.full_path);
+                    md_paths.iter().any(|path| path.starts_with(&prefix))
+                };
+                if !keep {
+                    continue;
+                }
+            }
+
+            let indent = "    ".repeat(item.level.saturating_sub(1));
+            if item.is_file {
+                // .md文件使用去掉扩展名的名称作为标题
+                let title = if self.is_markdown(&item.name) {
+                    &item.name[..item.name.len() - 3]
+                } else {
+                    item.name.as_str()
+                };
+                let link = if item.full_path.contains(' ') {
+                    format!("<{}>", item.full_path)
+                } else {
+                    item.full_path.clone()
+                };
+                content.push_str(&format!("{indent}- [{title}]({link})\n"));
+            } else {
+                // 目录作为草稿章节（空链接）
+                content.push_str(&format!("{indent}- [{}]()\n", item.name));
+            }
+        }
+        content
+    }
+
+    /// 判断是否为Markdown文件
+    fn is_markdown(&self, name: &str) -> bool {
+        name.len() > 3 && name.to_ascii_lowercase().ends_with(".md")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_summary_md_only() {
+        let parser = TreeParser::new();
+        let input = "├── docs\n│   ├── intro.md\n│   └── logo.png\n└── src\n    └── main.rs\n";
+        let items = parser.parse(input, false).unwrap();
+
+        let summary = SummaryGenerator::new().render(&items, true);
+        assert_eq!(
+            summary,
+            "# Summary\n\n- [docs]()\n    - [intro](docs/intro.md)\n"
+        );
+    }
+}
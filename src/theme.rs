@@ -0,0 +1,316 @@
+//! Excel配色主题（--theme）
+
+use anyhow::Context;
+use rust_xlsxwriter::{Format, FormatBorder, FormatUnderline};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::{bail, Result};
+
+/// 内置主题：名称及TOML内容（light为默认主题，其余主题在其基础上覆盖）
+const BUILTIN_THEMES: [(&str, &str); 4] = [
+    ("light", include_str!("../themes/light.toml")),
+    ("dark", include_str!("../themes/dark.toml")),
+    ("mono", include_str!("../themes/mono.toml")),
+    ("corporate", include_str!("../themes/corporate.toml")),
+];
+
+/// 单元格边框样式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Border {
+    None,
+    Thin,
+    Medium,
+    Thick,
+    Dashed,
+    Dotted,
+    Double,
+    Hair,
+}
+
+/// 一类单元格的颜色及字体样式（未设置的项目不应用）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CellStyle {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>, // 背景色，如 "#E8F4FD"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_color: Option<String>, // 字体颜色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underline: Option<bool>,
+}
+
+/// 文件类别：按扩展名匹配的文件名单元格颜色
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Category {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_color: Option<String>,
+    pub extensions: Vec<String>, // 扩展名（不含"."），无扩展名的文件匹配完整文件名，如 "makefile"
+}
+
+/// 配色主题：ExcelGenerator使用的全部单元格格式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    pub font: String,         // 字体名称
+    pub font_size: f64,       // 字号
+    pub border: Border,       // 所有单元格的边框
+    pub header: CellStyle,    // 表头
+    pub directory: CellStyle, // 目录名称
+    pub file: CellStyle,      // 文件名称
+    pub link: CellStyle,      // 符号链接名称
+    pub path: CellStyle,      // 完整路径
+    pub hyperlink: CellStyle, // 超链接
+    pub notes: CellStyle,     // 备注
+    pub secret: CellStyle,    // 安全标记
+    pub duplicate: CellStyle, // 重复文件的组编号及路径
+    pub added: CellStyle,     // 对比时新增的行
+    pub removed: CellStyle,   // 对比时删除的行
+    pub changed: CellStyle,   // 对比时大小变化的行
+    pub error: CellStyle,     // 错误行的备注
+    pub plain: CellStyle,     // 大小、时间、权限等其他列
+    pub stats: CellStyle,     // 统计行
+    #[serde(default)]
+    pub categories: BTreeMap<String, Category>, // 文件类别（code、docs、images等）
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::builtin("light").expect("内置light主题无效")
+    }
+}
+
+impl Theme {
+    /// 内置主题的名称
+    pub fn builtin_names() -> Vec<&'static str> {
+        BUILTIN_THEMES.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// 按名称加载内置主题
+    pub fn builtin(name: &str) -> Result<Self> {
+        let (_, content) = BUILTIN_THEMES
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .with_context(|| format!("未知的内置主题: {name}"))?;
+        if name == "light" {
+            return Ok(toml::from_str(content)?);
+        }
+        Self::builtin("light")?.merge_toml(content)
+    }
+
+    /// 加载主题：内置主题名称，或TOML主题文件的路径
+    pub fn load(name_or_path: &str) -> Result<Self> {
+        if Self::builtin_names().contains(&name_or_path) {
+            return Self::builtin(name_or_path);
+        }
+
+        let path = Path::new(name_or_path);
+        if !path.exists() {
+            bail!(
+                "主题不存在: {name_or_path}（内置主题: {}）",
+                Self::builtin_names().join(", ")
+            );
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("无法读取主题文件: {name_or_path}"))?;
+        Ok(Self::from_toml(&content).with_context(|| format!("主题文件无效: {name_or_path}"))?)
+    }
+
+    /// 解析TOML主题：可用 base = "dark" 指定基础主题（默认light），只需写出要覆盖的项目
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let mut table: toml::Table = toml::from_str(content)?;
+        let base = match table.remove("base") {
+            Some(toml::Value::String(base)) => Self::builtin(&base)?,
+            Some(_) => bail!("base必须是内置主题名称"),
+            None => Self::default(),
+        };
+        base.merge_toml(&toml::to_string(&table)?)
+    }
+
+    /// 用TOML内容覆盖当前主题中的对应项目
+    fn merge_toml(&self, content: &str) -> Result<Self> {
+        let mut merged = toml::Table::try_from(self)?;
+        merge_table(&mut merged, toml::from_str(content)?);
+
+        let theme: Self = merged.try_into()?;
+        theme.validate()?;
+        Ok(theme)
+    }
+
+    /// 检查所有颜色均为 #RRGGBB 格式
+    fn validate(&self) -> Result<()> {
+        let colors = self
+            .styles()
+            .into_iter()
+            .flat_map(|style| [&style.background, &style.font_color])
+            .chain(
+                self.categories
+                    .values()
+                    .flat_map(|category| [&category.background, &category.font_color]),
+            );
+        for color in colors.flatten() {
+            let valid = color.len() == 7
+                && color.starts_with('#')
+                && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                bail!("颜色格式无效（应为#RRGGBB）: {color}");
+            }
+        }
+        Ok(())
+    }
+
+    fn styles(&self) -> [&CellStyle; 15] {
+        [
+            &self.header,
+            &self.directory,
+            &self.file,
+            &self.link,
+            &self.path,
+            &self.hyperlink,
+            &self.notes,
+            &self.secret,
+            &self.duplicate,
+            &self.added,
+            &self.removed,
+            &self.changed,
+            &self.error,
+            &self.plain,
+            &self.stats,
+        ]
+    }
+
+    /// 文件所属的类别名称（按扩展名匹配，无扩展名时按完整文件名，如.env、Makefile）
+    pub fn category(&self, file_name: &str) -> Option<&str> {
+        let lower = file_name.to_lowercase();
+        let key = match lower.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() => ext,
+            _ => lower.trim_start_matches('.'),
+        };
+        self.categories
+            .iter()
+            .find(|(_, category)| {
+                category
+                    .extensions
+                    .iter()
+                    .any(|ext| ext.eq_ignore_ascii_case(key))
+            })
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// 各文件类别的单元格格式
+    pub(crate) fn category_formats(&self) -> BTreeMap<String, Format> {
+        self.categories
+            .iter()
+            .map(|(name, category)| {
+                let style = CellStyle {
+                    background: category.background.clone(),
+                    font_color: category.font_color.clone(),
+                    ..CellStyle::default()
+                };
+                (name.clone(), self.format(&style))
+            })
+            .collect()
+    }
+
+    /// 按主题创建单元格格式：应用字体、边框及样式中设置的颜色
+    pub(crate) fn format(&self, style: &CellStyle) -> Format {
+        let border = match self.border {
+            Border::None => FormatBorder::None,
+            Border::Thin => FormatBorder::Thin,
+            Border::Medium => FormatBorder::Medium,
+            Border::Thick => FormatBorder::Thick,
+            Border::Dashed => FormatBorder::Dashed,
+            Border::Dotted => FormatBorder::Dotted,
+            Border::Double => FormatBorder::Double,
+            Border::Hair => FormatBorder::Hair,
+        };
+        let mut format = Format::new()
+            .set_font_name(&self.font)
+            .set_font_size(self.font_size)
+            .set_border(border);
+
+        if let Some(background) = &style.background {
+            format = format.set_background_color(background.as_str());
+        }
+        if let Some(font_color) = &style.font_color {
+            format = format.set_font_color(font_color.as_str());
+        }
+        if style.bold == Some(true) {
+            format = format.set_bold();
+        }
+        if style.italic == Some(true) {
+            format = format.set_italic();
+        }
+        if style.underline == Some(true) {
+            format = format.set_underline(FormatUnderline::Single);
+        }
+        format
+    }
+}
+
+/// 递归合并TOML表：子表逐项合并，其余值（含数组）直接覆盖
+fn merge_table(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overrides)) => {
+                merge_table(base, overrides)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_themes() {
+        for name in Theme::builtin_names() {
+            Theme::builtin(name).unwrap();
+        }
+        let dark = Theme::builtin("dark").unwrap();
+        assert_eq!(dark.header.background.as_deref(), Some("#1F1F1F"));
+        // 未覆盖的项目沿用light主题
+        assert_eq!(dark.header.bold, Some(true));
+        assert_eq!(dark.border, Border::Thin);
+
+        let theme = Theme::from_toml(
+            "base = \"corporate\"\nfont_size = 12.0\n[file]\nbackground = \"#FFFFFF\"\n",
+        )
+        .unwrap();
+        assert_eq!(theme.font, "Arial");
+        assert_eq!(theme.font_size, 12.0);
+        assert_eq!(theme.file.background.as_deref(), Some("#FFFFFF"));
+
+        assert!(Theme::from_toml("[file]\nbackground = \"red\"\n").is_err());
+        assert!(Theme::from_toml("[file]\ncolor = \"#FFFFFF\"\n").is_err());
+        assert!(Theme::load("no-such-theme").is_err());
+    }
+
+    #[test]
+    fn test_category() {
+        let theme = Theme::builtin("dark").unwrap();
+        // dark主题只覆盖颜色，扩展名沿用light主题
+        assert_eq!(
+            theme.categories["code"].background.as_deref(),
+            Some("#1E3A5F")
+        );
+        assert_eq!(theme.category("src/MAIN.RS"), Some("code"));
+        assert_eq!(theme.category("Makefile"), Some("config"));
+        assert_eq!(theme.category(".env"), Some("config"));
+        assert_eq!(theme.category("logo.png"), Some("images"));
+        assert_eq!(theme.category("LICENSE"), None);
+    }
+}
@@ -0,0 +1,118 @@
+//! 经典tree文本输出（reverse子命令）
+
+use anyhow::Context;
+use std::io::Write;
+
+use crate::error::Result;
+use crate::output::open_output;
+use crate::parser::TreeItem;
+
+/// tree文本生成器：按层级还原 "├──/└──" 缩进的目录树，末尾为tree风格的统计行
+#[derive(Debug, Default)]
+pub struct TreeTextGenerator;
+
+impl TreeTextGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 生成tree文本文件（"-"表示标准输出）
+    pub fn generate(&self, items: &[TreeItem], output_path: &str) -> Result<()> {
+        let mut writer = open_output(output_path)?;
+        writer
+            .write_all(self.render(items).as_bytes())
+            .and_then(|()| writer.flush())
+            .with_context(|| format!("无法保存tree文本: {output_path}"))?;
+        Ok(())
+    }
+
+    /// 渲染为tree文本：每个根目录一段（没有根目录名称时为 "."），统计行按实际项目数重新计算
+    pub fn render(&self, items: &[TreeItem]) -> String {
+        let entries: Vec<&TreeItem> = items
+            .iter()
+            .filter(|item| !item.name.starts_with("📊"))
+            .collect();
+
+        // 从后往前确定每个项目是否为同级的最后一项
+        let mut is_last = vec![true; entries.len()];
+        let mut has_next: Vec<bool> = Vec::new();
+        for (index, item) in entries.iter().enumerate().rev() {
+            let root_ends = entries
+                .get(index + 1)
+                .is_some_and(|next| next.root != item.root);
+            if root_ends {
+                has_next.clear();
+            }
+            let level = item.level.max(1);
+            // 更深层的记录属于其他目录，丢弃
+            has_next.resize(level, false);
+            is_last[index] = !has_next[level - 1];
+            has_next[level - 1] = true;
+        }
+
+        let mut content = String::new();
+        // 各层上级目录之后是否还有同级项目（决定是否画 "│"）
+        let mut continues: Vec<bool> = Vec::new();
+        for (index, item) in entries.iter().enumerate() {
+            if index == 0 || entries[index - 1].root != item.root {
+                if index > 0 {
+                    content.push('\n');
+                }
+                content.push_str(item.root.as_deref().unwrap_or("."));
+                content.push('\n');
+                continues.clear();
+            }
+            let level = item.level.max(1);
+            continues.resize(level - 1, false);
+            for &more in &continues {
+                content.push_str(if more { "│   " } else { "    " });
+            }
+            content.push_str(if is_last[index] {
+                "└── "
+            } else {
+                "├── "
+            });
+            content.push_str(&item.name);
+            if let Some(target) = &item.link_target {
+                content.push_str(&format!(" -> {target}"));
+            }
+            content.push('\n');
+            continues.push(!is_last[index]);
+        }
+
+        let files = entries.iter().filter(|item| item.is_file).count();
+        let dirs = entries.len() - files;
+        let plural = |count: usize, one: &str, many: &str| {
+            format!("{count} {}", if count == 1 { one } else { many })
+        };
+        content.push_str(&format!(
+            "\n{}, {}\n",
+            plural(dirs, "directory", "directories"),
+            plural(files, "file", "files")
+        ));
+        content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_render_tree_text() {
+        let input = ".\n├── src\n│   ├── bin\n│   │   └── main.rs\n│   └── lib.rs\n└── README.md\n\n2 directories, 3 files\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        assert_eq!(TreeTextGenerator::new().render(&items), input);
+
+        // 删除行后统计数随之变化
+        let items: Vec<TreeItem> = items
+            .into_iter()
+            .filter(|item| item.name != "README.md")
+            .collect();
+        assert_eq!(
+            TreeTextGenerator::new().render(&items),
+            ".\n└── src\n    ├── bin\n    │   └── main.rs\n    └── lib.rs\n\n2 directories, 2 files\n"
+        );
+    }
+}
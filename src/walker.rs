@@ -0,0 +1,336 @@
+//! 直接遍历文件系统构建层级（无需tree命令）
+
+use anyhow::Context;
+use ignore::WalkBuilder;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{bail, Result};
+use crate::git;
+use crate::parser::{EntryKind, TreeItem};
+
+/// 文件系统目录遍历器
+#[derive(Debug, Default)]
+pub struct DirWalker {
+    git: bool,             // 是否读取git状态及最后提交
+    use_gitignore: bool,   // 是否跳过.gitignore等忽略规则匹配的项目
+    follow_symlinks: bool, // 是否进入符号链接指向的目录
+}
+
+impl DirWalker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 读取各项目的git状态（已跟踪、未跟踪、已忽略等）、最后提交时间及作者
+    pub fn git(mut self) -> Self {
+        self.git = true;
+        self
+    }
+
+    /// 按.gitignore（含上级目录中的）、.git/info/exclude及全局忽略文件（core.excludesFile）跳过项目
+    pub fn use_gitignore(mut self) -> Self {
+        self.use_gitignore = true;
+        self
+    }
+
+    /// 进入符号链接指向的目录；指向上级目录的链接（按设备号和inode判断）标为循环，不再进入
+    pub fn follow_symlinks(mut self) -> Self {
+        self.follow_symlinks = true;
+        self
+    }
+
+    /// 遍历目录，返回与TreeParser::parse相同结构的项目列表
+    pub fn walk(&self, root: &Path, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        if !root.is_dir() {
+            bail!("不是有效的目录: {}", root.display());
+        }
+
+        let mut items: Vec<TreeItem> = Vec::new();
+
+        // 与tree一致：按名称排序，默认不跟随符号链接、不应用任何忽略规则
+        let mut builder = WalkBuilder::new(root);
+        builder
+            .standard_filters(false)
+            .hidden(!include_hidden)
+            .git_ignore(self.use_gitignore)
+            .git_global(self.use_gitignore)
+            .git_exclude(self.use_gitignore)
+            .parents(self.use_gitignore)
+            .require_git(false)
+            .follow_links(self.follow_symlinks)
+            .sort_by_file_name(|a, b| a.cmp(b));
+        // 与git一致，.git目录本身也不列出（-a时）
+        if self.use_gitignore {
+            builder.filter_entry(|entry| entry.file_name() != ".git");
+        }
+        let walker = builder.build();
+
+        for entry in walker {
+            let entry = match entry {
+                // 跳过根目录本身
+                Ok(entry) if entry.depth() == 0 => continue,
+                Ok(entry) => entry,
+                Err(err) => {
+                    // 无法读取的目录及符号链接循环不中断整个遍历，在对应项目上记录错误
+                    log::warn!("跳过无法访问的路径: {err}");
+                    let Some((path, full_path)) = error_path(&err)
+                        .and_then(|path| Some((path, self.relative_path(root, path).ok()?)))
+                    else {
+                        continue;
+                    };
+                    let error = match find_loop(&err) {
+                        Some(ancestor) => format!(
+                            "符号链接循环（指向上级目录 {}）",
+                            self.relative_path(root, ancestor)
+                                .ok()
+                                .filter(|ancestor| !ancestor.is_empty())
+                                .unwrap_or_else(|| ".".to_string())
+                        ),
+                        None => err
+                            .io_error()
+                            .map_or_else(|| err.to_string(), |io_err| io_err.to_string()),
+                    };
+                    if let Some(item) = items
+                        .iter_mut()
+                        .rev()
+                        .find(|item| item.full_path == full_path)
+                    {
+                        item.error = Some(error);
+                    } else if path.is_symlink() {
+                        // 跟随符号链接时，循环或目标不存在的链接本身不会作为项目返回
+                        items.push(TreeItem {
+                            name: path.file_name().map_or_else(String::new, |name| {
+                                name.to_string_lossy().into_owned()
+                            }),
+                            level: full_path.split('/').count(),
+                            // 指向目录的链接（循环）按目录统计，目标不存在的按文件
+                            is_file: !path.is_dir(),
+                            kind: EntryKind::Symlink,
+                            full_path,
+                            link_target: fs::read_link(path)
+                                .ok()
+                                .map(|target| target.to_string_lossy().into_owned()),
+                            error: Some(error),
+                            root: Some(root.display().to_string()),
+                            ..Default::default()
+                        });
+                    }
+                    continue;
+                }
+            };
+
+            let full_path = self.relative_path(root, entry.path())?;
+
+            let file_type = entry.file_type();
+            let kind = if file_type.is_some_and(|file_type| file_type.is_dir()) {
+                EntryKind::Directory
+            } else if entry.path_is_symlink() {
+                EntryKind::Symlink
+            } else {
+                EntryKind::File
+            };
+            let is_file = kind != EntryKind::Directory;
+            let size = if is_file {
+                entry.metadata().ok().map(|metadata| metadata.len())
+            } else {
+                None
+            };
+
+            let link_target = if entry.path_is_symlink() {
+                fs::read_link(entry.path())
+                    .ok()
+                    .map(|target| target.to_string_lossy().into_owned())
+            } else {
+                None
+            };
+
+            items.push(TreeItem {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                level: entry.depth(),
+                is_file,
+                kind,
+                full_path,
+                size,
+                link_target,
+                root: Some(root.display().to_string()),
+                ..Default::default()
+            });
+        }
+
+        if self.git {
+            git::annotate(root, &mut items)?;
+        }
+
+        let file_count = items.iter().filter(|item| item.is_file).count();
+        let dir_count = items.len() - file_count;
+        items.push(TreeItem::stats(&format!(
+            "{dir_count} directories, {file_count} files"
+        )));
+
+        Ok(items)
+    }
+
+    /// 计算相对于根目录的路径，统一使用 "/" 分隔
+    fn relative_path(&self, root: &Path, path: &Path) -> Result<String> {
+        let relative = path
+            .strip_prefix(root)
+            .with_context(|| format!("无法计算相对路径: {}", path.display()))?;
+        Ok(relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"))
+    }
+}
+
+/// 遍历错误对应的路径（符号链接循环时为链接本身）
+fn error_path(err: &ignore::Error) -> Option<&Path> {
+    match err {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::Loop { child, .. } => Some(child),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            error_path(err)
+        }
+        _ => None,
+    }
+}
+
+/// 符号链接循环指向的上级目录
+fn find_loop(err: &ignore::Error) -> Option<&Path> {
+    match err {
+        ignore::Error::Loop { ancestor, .. } => Some(ancestor),
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => find_loop(err),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 项目的(完整路径, 层级, 是否文件)，不含统计行
+    fn entries(items: &[TreeItem]) -> Vec<(&str, usize, bool)> {
+        items
+            .iter()
+            .filter(|item| !item.name.starts_with("📊"))
+            .map(|item| (item.full_path.as_str(), item.level, item.is_file))
+            .collect()
+    }
+
+    #[test]
+    fn test_walk() {
+        let root = std::env::temp_dir().join("tree_to_excel_walk_test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        fs::write(root.join("src/bin/tool.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+        fs::write(root.join("README.md"), "# readme").unwrap();
+        fs::write(root.join(".env"), "KEY=1").unwrap();
+
+        let items = DirWalker::new().walk(&root, false).unwrap();
+        let hidden = DirWalker::new().walk(&root, true).unwrap();
+        let not_dir = DirWalker::new().walk(&root.join("README.md"), false);
+        fs::remove_dir_all(&root).unwrap();
+
+        // 按名称排序，默认不含隐藏文件
+        assert_eq!(
+            entries(&items),
+            [
+                ("README.md", 1, true),
+                ("src", 1, false),
+                ("src/bin", 2, false),
+                ("src/bin/tool.rs", 3, true),
+                ("src/lib.rs", 2, true),
+            ]
+        );
+        assert_eq!(items[0].size, Some(8));
+        assert_eq!(items[1].size, None);
+        assert_eq!(
+            items.last().unwrap().name,
+            TreeItem::stats("2 directories, 3 files").name
+        );
+        assert_eq!(entries(&hidden)[0], (".env", 1, true));
+        assert!(not_dir.is_err());
+    }
+
+    #[test]
+    fn test_use_gitignore_without_git() {
+        let root = std::env::temp_dir().join("tree_to_excel_gitignore_test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n*.o\n").unwrap();
+        fs::write(root.join("target/debug/app"), "").unwrap();
+        fs::write(root.join("main.o"), "").unwrap();
+        fs::write(root.join("src/main.rs"), "").unwrap();
+
+        let all = DirWalker::new().walk(&root, false).unwrap();
+        let filtered = DirWalker::new().use_gitignore().walk(&root, false).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        // 默认不应用忽略规则
+        let paths: Vec<&str> = entries(&all).iter().map(|entry| entry.0).collect();
+        assert_eq!(
+            paths,
+            [
+                "main.o",
+                "src",
+                "src/main.rs",
+                "target",
+                "target/debug",
+                "target/debug/app"
+            ]
+        );
+        // 没有.git目录时.gitignore同样生效
+        let paths: Vec<&str> = entries(&filtered).iter().map(|entry| entry.0).collect();
+        assert_eq!(paths, ["src", "src/main.rs"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_follow_symlink_loop() {
+        let root = std::env::temp_dir().join("tree_to_excel_symlink_loop_test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("sub/a.txt"), "").unwrap();
+        std::os::unix::fs::symlink("..", root.join("sub/loop")).unwrap();
+
+        let plain = DirWalker::new().walk(&root, false).unwrap();
+        let followed = DirWalker::new()
+            .follow_symlinks()
+            .walk(&root, false)
+            .unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        // 默认不跟随，链接本身作为项目
+        let link = plain
+            .iter()
+            .find(|item| item.full_path == "sub/loop")
+            .unwrap();
+        assert_eq!(link.kind, EntryKind::Symlink);
+        assert_eq!(link.link_target.as_deref(), Some(".."));
+        assert!(link.error.is_none());
+
+        // 跟随时遍历能结束，循环记录在链接上
+        assert_eq!(
+            entries(&followed),
+            [
+                ("sub", 1, false),
+                ("sub/a.txt", 2, true),
+                ("sub/loop", 2, false)
+            ]
+        );
+        let link = followed
+            .iter()
+            .find(|item| item.full_path == "sub/loop")
+            .unwrap();
+        assert_eq!(link.kind, EntryKind::Symlink);
+        assert_eq!(
+            link.error.as_deref(),
+            Some("符号链接循环（指向上级目录 .）")
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! WebDAV目录列表（--webdav，需启用webdav特性）：逐层发送Depth: 1的PROPFIND请求
+
+use anyhow::Context;
+use chrono::{DateTime, Local, NaiveDateTime};
+use std::io::Read;
+use std::time::Duration;
+use url::Url;
+
+use crate::error::{bail, Result};
+use crate::flat::{build_tree, FlatEntry};
+use crate::parser::TreeItem;
+
+/// PROPFIND请求体：只请求类型、大小和修改时间
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/><d:getcontentlength/><d:getlastmodified/></d:prop></d:propfind>"#;
+
+/// multistatus响应中的一个资源
+#[derive(Debug, Clone, PartialEq)]
+struct DavResource {
+    href: String, // 服务器返回的地址（保持编码）
+    is_dir: bool,
+    size: Option<u64>,
+    modified: Option<NaiveDateTime>,
+}
+
+/// WebDAV目录读取器：凭据取自URL中的user:password@，
+/// 或WEBDAV_USER/WEBDAV_PASSWORD环境变量（优先）
+#[derive(Debug)]
+pub struct WebDavLister {
+    agent: ureq::Agent,
+}
+
+impl Default for WebDavLister {
+    fn default() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(60))
+                .build(),
+        }
+    }
+}
+
+impl WebDavLister {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 递归列出WebDAV目录，返回与TreeParser::parse相同结构的项目列表；
+    /// 无法读取的子目录记入错误
+    pub fn list(&self, url: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let mut root = Url::parse(url).with_context(|| format!("无效的WebDAV地址: {url}"))?;
+        if !matches!(root.scheme(), "http" | "https") {
+            bail!("WebDAV地址应以http://或https://开头: {url}");
+        }
+        if !root.path().ends_with('/') {
+            root.set_path(&format!("{}/", root.path()));
+        }
+        if let Ok(user) = std::env::var("WEBDAV_USER") {
+            let _ = root.set_username(&user);
+        }
+        if let Ok(password) = std::env::var("WEBDAV_PASSWORD") {
+            let _ = root.set_password(Some(&password));
+        }
+
+        let mut entries = Vec::new();
+        self.walk(&root, &root, include_hidden, &mut entries)
+            .with_context(|| format!("无法列出WebDAV目录: {url}"))?;
+
+        // 项目的根目录不含凭据
+        let mut display = root.clone();
+        let _ = display.set_username("");
+        let _ = display.set_password(None);
+        let display = display.to_string();
+        let display = display.trim_end_matches('/');
+        let mut items = build_tree(entries, include_hidden);
+        for item in items.iter_mut().filter(|item| !item.name.starts_with("📊")) {
+            item.root = Some(display.to_string());
+        }
+        Ok(items)
+    }
+
+    /// 深度优先列出dir，同一目录下按名称排序
+    fn walk(
+        &self,
+        root: &Url,
+        dir: &Url,
+        include_hidden: bool,
+        entries: &mut Vec<FlatEntry>,
+    ) -> Result<()> {
+        let mut resources = self.propfind(dir)?;
+        resources.sort_by(|a, b| a.href.cmp(&b.href));
+        let root_path = percent_decode(root.path());
+        let dir_path = percent_decode(dir.path());
+        for resource in resources {
+            let Ok(mut url) = dir.join(&resource.href) else {
+                continue;
+            };
+            // 绝对地址形式的href不带凭据，沿用根地址的凭据
+            let _ = url.set_username(root.username());
+            let _ = url.set_password(root.password());
+            let path = percent_decode(url.path());
+            if path.trim_end_matches('/') == dir_path.trim_end_matches('/') {
+                continue;
+            }
+            let Some(relative) = path.strip_prefix(&root_path) else {
+                continue;
+            };
+            let relative = relative.trim_end_matches('/');
+            let name = relative.rsplit('/').next().unwrap_or(relative);
+            if relative.is_empty() || (!include_hidden && name.starts_with('.')) {
+                continue;
+            }
+
+            let is_dir = resource.is_dir || resource.href.ends_with('/');
+            entries.push(FlatEntry {
+                is_dir,
+                size: resource.size.filter(|_| !is_dir),
+                modified: resource.modified,
+                ..FlatEntry::new(relative)
+            });
+            if is_dir {
+                if !url.path().ends_with('/') {
+                    url.set_path(&format!("{}/", url.path()));
+                }
+                let index = entries.len() - 1;
+                if let Err(err) = self.walk(root, &url, include_hidden, entries) {
+                    entries[index].error = Some(format!("{err:#}"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 对一个目录发送PROPFIND请求
+    fn propfind(&self, dir: &Url) -> Result<Vec<DavResource>> {
+        let response = self
+            .agent
+            .request_url("PROPFIND", dir)
+            .set("Depth", "1")
+            .set("Content-Type", "application/xml; charset=utf-8")
+            .send_string(PROPFIND_BODY);
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(401, _)) => {
+                bail!("认证失败，请在地址中提供user:password@，或设置WEBDAV_USER/WEBDAV_PASSWORD环境变量")
+            }
+            Err(ureq::Error::Status(code, response)) => {
+                bail!("服务器返回 {code} {}", response.status_text())
+            }
+            Err(err) => Err(err).context("请求失败")?,
+        };
+        let mut body = String::new();
+        response
+            .into_reader()
+            .read_to_string(&mut body)
+            .context("无法读取响应")?;
+        parse_multistatus(&body)
+    }
+}
+
+/// 解析PROPFIND返回的multistatus XML
+fn parse_multistatus(xml: &str) -> Result<Vec<DavResource>> {
+    let document = roxmltree::Document::parse(xml).context("无法解析PROPFIND响应")?;
+    let is_dav = |node: &roxmltree::Node, name: &str| {
+        node.is_element()
+            && node.tag_name().name() == name
+            && node.tag_name().namespace() == Some("DAV:")
+    };
+    let resources = document
+        .descendants()
+        .filter(|node| is_dav(node, "response"))
+        .filter_map(|response| {
+            let find = |name: &str| response.descendants().find(|node| is_dav(node, name));
+            let text = |name: &str| find(name).and_then(|node| node.text()).map(str::trim);
+            Some(DavResource {
+                href: text("href")?.to_string(),
+                is_dir: find("resourcetype")
+                    .is_some_and(|node| node.children().any(|child| is_dav(&child, "collection"))),
+                size: text("getcontentlength").and_then(|size| size.parse().ok()),
+                modified: text("getlastmodified")
+                    .and_then(|date| DateTime::parse_from_rfc2822(date).ok())
+                    .map(|date| date.with_timezone(&Local).naive_local()),
+            })
+        })
+        .collect();
+    Ok(resources)
+}
+
+/// 解码URL路径中的百分号编码
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multistatus() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<D:multistatus xmlns:D="DAV:">
+  <D:response>
+    <D:href>/dav/docs/</D:href>
+    <D:propstat><D:prop><D:resourcetype><D:collection/></D:resourcetype></D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status></D:propstat>
+  </D:response>
+  <D:response>
+    <D:href>/dav/docs/%E6%8A%A5%E5%91%8A%20v2.pdf</D:href>
+    <D:propstat><D:prop>
+      <D:resourcetype/>
+      <D:getcontentlength>2048</D:getcontentlength>
+      <D:getlastmodified>Mon, 15 Jan 2024 12:34:56 GMT</D:getlastmodified>
+    </D:prop><D:status>HTTP/1.1 200 OK</D:status></D:propstat>
+  </D:response>
+</D:multistatus>"#;
+        let resources = parse_multistatus(xml).unwrap();
+        assert_eq!(resources.len(), 2);
+        assert!(resources[0].is_dir);
+        assert_eq!(resources[1].size, Some(2048));
+        assert!(!resources[1].is_dir);
+        assert!(resources[1].modified.is_some());
+        assert_eq!(percent_decode(&resources[1].href), "/dav/docs/报告 v2.pdf");
+        assert_eq!(percent_decode("/a%2"), "/a%2");
+    }
+}
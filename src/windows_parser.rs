@@ -0,0 +1,187 @@
+//! Windows `tree /F` 输出解析
+
+use crate::error::Result;
+use crate::parser::{EntryKind, TreeItem};
+
+/// Windows tree.exe输出解析器
+///
+/// Windows的tree /F中目录以 "├───"/"└───"（/A时为 "+---"/"\---"）开头，
+/// 文件没有连接符，直接跟在缩进之后，因此可以据此准确区分文件和目录。
+#[derive(Debug, Default)]
+pub struct WindowsTreeParser;
+
+impl WindowsTreeParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 判断输入是否为Windows tree输出
+    pub fn detect(input: &str) -> bool {
+        input.lines().take(50).any(|line| {
+            let trimmed = line.trim();
+            trimmed.starts_with("Folder PATH listing")
+                || trimmed.starts_with("卷 ")
+                || Self::is_drive_root(trimmed)
+                || trimmed.contains("+---")
+                || trimmed.contains("\\---")
+                || trimmed.contains("├───")
+                || trimmed.contains("└───")
+        })
+    }
+
+    /// 解析Windows tree输出，返回与TreeParser::parse相同结构的项目列表
+    pub fn parse(&self, input: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let mut items = Vec::new();
+        let mut path_stack: Vec<String> = Vec::new();
+        let mut hidden_levels: Vec<usize> = Vec::new(); // 记录被过滤的隐藏目录的层级
+
+        for line in input.lines() {
+            let Some((level, name, is_file)) = self.parse_line(line) else {
+                continue;
+            };
+
+            // 清理过期的隐藏层级记录
+            hidden_levels.retain(|&hidden_level| hidden_level < level);
+            let in_hidden_dir = !hidden_levels.is_empty();
+
+            // 过滤隐藏目录/文件（以.开头的项目，如.git）
+            if !include_hidden && (name.starts_with('.') || in_hidden_dir) {
+                if name.starts_with('.') {
+                    hidden_levels.push(level);
+                }
+                continue;
+            }
+
+            path_stack.truncate(level.saturating_sub(1));
+            let full_path = if path_stack.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", path_stack.join("/"), name)
+            };
+            path_stack.push(name.clone());
+
+            items.push(TreeItem {
+                name,
+                level,
+                is_file,
+                kind: if is_file {
+                    EntryKind::File
+                } else {
+                    EntryKind::Directory
+                },
+                full_path,
+                ..Default::default()
+            });
+        }
+
+        // Windows tree不输出统计行，直接计算
+        let file_count = items.iter().filter(|item| item.is_file).count();
+        let dir_count = items.len() - file_count;
+        items.push(TreeItem::stats(&format!(
+            "{dir_count} directories, {file_count} files"
+        )));
+
+        Ok(items)
+    }
+
+    /// 解析单行，返回(层级, 名称, 是否为文件)
+    fn parse_line(&self, line: &str) -> Option<(usize, String, bool)> {
+        let trimmed = line.trim();
+        if trimmed.is_empty()
+            || Self::is_drive_root(trimmed)
+            || trimmed.starts_with("Folder PATH listing")
+            || trimmed.starts_with("Volume serial number")
+            || trimmed.starts_with("No subfolders exist")
+            || trimmed.starts_with("卷 ")
+            || trimmed.starts_with("卷序列号")
+            || trimmed.starts_with("没有子文件夹")
+        {
+            return None;
+        }
+
+        let chars: Vec<char> = line.trim_end().chars().collect();
+        let mut pos = 0;
+        let mut units = 0;
+
+        // 每级缩进固定4个字符："│   "、"|   " 或 "    "
+        while pos + 3 < chars.len()
+            && matches!(chars[pos], '│' | '|' | ' ')
+            && chars[pos + 1..pos + 4].iter().all(|c| *c == ' ')
+        {
+            units += 1;
+            pos += 4;
+        }
+
+        // 目录连接符："├───"、"└───"、"+---"、"\---"
+        if pos + 3 < chars.len()
+            && matches!(chars[pos], '├' | '└' | '+' | '\\')
+            && chars[pos + 1..pos + 4]
+                .iter()
+                .all(|c| matches!(c, '─' | '-'))
+        {
+            let name: String = chars[pos + 4..].iter().collect();
+            let name = name.trim();
+            return (!name.is_empty()).then(|| (units + 1, name.to_string(), false));
+        }
+
+        // 没有连接符的是文件，层级等于缩进级数
+        let name: String = chars[pos..].iter().collect();
+        let name = name.trim();
+        if name.is_empty() || units == 0 || name.chars().all(|c| matches!(c, '│' | '|')) {
+            return None;
+        }
+        Some((units, name.to_string(), true))
+    }
+
+    /// 判断是否为盘符根目录行（如 "C:." 或 "D:\\PROJECTS"）
+    fn is_drive_root(trimmed: &str) -> bool {
+        let mut chars = trimmed.chars();
+        matches!(
+            (chars.next(), chars.next()),
+            (Some(drive), Some(':')) if drive.is_ascii_alphabetic()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_windows_tree() {
+        let input = "Folder PATH listing for volume OS
+Volume serial number is 1234-ABCD
+C:.
+|   Makefile
+|
++---src
+|   |   main.rs
+|   |
+|   \\---bin
+|           tool
+|
+\\---docs
+        guide.md
+";
+        assert!(WindowsTreeParser::detect(input));
+
+        let items = WindowsTreeParser::new().parse(input, false).unwrap();
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.level, item.full_path.as_str(), item.is_file))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                (1, "Makefile", true),
+                (1, "src", false),
+                (2, "src/main.rs", true),
+                (2, "src/bin", false),
+                (3, "src/bin/tool", true),
+                (1, "docs", false),
+                (2, "docs/guide.md", true),
+                (0, "📊 统计: 3 directories, 4 files", false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! 读取本工具生成的xlsx（--baseline等），还原为项目列表
+
+use anyhow::Context;
+use calamine::{open_workbook, Data, Reader, Xlsx};
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::parser::{EntryKind, TreeItem};
+
+/// 表头中各列的位置（中英文表头均可识别）
+#[derive(Debug, Default)]
+struct HeaderColumns {
+    levels: Vec<usize>, // L1、L2…
+    path: Option<usize>,
+    name: Option<usize>,
+    root: Option<usize>,
+    kind: Option<usize>,
+    size: Option<usize>,
+    file_count: Option<usize>,
+}
+
+impl HeaderColumns {
+    fn new(headers: &[String]) -> Self {
+        let mut columns = Self::default();
+        let find = |names: [&str; 2]| {
+            headers
+                .iter()
+                .position(|header| names.contains(&header.as_str()))
+        };
+        columns.levels = headers
+            .iter()
+            .enumerate()
+            .filter(|(_, header)| {
+                header
+                    .strip_prefix('L')
+                    .is_some_and(|level| level.parse::<usize>().is_ok())
+            })
+            .map(|(col, _)| col)
+            .collect();
+        columns.path = find(["完整路径", "Full Path"]);
+        columns.name = find(["名称", "Name"]);
+        columns.root = find(["根目录", "Root"]);
+        columns.kind = find(["类型", "Type"]);
+        columns.size = find(["大小", "Size"]);
+        columns.file_count = find(["文件数", "Files"]);
+        columns
+    }
+}
+
+/// xlsx解析器：读取第一个工作表，按表头识别层级列、完整路径列等
+#[derive(Debug, Default)]
+pub struct XlsxTreeParser;
+
+impl XlsxTreeParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 解析xlsx文件，返回与TreeParser::parse相同结构的项目列表。
+    /// 路径优先按层级列（合并单元格或大纲布局中省略的上级目录沿用上方的行）还原，
+    /// 没有层级列时（扁平、缩进布局）使用完整路径列；
+    /// 类型按类型列判断，否则有下级项目、名称以"/"结尾或有文件数的行为目录
+    pub fn parse_file(&self, path: &Path) -> Result<Vec<TreeItem>> {
+        let mut workbook: Xlsx<_> =
+            open_workbook(path).with_context(|| format!("无法打开xlsx文件: {}", path.display()))?;
+        let sheet_name = workbook
+            .sheet_names()
+            .first()
+            .cloned()
+            .context("xlsx文件中没有工作表")?;
+        let range = workbook
+            .worksheet_range(&sheet_name)
+            .with_context(|| format!("无法读取工作表: {sheet_name}"))?;
+
+        let mut grid: Vec<Vec<String>> = range
+            .rows()
+            .map(|row| row.iter().map(cell_text).collect())
+            .collect();
+        // 合并单元格只有左上角有值，填充到整个范围
+        let (first_row, first_col) = range.start().unwrap_or((0, 0));
+        if let Some(Ok(merged)) = workbook.worksheet_merge_cells(&sheet_name) {
+            for region in merged {
+                let row = (region.start.0 - first_row) as usize;
+                let col = (region.start.1 - first_col) as usize;
+                let Some(value) = grid.get(row).and_then(|cells| cells.get(col)).cloned() else {
+                    continue;
+                };
+                for row in region.start.0..=region.end.0 {
+                    for col in region.start.1..=region.end.1 {
+                        let cell = grid
+                            .get_mut((row - first_row) as usize)
+                            .and_then(|cells| cells.get_mut((col - first_col) as usize));
+                        if let Some(cell) = cell {
+                            cell.clone_from(&value);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut rows = grid.into_iter();
+        let headers = rows.next().unwrap_or_default();
+        let columns = HeaderColumns::new(&headers);
+        if columns.levels.is_empty() && columns.path.is_none() {
+            return Err(Error::parse(
+                Some(1),
+                format!("工作表 {sheet_name} 中没有层级列（L1…）或完整路径列"),
+            ));
+        }
+
+        let cell = |row: &[String], col: Option<usize>| -> String {
+            col.and_then(|col| row.get(col))
+                .map_or(String::new(), |value| value.trim().to_string())
+        };
+        let mut items = Vec::new();
+        let mut stack: Vec<String> = Vec::new();
+        for row in rows {
+            // 跳过统计行及空行
+            let first = row.iter().find(|value| !value.trim().is_empty());
+            let Some(first) = first else {
+                continue;
+            };
+            if first.starts_with("📊") {
+                continue;
+            }
+
+            let levels: Vec<String> = columns
+                .levels
+                .iter()
+                .map(|&col| cell(&row, Some(col)))
+                .collect();
+            let full_path = match levels.iter().rposition(|level| !level.is_empty()) {
+                Some(depth) => {
+                    for (index, level) in levels[..depth].iter().enumerate() {
+                        if !level.is_empty() {
+                            stack.truncate(index);
+                            stack.resize(index, String::new());
+                            stack.push(level.clone());
+                        }
+                    }
+                    stack.truncate(depth);
+                    stack.resize(depth, String::new());
+                    stack.push(levels[depth].clone());
+                    stack
+                        .iter()
+                        .map(|name| name.trim_end_matches('/'))
+                        .collect::<Vec<_>>()
+                        .join("/")
+                }
+                None => cell(&row, columns.path),
+            };
+            if full_path.is_empty() {
+                continue;
+            }
+            let name = match cell(&row, columns.name) {
+                name if !name.is_empty() && columns.levels.is_empty() => name,
+                _ => full_path.rsplit('/').next().unwrap_or_default().to_string(),
+            };
+            let last_level = levels.iter().rev().find(|level| !level.is_empty());
+            let marked_dir = last_level.is_some_and(|level| level.ends_with('/'))
+                || cell(&row, columns.path).ends_with('/')
+                || !cell(&row, columns.file_count).is_empty();
+            let kind = match cell(&row, columns.kind).as_str() {
+                "目录" | "Directory" => EntryKind::Directory,
+                "链接" | "Link" => EntryKind::Symlink,
+                "文件" | "File" => EntryKind::File,
+                _ if marked_dir => EntryKind::Directory,
+                _ => EntryKind::File,
+            };
+            let root = Some(cell(&row, columns.root)).filter(|root| !root.is_empty());
+            let size = cell(&row, columns.size)
+                .parse::<f64>()
+                .ok()
+                .map(|size| size as u64);
+
+            let full_path = full_path.trim_end_matches('/').to_string();
+            items.push(TreeItem {
+                name: name.trim_end_matches('/').to_string(),
+                level: full_path.split('/').count(),
+                is_file: kind != EntryKind::Directory,
+                kind,
+                full_path,
+                size,
+                root,
+                ..Default::default()
+            });
+        }
+
+        // 有下级项目的行是目录
+        for index in 0..items.len() {
+            let prefix = format!("{}/", items[index].full_path);
+            let has_children = items[index + 1..]
+                .iter()
+                .take_while(|item| item.root == items[index].root)
+                .any(|item| item.full_path.starts_with(&prefix));
+            if has_children {
+                items[index].is_file = false;
+                items[index].kind = EntryKind::Directory;
+            }
+        }
+
+        let file_count = items.iter().filter(|item| item.is_file).count();
+        let dir_count = items.len() - file_count;
+        items.push(TreeItem::stats(&format!(
+            "{dir_count} directories, {file_count} files"
+        )));
+        Ok(items)
+    }
+}
+
+/// 单元格的文本（整数不带小数点）
+fn cell_text(data: &Data) -> String {
+    match data {
+        Data::Empty => String::new(),
+        Data::Float(value) if value.fract() == 0.0 => format!("{value:.0}"),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::excel::ExcelGenerator;
+    use crate::parser::TreeParser;
+
+    #[test]
+    fn test_parse_generated_xlsx() {
+        let input = ".\n├── [        100]  README.md\n└── [       4096]  src\n    ├── [       4096]  bin\n    │   └── [         30]  main.rs\n    └── [         20]  lib.rs\n";
+        let items = TreeParser::new().parse(input, false).unwrap();
+        let path = std::env::temp_dir().join("tree_to_excel_xlsx_parser_test.xlsx");
+        ExcelGenerator::new(false)
+            .generate(items, path.to_str().unwrap())
+            .unwrap();
+
+        let items = XlsxTreeParser::new().parse_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let parsed: Vec<_> = items
+            .iter()
+            .map(|item| (item.full_path.as_str(), item.is_file, item.size))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("README.md", true, Some(100)),
+                ("src", false, Some(4096)),
+                ("src/bin", false, Some(4096)),
+                ("src/bin/main.rs", true, Some(30)),
+                ("src/lib.rs", true, Some(20)),
+                ("📊 统计: 2 directories, 3 files", false, None),
+            ]
+        );
+        assert_eq!(items[3].level, 3);
+    }
+}
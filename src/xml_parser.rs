@@ -0,0 +1,153 @@
+//! tree -X（XML）输出解析
+
+use anyhow::Context;
+use roxmltree::{Document, Node};
+
+use crate::error::Result;
+use crate::parser::{EntryKind, TreeItem};
+
+/// tree -X输出解析器
+#[derive(Debug, Default)]
+pub struct XmlTreeParser;
+
+impl XmlTreeParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 解析tree -X输出，返回与TreeParser::parse相同结构的项目列表
+    pub fn parse(&self, input: &str, include_hidden: bool) -> Result<Vec<TreeItem>> {
+        let document = Document::parse(input).context("无法解析tree -X输出的XML")?;
+
+        let mut items = Vec::new();
+        let mut stats_line = None;
+
+        for node in document.root_element().children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "report" => {
+                    let count = |tag: &str| {
+                        node.children()
+                            .find(|child| child.has_tag_name(tag))
+                            .and_then(|child| child.text())
+                            .map(|text| text.trim().to_string())
+                            .unwrap_or_else(|| "0".to_string())
+                    };
+                    stats_line = Some(format!(
+                        "{} directories, {} files",
+                        count("directories"),
+                        count("files")
+                    ));
+                }
+                // 顶层节点是根目录（如"."，tree -X dirA dirB时有多个），
+                // 与文本模式一样不作为层级展示，只记录在项目的root中
+                _ => {
+                    let start = items.len();
+                    self.collect(node, 1, "", include_hidden, &mut items);
+                    for item in &mut items[start..] {
+                        item.root = node.attribute("name").map(str::to_string);
+                    }
+                }
+            }
+        }
+
+        let file_count = items.iter().filter(|item| item.is_file).count();
+        let dir_count = items.len() - file_count;
+
+        let stats_text = match stats_line {
+            Some(stats_line) if include_hidden => stats_line,
+            _ => format!("{dir_count} directories, {file_count} files"),
+        };
+        items.push(TreeItem::stats(&stats_text));
+
+        Ok(items)
+    }
+
+    /// 递归收集子元素
+    fn collect(
+        &self,
+        parent: Node,
+        level: usize,
+        parent_path: &str,
+        include_hidden: bool,
+        items: &mut Vec<TreeItem>,
+    ) {
+        for node in parent.children().filter(Node::is_element) {
+            let Some(name) = node.attribute("name") else {
+                continue;
+            };
+
+            // 过滤隐藏目录/文件及其子项目
+            if !include_hidden && name.starts_with('.') {
+                continue;
+            }
+
+            let full_path = if parent_path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{parent_path}/{name}")
+            };
+
+            let kind = EntryKind::from_tree_type(node.tag_name().name());
+            items.push(TreeItem {
+                name: name.to_string(),
+                level,
+                is_file: kind != EntryKind::Directory,
+                kind,
+                full_path: full_path.clone(),
+                size: node.attribute("size").and_then(|size| size.parse().ok()),
+                permissions: node.attribute("prot").map(str::to_string),
+                owner: node.attribute("user").map(str::to_string),
+                group: node.attribute("group").map(str::to_string),
+                link_target: node.attribute("target").map(str::to_string),
+                // 无法打开的目录包含 <error>opening dir</error> 子元素
+                error: node
+                    .children()
+                    .find(|child| child.has_tag_name("error"))
+                    .and_then(|child| child.text())
+                    .map(|text| text.trim().to_string()),
+                ..Default::default()
+            });
+
+            self.collect(node, level + 1, &full_path, include_hidden, items);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_xml() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<tree>
+  <directory name=".">
+    <directory name="src">
+      <file name="main.rs" size="120"></file>
+    </directory>
+    <directory name=".git">
+      <file name="HEAD"></file>
+    </directory>
+  </directory>
+  <report>
+    <directories>2</directories>
+    <files>2</files>
+  </report>
+</tree>"#;
+        let items = XmlTreeParser::new().parse(input, false).unwrap();
+
+        let paths: Vec<_> = items.iter().map(|item| item.full_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["src", "src/main.rs", "📊 统计: 1 directories, 1 files"]
+        );
+        assert_eq!(items[1].level, 2);
+        assert_eq!(items[1].size, Some(120));
+
+        let items = XmlTreeParser::new().parse(input, true).unwrap();
+        assert_eq!(
+            items.last().unwrap().name,
+            "📊 统计: 2 directories, 2 files"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! 命令行程序的端到端测试
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// 写入临时输入文件，运行tree-to-excel并返回输出
+fn run(name: &str, input: &str, args: &[&str]) -> (Output, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("tree_to_excel_cli_{name}"));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input_path = dir.join("input.txt");
+    let output_path = dir.join("output.xlsx");
+    std::fs::write(&input_path, input).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_tree-to-excel"))
+        .arg("-i")
+        .arg(&input_path)
+        .arg("-o")
+        .arg(&output_path)
+        .args(args)
+        .env_remove("RUST_BACKTRACE")
+        .output()
+        .unwrap();
+    (output, output_path)
+}
+
+#[test]
+fn test_report_json_stdout() {
+    let input = ".\n├── a.rs\n└── src\n    └── main.rs\n\n1 directory, 2 files\n";
+
+    // 不加-q时标准输出也只有运行摘要，提示信息在标准错误
+    let (output, _) = run("report", input, &["--report", "json"]);
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["success"], true);
+    assert_eq!(report["items"], 3);
+    assert_eq!(report["rows_written"], 4);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("✅ 完成"), "{stderr}");
+
+    let (output, _) = run(
+        "report_low_memory",
+        input,
+        &["--report", "json", "--low-memory"],
+    );
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["items"], 3);
+    assert_eq!(report["rows_written"], 4);
+}
+
+#[test]
+fn test_strict_exit_code() {
+    let input = ".\n├── a.rs\nhello world\n└── src\n    └── main.rs\n";
+
+    // 默认只警告跳过的行
+    let (output, output_path) = run("lenient", input, &[]);
+    assert!(output.status.success());
+    assert!(output_path.exists());
+
+    // --strict以状态码3退出，并列出无法解析的行
+    let (output, _) = run("strict", input, &["--strict"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("第 3 行无法解析: hello world"), "{stderr}");
+}
+
+#[test]
+fn test_verify_report_on_failure() {
+    let dir = std::env::temp_dir().join("tree_to_excel_cli_verify");
+    let _ = std::fs::remove_dir_all(&dir);
+    let tree = dir.join("tree");
+    std::fs::create_dir_all(tree.join("src")).unwrap();
+    std::fs::write(tree.join("src/main.rs"), "fn main() {}").unwrap();
+    std::fs::write(tree.join("README.md"), "# readme").unwrap();
+    let xlsx = dir.join("inventory.xlsx");
+    let bin = env!("CARGO_BIN_EXE_tree-to-excel");
+    let output = Command::new(bin)
+        .arg("-p")
+        .arg(&tree)
+        .arg("-o")
+        .arg(&xlsx)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // 删除一个文件后校验失败，仍输出运行摘要并以状态码1退出
+    std::fs::remove_file(tree.join("README.md")).unwrap();
+    let output = Command::new(bin)
+        .arg("verify")
+        .arg(&xlsx)
+        .arg("-p")
+        .arg(&tree)
+        .args(["--report", "json"])
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["success"], false);
+    assert_eq!(report["error"], "校验失败：缺失 1，未登记 0，大小不符 0");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("缺失\tREADME.md"), "{stderr}");
+}